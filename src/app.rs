@@ -5,7 +5,7 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use crate::window::BlinkWindow;
-use crate::core::ColorConfig;
+use crate::core::{Archive, ColorConfig, Logger, Session, WindowState};
 
 const APP_ID: &str = "com.blink.fileexplorer";
 
@@ -23,11 +23,63 @@ impl BlinkApp {
         let css_provider_clone = css_provider.clone();
         app.connect_startup(move |_| {
             load_css_with_colors(&css_provider_clone);
+            std::thread::spawn(Archive::remove_stale_temp_files);
         });
 
-        app.connect_activate(|app| {
-            let window = BlinkWindow::new(app);
-            window.present();
+        // --no-restore starts with a fresh window instead of the previous session
+        app.add_main_option(
+            "no-restore",
+            glib::Char::from(0),
+            glib::OptionFlags::NONE,
+            glib::OptionArg::None,
+            "Don't reopen the windows from the last session",
+            None,
+        );
+        // --verbose turns on debug messages in the log
+        app.add_main_option(
+            "verbose",
+            glib::Char::from(b'v'),
+            glib::OptionFlags::NONE,
+            glib::OptionArg::None,
+            "Log debug messages",
+            None,
+        );
+        let restore = Rc::new(RefCell::new(true));
+        {
+            let restore = restore.clone();
+            app.connect_handle_local_options(move |_, options| {
+                // Options are parsed before startup, so this comes ahead of anything worth logging
+                Logger::init(options.contains("verbose"));
+                if options.contains("no-restore") {
+                    restore.replace(false);
+                }
+                -1
+            });
+        }
+
+        let windows: Rc<RefCell<TrackedWindows>> = Rc::new(RefCell::new(TrackedWindows::default()));
+        {
+            let windows = windows.clone();
+            app.connect_shutdown(move |_| save_session(&windows));
+        }
+        app.connect_activate(move |app| {
+            // Only the first window of a run restores the session
+            let states = if restore.replace(false) {
+                Session::load().windows.into_iter().filter_map(|state| state.pruned()).collect()
+            } else {
+                Vec::new()
+            };
+
+            let new_windows: Vec<BlinkWindow> = if states.is_empty() {
+                vec![BlinkWindow::new(app, None)]
+            } else {
+                states.into_iter().map(|state| BlinkWindow::new(app, Some(state))).collect()
+            };
+
+            for window in new_windows {
+                track_window(&windows, &window);
+                window.present();
+            }
         });
 
         // Start monitoring for color changes
@@ -46,6 +98,44 @@ impl BlinkApp {
     }
 }
 
+/// The windows of this run that are still open, and how the last one looked when it closed
+#[derive(Default)]
+struct TrackedWindows {
+    open: Vec<BlinkWindow>,
+    last_closed: Option<WindowState>,
+}
+
+/// Follow a window until it closes. Windows closed while others stay open are forgotten; the
+/// last one is remembered because closing it is what ends the app.
+fn track_window(windows: &Rc<RefCell<TrackedWindows>>, window: &BlinkWindow) {
+    windows.borrow_mut().open.push(window.clone());
+
+    let windows = windows.clone();
+    window.window.connect_close_request(move |closing| {
+        let mut windows = windows.borrow_mut();
+        if let Some(index) = windows.open.iter().position(|window| &window.window == closing) {
+            let window = windows.open.remove(index);
+            if windows.open.is_empty() {
+                windows.last_closed = Some(window.state());
+            }
+        }
+        glib::Propagation::Proceed
+    });
+}
+
+/// Save the windows open at quit, or the last one closed when that's what ended the app
+fn save_session(windows: &Rc<RefCell<TrackedWindows>>) {
+    let windows = windows.borrow();
+    let states: Vec<WindowState> = if windows.open.is_empty() {
+        windows.last_closed.iter().cloned().collect()
+    } else {
+        windows.open.iter().map(|window| window.state()).collect()
+    };
+    if let Err(e) = (Session { windows: states }).save() {
+        log::warn!("Failed to save session: {}", e);
+    }
+}
+
 fn load_css_with_colors(css_provider_rc: &Rc<RefCell<Option<CssProvider>>>) {
     let config = ColorConfig::load();
     
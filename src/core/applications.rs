@@ -0,0 +1,96 @@
+use std::path::{Path, PathBuf};
+
+use gtk4::gio;
+use gtk4::prelude::*;
+
+use crate::core::{RecentFiles, Remote};
+
+/// Desktop applications that can open files, as registered with GIO
+pub struct Applications;
+
+impl Applications {
+    /// Applications that can open every one of the given content types,
+    /// with the default application of the first type listed first
+    pub fn for_content_types(content_types: &[String]) -> Vec<gio::AppInfo> {
+        let Some((first, rest)) = content_types.split_first() else {
+            return Vec::new();
+        };
+
+        let mut apps = gio::AppInfo::all_for_type(first);
+        for content_type in rest {
+            let ids: Vec<_> = gio::AppInfo::all_for_type(content_type)
+                .iter()
+                .filter_map(|app| app.id())
+                .collect();
+            apps.retain(|app| app.id().is_some_and(|id| ids.contains(&id)));
+        }
+
+        if let Some(default_id) = gio::AppInfo::default_for_type(first, false).and_then(|app| app.id()) {
+            if let Some(index) = apps.iter().position(|app| app.id().as_ref() == Some(&default_id)) {
+                let default_app = apps.remove(index);
+                apps.insert(0, default_app);
+            }
+        }
+
+        apps
+    }
+
+    /// Look up an installed application by its desktop file id
+    pub fn find(id: &str) -> Option<gio::AppInfo> {
+        gio::AppInfo::all()
+            .into_iter()
+            .find(|app| app.id().as_deref() == Some(id))
+    }
+
+    /// Launch an application with files. GIO passes them all to one instance
+    /// for %F/%U and starts one instance per file for %f/%u.
+    /// Launched files are added to the recent files list.
+    pub fn launch(app: &gio::AppInfo, paths: &[PathBuf]) -> Result<(), gtk4::glib::Error> {
+        let files: Vec<gio::File> = paths.iter().map(|path| Remote::file(path)).collect();
+        let context = gtk4::gdk::Display::default().map(|display| display.app_launch_context());
+        app.launch(&files, context.as_ref())?;
+        for path in paths {
+            RecentFiles::add(path);
+        }
+        Ok(())
+    }
+
+    /// Open files with their default applications, one launch per application.
+    /// Returns a message for each launch that failed.
+    pub fn open_with_defaults(files: &[(PathBuf, String)]) -> Vec<String> {
+        let mut groups: Vec<(gio::AppInfo, Vec<PathBuf>)> = Vec::new();
+        let mut failures = Vec::new();
+
+        for (path, content_type) in files {
+            match gio::AppInfo::default_for_type(content_type, false) {
+                Some(app) => match groups.iter_mut().find(|(a, _)| a.id() == app.id()) {
+                    Some((_, paths)) => paths.push(path.clone()),
+                    None => groups.push((app, vec![path.clone()])),
+                },
+                None => {
+                    if let Err(e) = Self::open_fallback(path) {
+                        failures.push(format!("Failed to open {}: {}", path.display(), e));
+                    }
+                }
+            }
+        }
+
+        for (app, paths) in groups {
+            if let Err(e) = Self::launch(&app, &paths) {
+                failures.push(format!("Failed to launch {}: {}", app.display_name(), e));
+            }
+        }
+        failures
+    }
+
+    /// Make an application the default for a content type (written to mimeapps.list)
+    pub fn set_default(app: &gio::AppInfo, content_type: &str) -> Result<(), gtk4::glib::Error> {
+        app.set_as_default_for_type(content_type)
+    }
+
+    fn open_fallback(path: &Path) -> std::io::Result<()> {
+        open::that(path)?;
+        RecentFiles::add(path);
+        Ok(())
+    }
+}
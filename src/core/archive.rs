@@ -0,0 +1,783 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{Local, NaiveDate, TimeZone};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use xz2::read::XzDecoder;
+use xz2::write::XzEncoder;
+
+use crate::core::{FileEntry, FileOperations, Mime, ProgressInfo};
+
+/// Members extracted for opening in another app are removed after this long
+const TEMP_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+    TarXz,
+    SevenZip,
+}
+
+impl ArchiveFormat {
+    pub const ALL: [ArchiveFormat; 5] = [
+        ArchiveFormat::Zip,
+        ArchiveFormat::TarGz,
+        ArchiveFormat::TarXz,
+        ArchiveFormat::Tar,
+        ArchiveFormat::SevenZip,
+    ];
+
+    /// Detect the archive format from a file name
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveFormat::TarGz)
+        } else if name.ends_with(".tar.xz") || name.ends_with(".txz") {
+            Some(ArchiveFormat::TarXz)
+        } else if name.ends_with(".tar") {
+            Some(ArchiveFormat::Tar)
+        } else if name.ends_with(".zip") || name.ends_with(".jar") {
+            Some(ArchiveFormat::Zip)
+        } else if name.ends_with(".7z") {
+            Some(ArchiveFormat::SevenZip)
+        } else {
+            None
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => ".zip",
+            ArchiveFormat::Tar => ".tar",
+            ArchiveFormat::TarGz => ".tar.gz",
+            ArchiveFormat::TarXz => ".tar.xz",
+            ArchiveFormat::SevenZip => ".7z",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => ".zip (compatible with most systems)",
+            ArchiveFormat::Tar => ".tar (uncompressed)",
+            ArchiveFormat::TarGz => ".tar.gz (gzip)",
+            ArchiveFormat::TarXz => ".tar.xz (smaller, slower)",
+            ArchiveFormat::SevenZip => ".7z (7-Zip)",
+        }
+    }
+}
+
+/// A single file or directory stored inside an archive
+#[derive(Clone, Debug)]
+pub struct ArchiveMember {
+    pub path: PathBuf,
+    pub is_directory: bool,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+/// Reader wrapper that reports the number of bytes read to a ProgressInfo
+struct ProgressReader<R> {
+    inner: R,
+    progress: Option<Arc<Mutex<ProgressInfo>>>,
+}
+
+impl<R: Read> Read for ProgressReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes_read = self.inner.read(buf)?;
+        if let Some(progress_info) = &self.progress {
+            progress_info.lock().unwrap().bytes_copied += bytes_read as u64;
+        }
+        Ok(bytes_read)
+    }
+}
+
+pub struct Archive;
+
+impl Archive {
+    /// Check whether a path is an archive file that can be browsed and extracted
+    pub fn is_archive(path: &Path) -> bool {
+        ArchiveFormat::from_path(path).is_some() && path.is_file()
+    }
+
+    /// Split a path that points inside an archive into (archive file, path inside archive).
+    /// Returns None for regular filesystem paths.
+    pub fn split_path(path: &Path) -> Option<(PathBuf, PathBuf)> {
+        for ancestor in path.ancestors() {
+            if ancestor.is_dir() {
+                return None;
+            }
+
+            if ancestor.is_file() {
+                ArchiveFormat::from_path(ancestor)?;
+                let inner = path.strip_prefix(ancestor).ok()?.to_path_buf();
+                return Some((ancestor.to_path_buf(), inner));
+            }
+        }
+
+        None
+    }
+
+    /// List every member of an archive
+    pub fn list(archive: &Path) -> io::Result<Vec<ArchiveMember>> {
+        let format = Self::format_of(archive)?;
+        let mut members = Vec::new();
+
+        match format {
+            ArchiveFormat::Zip => {
+                let mut zip = zip::ZipArchive::new(fs::File::open(archive)?).map_err(to_io_error)?;
+                for i in 0..zip.len() {
+                    let file = zip.by_index(i).map_err(to_io_error)?;
+                    let Some(path) = file.enclosed_name() else { continue };
+                    members.push(ArchiveMember {
+                        path,
+                        is_directory: file.is_dir(),
+                        size: file.size(),
+                        modified: file.last_modified().and_then(zip_time_to_system_time),
+                    });
+                }
+            }
+            ArchiveFormat::Tar | ArchiveFormat::TarGz | ArchiveFormat::TarXz => {
+                let mut tar = tar::Archive::new(Self::tar_reader(archive, format)?);
+                for entry in tar.entries()? {
+                    let entry = entry?;
+                    let Some(path) = sanitize_member_path(&entry.path()?) else { continue };
+                    let header = entry.header();
+                    members.push(ArchiveMember {
+                        path,
+                        is_directory: header.entry_type().is_dir(),
+                        size: header.size().unwrap_or(0),
+                        modified: header.mtime().ok().map(|t| UNIX_EPOCH + Duration::from_secs(t)),
+                    });
+                }
+            }
+            ArchiveFormat::SevenZip => {
+                let reader = sevenz_rust::SevenZReader::open(archive, sevenz_rust::Password::empty())
+                    .map_err(to_io_error)?;
+                for entry in &reader.archive().files {
+                    let Some(path) = sanitize_member_path(Path::new(entry.name())) else { continue };
+                    members.push(ArchiveMember {
+                        path,
+                        is_directory: entry.is_directory(),
+                        size: entry.size(),
+                        modified: if entry.has_last_modified_date {
+                            Some(SystemTime::from(entry.last_modified_date()))
+                        } else {
+                            None
+                        },
+                    });
+                }
+            }
+        }
+
+        Ok(members)
+    }
+
+    /// List the direct children of a directory inside an archive as FileEntries,
+    /// so the archive can be browsed like a read-only folder.
+    pub fn list_dir(archive: &Path, inner: &Path, show_hidden: bool) -> io::Result<Vec<FileEntry>> {
+        let members = Self::list(archive)?;
+        let mut children: BTreeMap<String, FileEntry> = BTreeMap::new();
+
+        for member in &members {
+            let Ok(relative) = member.path.strip_prefix(inner) else { continue };
+            let mut components = relative.components();
+            let Some(first) = components.next() else { continue };
+            let name = first.as_os_str().to_string_lossy().to_string();
+
+            if !show_hidden && name.starts_with('.') {
+                continue;
+            }
+
+            // Members nested deeper imply a directory even if the archive has no entry for it
+            let is_nested = components.next().is_some();
+            let is_directory = is_nested || member.is_directory;
+
+            let entry = children.entry(name.clone()).or_insert_with(|| {
+                let mime = Mime::for_name(&name, is_directory);
+                FileEntry {
+                    name: name.clone(),
+                    path: archive.join(inner).join(&name),
+                    is_directory,
+                    is_hidden: name.starts_with('.'),
+                    mode: if is_directory { 0o755 } else { 0o644 },
+                    icon_name: mime.icon_name,
+                    content_type: mime.content_type,
+                    type_description: mime.description,
+                    ..Default::default()
+                }
+            });
+
+            if !is_nested {
+                let mime = Mime::for_name(&name, member.is_directory);
+                entry.is_directory = member.is_directory;
+                entry.size = if member.is_directory { 0 } else { member.size };
+                entry.icon_name = mime.icon_name;
+                entry.content_type = mime.content_type;
+                entry.type_description = mime.description;
+                entry.modified = member.modified;
+            }
+        }
+
+        let mut entries: Vec<FileEntry> = children.into_values().collect();
+        entries.sort_by_cached_key(|e| (!e.is_directory, e.name.to_lowercase()));
+        Ok(entries)
+    }
+
+    /// Total uncompressed size and file count of a member (or the whole archive when inner is empty)
+    pub fn member_size(archive: &Path, inner: &Path) -> (u64, usize) {
+        let Ok(members) = Self::list(archive) else { return (0, 0) };
+
+        members
+            .iter()
+            .filter(|m| !m.is_directory && m.path.starts_with(inner))
+            .fold((0, 0), |(size, count), m| (size + m.size, count + 1))
+    }
+
+    /// Pick the folder an archive should be extracted into when using "Extract Here".
+    /// Archives with a single top-level folder are extracted in place, everything else
+    /// goes into a new folder named after the archive.
+    pub fn extract_here_destination(archive: &Path) -> io::Result<PathBuf> {
+        let parent = archive.parent().unwrap_or_else(|| Path::new("/")).to_path_buf();
+        let members = Self::list(archive)?;
+
+        let mut top_level: Vec<&Path> = members
+            .iter()
+            .filter_map(|m| m.path.components().next())
+            .map(|c| Path::new(c.as_os_str()))
+            .collect();
+        top_level.sort();
+        top_level.dedup();
+
+        if top_level.len() == 1 {
+            let single = top_level[0];
+            let is_folder = members.iter().any(|m| m.path.starts_with(single) && m.path != single)
+                || members.iter().any(|m| m.path == single && m.is_directory);
+            if is_folder && !parent.join(single).exists() {
+                return Ok(parent);
+            }
+        }
+
+        let stem = Self::archive_stem(archive);
+        Ok(FileOperations::unique_path(&parent, &stem, ""))
+    }
+
+    /// File name of an archive without its format extension (e.g. "photos" for "photos.tar.gz")
+    pub fn archive_stem(archive: &Path) -> String {
+        let name = archive
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| String::from("Archive"));
+
+        let lower = name.to_lowercase();
+        for suffix in [".tar.gz", ".tar.xz", ".tgz", ".txz", ".tar", ".zip", ".jar", ".7z"] {
+            if lower.ends_with(suffix) && name.len() > suffix.len() {
+                return name[..name.len() - suffix.len()].to_string();
+            }
+        }
+
+        name
+    }
+
+    /// Extract the whole archive into a destination directory
+    pub fn extract(
+        archive: &Path,
+        destination: &Path,
+        progress: Option<Arc<Mutex<ProgressInfo>>>,
+    ) -> io::Result<()> {
+        fs::create_dir_all(destination)?;
+        Self::extract_filtered(archive, destination, progress, |member| Some(destination.join(member)))
+    }
+
+    /// Extract a single member (file or directory subtree) to a destination path
+    pub fn extract_member(
+        archive: &Path,
+        inner: &Path,
+        destination: &Path,
+        progress: Option<Arc<Mutex<ProgressInfo>>>,
+    ) -> io::Result<()> {
+        if inner.as_os_str().is_empty() {
+            return Self::extract(archive, destination, progress);
+        }
+
+        Self::extract_filtered(archive, destination, progress, |member| {
+            if member == inner {
+                Some(destination.to_path_buf())
+            } else {
+                member
+                    .strip_prefix(inner)
+                    .ok()
+                    .map(|relative| destination.join(relative))
+            }
+        })
+    }
+
+    /// Extract a member into a private temporary folder so it can be opened with another application
+    pub fn extract_to_temp(archive: &Path, inner: &Path) -> io::Result<PathBuf> {
+        let name = inner
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid member name"))?;
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let temp_dir = Self::temp_root().join(format!("{}-{}", std::process::id(), nanos));
+        fs::create_dir_all(&temp_dir)?;
+
+        let destination = temp_dir.join(name);
+        Self::extract_member(archive, inner, &destination, None)?;
+        Ok(destination)
+    }
+
+    /// Remove members extracted for opening more than a day ago; the apps they were opened in are
+    /// done with them by then
+    pub fn remove_stale_temp_files() {
+        let Ok(entries) = fs::read_dir(Self::temp_root()) else { return };
+        for entry in entries.flatten() {
+            let stale = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age >= TEMP_MAX_AGE);
+            if stale {
+                if let Err(e) = fs::remove_dir_all(entry.path()) {
+                    log::warn!("Failed to remove {}: {}", entry.path().display(), e);
+                }
+            }
+        }
+    }
+
+    fn temp_root() -> PathBuf {
+        std::env::temp_dir().join("blink-archives")
+    }
+
+    /// Compress files and folders into a new archive
+    pub fn compress(
+        sources: &[PathBuf],
+        destination: &Path,
+        format: ArchiveFormat,
+        progress: Option<Arc<Mutex<ProgressInfo>>>,
+    ) -> io::Result<()> {
+        let file = fs::File::create(destination)?;
+
+        let result = match format {
+            ArchiveFormat::Zip => Self::write_zip(file, sources, &progress),
+            ArchiveFormat::Tar => Self::write_tar(file, sources, &progress).map(|_| ()),
+            ArchiveFormat::TarGz => {
+                let encoder = GzEncoder::new(file, flate2::Compression::default());
+                Self::write_tar(encoder, sources, &progress)?.finish().map(|_| ())
+            }
+            ArchiveFormat::TarXz => {
+                let encoder = XzEncoder::new(file, 6);
+                Self::write_tar(encoder, sources, &progress)?.finish().map(|_| ())
+            }
+            ArchiveFormat::SevenZip => Self::write_7z(file, sources, &progress),
+        };
+
+        // Don't leave a truncated archive behind
+        if result.is_err() {
+            let _ = fs::remove_file(destination);
+        }
+
+        result
+    }
+
+    fn format_of(archive: &Path) -> io::Result<ArchiveFormat> {
+        ArchiveFormat::from_path(archive).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Unsupported archive format: {:?}", archive),
+            )
+        })
+    }
+
+    fn tar_reader(archive: &Path, format: ArchiveFormat) -> io::Result<Box<dyn Read>> {
+        let file = io::BufReader::new(fs::File::open(archive)?);
+        Ok(match format {
+            ArchiveFormat::TarGz => Box::new(GzDecoder::new(file)),
+            ArchiveFormat::TarXz => Box::new(XzDecoder::new(file)),
+            _ => Box::new(file),
+        })
+    }
+
+    /// Extract every member for which `target` returns a destination path inside `root`
+    fn extract_filtered<F>(
+        archive: &Path,
+        root: &Path,
+        progress: Option<Arc<Mutex<ProgressInfo>>>,
+        target: F,
+    ) -> io::Result<()>
+    where
+        F: Fn(&Path) -> Option<PathBuf>,
+    {
+        let format = Self::format_of(archive)?;
+
+        match format {
+            ArchiveFormat::Zip => {
+                let mut zip = zip::ZipArchive::new(fs::File::open(archive)?).map_err(to_io_error)?;
+                for i in 0..zip.len() {
+                    let mut file = zip.by_index(i).map_err(to_io_error)?;
+                    let Some(member) = file.enclosed_name() else { continue };
+                    let Some(out_path) = target(&member) else { continue };
+                    check_contained(root, &out_path)?;
+
+                    if file.is_dir() {
+                        fs::create_dir_all(&out_path)?;
+                        continue;
+                    }
+
+                    let mode = file.unix_mode();
+                    Self::write_member(&mut file, &member, &out_path, &progress)?;
+
+                    if let Some(mode) = mode {
+                        use std::os::unix::fs::PermissionsExt;
+                        let _ = fs::set_permissions(&out_path, fs::Permissions::from_mode(mode & 0o7777));
+                    }
+                }
+            }
+            ArchiveFormat::Tar | ArchiveFormat::TarGz | ArchiveFormat::TarXz => {
+                let mut tar = tar::Archive::new(Self::tar_reader(archive, format)?);
+                for entry in tar.entries()? {
+                    let mut entry = entry?;
+                    let Some(member) = sanitize_member_path(&entry.path()?) else { continue };
+                    let Some(out_path) = target(&member) else { continue };
+                    check_contained(root, &out_path)?;
+                    let entry_type = entry.header().entry_type();
+
+                    if entry_type.is_dir() {
+                        fs::create_dir_all(&out_path)?;
+                    } else if entry_type.is_file() {
+                        let mode = entry.header().mode().ok();
+                        Self::write_member(&mut entry, &member, &out_path, &progress)?;
+
+                        if let Some(mode) = mode {
+                            use std::os::unix::fs::PermissionsExt;
+                            let _ = fs::set_permissions(&out_path, fs::Permissions::from_mode(mode & 0o7777));
+                        }
+                    } else if entry_type.is_symlink() {
+                        let Some(link) = entry.link_name()? else { continue };
+                        let parent = out_path.parent().unwrap_or(root);
+                        // A link out of the destination would let later members be written anywhere
+                        if !resolve_lexically(parent, &link).is_some_and(|resolved| resolved.starts_with(root)) {
+                            log::warn!("Skipped {}: it links outside the destination", member.display());
+                            continue;
+                        }
+                        fs::create_dir_all(parent)?;
+                        std::os::unix::fs::symlink(&link, &out_path)?;
+                    } else if entry_type.is_hard_link() {
+                        // Hard link targets name another member, so map them the same way
+                        let Some(link) = entry.link_name()? else { continue };
+                        let Some(source) = sanitize_member_path(&link).and_then(|link| target(&link)) else {
+                            log::warn!("Skipped {}: it links outside the destination", member.display());
+                            continue;
+                        };
+                        check_contained(root, &source)?;
+                        if let Some(parent) = out_path.parent() {
+                            fs::create_dir_all(parent)?;
+                        }
+                        fs::hard_link(&source, &out_path)?;
+                    } else {
+                        log::debug!("Skipped special member {}", member.display());
+                    }
+                }
+            }
+            ArchiveFormat::SevenZip => {
+                let mut reader = sevenz_rust::SevenZReader::open(archive, sevenz_rust::Password::empty())
+                    .map_err(to_io_error)?;
+                let mut write_error: Option<io::Error> = None;
+
+                reader
+                    .for_each_entries(|entry, data| {
+                        let Some(member) = sanitize_member_path(Path::new(entry.name())) else {
+                            return Ok(true);
+                        };
+                        let Some(out_path) = target(&member) else {
+                            // The reader still has to consume the data to reach the next entry
+                            io::copy(data, &mut io::sink()).map_err(sevenz_rust::Error::io)?;
+                            return Ok(true);
+                        };
+
+                        let result = if let Err(e) = check_contained(root, &out_path) {
+                            Err(e)
+                        } else if entry.is_directory() {
+                            fs::create_dir_all(&out_path)
+                        } else {
+                            Self::write_member(data, &member, &out_path, &progress)
+                        };
+
+                        match result {
+                            Ok(()) => Ok(true),
+                            Err(e) => {
+                                write_error = Some(e);
+                                Ok(false)
+                            }
+                        }
+                    })
+                    .map_err(to_io_error)?;
+
+                if let Some(e) = write_error {
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write a single member's data to disk, reporting progress
+    fn write_member<R: Read + ?Sized>(
+        data: &mut R,
+        member: &Path,
+        out_path: &Path,
+        progress: &Option<Arc<Mutex<ProgressInfo>>>,
+    ) -> io::Result<()> {
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        if let Some(progress_info) = progress {
+            let mut prog = progress_info.lock().unwrap();
+            prog.current_file = member
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+        }
+
+        let mut out_file = fs::File::create(out_path)?;
+        let mut reader = ProgressReader {
+            inner: data,
+            progress: progress.clone(),
+        };
+        io::copy(&mut reader, &mut out_file)?;
+
+        if let Some(progress_info) = progress {
+            progress_info.lock().unwrap().files_copied += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Collect (source path, name inside archive) pairs for everything under the given sources
+    fn collect_sources(sources: &[PathBuf]) -> io::Result<Vec<(PathBuf, PathBuf)>> {
+        fn walk(path: &Path, name: &Path, out: &mut Vec<(PathBuf, PathBuf)>) -> io::Result<()> {
+            out.push((path.to_path_buf(), name.to_path_buf()));
+
+            let metadata = fs::symlink_metadata(path)?;
+            if metadata.is_dir() {
+                let mut children: Vec<_> = fs::read_dir(path)?.filter_map(|e| e.ok()).collect();
+                children.sort_by_key(|e| e.file_name());
+                for child in children {
+                    walk(&child.path(), &name.join(child.file_name()), out)?;
+                }
+            }
+
+            Ok(())
+        }
+
+        let mut all = Vec::new();
+        for source in sources {
+            let Some(name) = source.file_name() else { continue };
+            walk(source, Path::new(name), &mut all)?;
+        }
+        Ok(all)
+    }
+
+    fn start_source_file(
+        path: &Path,
+        progress: &Option<Arc<Mutex<ProgressInfo>>>,
+    ) -> io::Result<ProgressReader<fs::File>> {
+        if let Some(progress_info) = progress {
+            let mut prog = progress_info.lock().unwrap();
+            prog.current_file = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+        }
+
+        Ok(ProgressReader {
+            inner: fs::File::open(path)?,
+            progress: progress.clone(),
+        })
+    }
+
+    fn finish_source_file(progress: &Option<Arc<Mutex<ProgressInfo>>>) {
+        if let Some(progress_info) = progress {
+            progress_info.lock().unwrap().files_copied += 1;
+        }
+    }
+
+    fn write_zip(
+        file: fs::File,
+        sources: &[PathBuf],
+        progress: &Option<Arc<Mutex<ProgressInfo>>>,
+    ) -> io::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mut zip = zip::ZipWriter::new(file);
+
+        for (path, name) in Self::collect_sources(sources)? {
+            // Store links as links rather than copying in whatever they point to
+            let metadata = fs::symlink_metadata(&path)?;
+            let name = name.to_string_lossy().to_string();
+            let options = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated)
+                .unix_permissions(metadata.permissions().mode())
+                .large_file(metadata.len() >= u32::MAX as u64);
+
+            if metadata.file_type().is_symlink() {
+                let target = fs::read_link(&path)?;
+                zip.add_symlink(name, target.to_string_lossy(), options).map_err(to_io_error)?;
+            } else if metadata.is_dir() {
+                zip.add_directory(format!("{}/", name), options).map_err(to_io_error)?;
+            } else {
+                zip.start_file(name, options).map_err(to_io_error)?;
+                let mut reader = Self::start_source_file(&path, progress)?;
+                io::copy(&mut reader, &mut zip)?;
+                Self::finish_source_file(progress);
+            }
+        }
+
+        zip.finish().map_err(to_io_error)?;
+        Ok(())
+    }
+
+    fn write_tar<W: Write>(
+        writer: W,
+        sources: &[PathBuf],
+        progress: &Option<Arc<Mutex<ProgressInfo>>>,
+    ) -> io::Result<W> {
+        let mut builder = tar::Builder::new(writer);
+
+        for (path, name) in Self::collect_sources(sources)? {
+            let metadata = fs::symlink_metadata(&path)?;
+            let mut header = tar::Header::new_gnu();
+            header.set_metadata(&metadata);
+
+            if metadata.file_type().is_symlink() {
+                let target = fs::read_link(&path)?;
+                header.set_size(0);
+                builder.append_link(&mut header, &name, &target)?;
+            } else if metadata.is_dir() {
+                builder.append_data(&mut header, &name, io::empty())?;
+            } else {
+                let reader = Self::start_source_file(&path, progress)?;
+                builder.append_data(&mut header, &name, reader)?;
+                Self::finish_source_file(progress);
+            }
+        }
+
+        builder.into_inner()
+    }
+
+    fn write_7z(
+        file: fs::File,
+        sources: &[PathBuf],
+        progress: &Option<Arc<Mutex<ProgressInfo>>>,
+    ) -> io::Result<()> {
+        let mut writer = sevenz_rust::SevenZWriter::new(file).map_err(to_io_error)?;
+
+        for (path, name) in Self::collect_sources(sources)? {
+            let entry = sevenz_rust::SevenZArchiveEntry::from_path(&path, name.to_string_lossy().to_string());
+
+            if path.is_dir() {
+                writer
+                    .push_archive_entry::<fs::File>(entry, None)
+                    .map_err(to_io_error)?;
+            } else {
+                let reader = Self::start_source_file(&path, progress)?;
+                writer
+                    .push_archive_entry(entry, Some(reader))
+                    .map_err(to_io_error)?;
+                Self::finish_source_file(progress);
+            }
+        }
+
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+/// Reject absolute paths and `..` components so extraction can't escape the destination
+fn sanitize_member_path(path: &Path) -> Option<PathBuf> {
+    let mut clean = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => clean.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+
+    if clean.as_os_str().is_empty() {
+        None
+    } else {
+        Some(clean)
+    }
+}
+
+/// Refuse to write `path` unless it lies inside `root` without passing through a symlink,
+/// which an earlier member could have planted to redirect the write
+fn check_contained(root: &Path, path: &Path) -> io::Result<()> {
+    let outside = || {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} would be written outside the destination", path.display()),
+        )
+    };
+    let relative = path.strip_prefix(root).map_err(|_| outside())?;
+
+    let mut current = root.to_path_buf();
+    for component in relative.components() {
+        let Component::Normal(part) = component else { return Err(outside()) };
+        current.push(part);
+        match fs::symlink_metadata(&current) {
+            Ok(metadata) if metadata.file_type().is_symlink() => return Err(outside()),
+            Ok(_) => {}
+            // Nothing further down exists yet, so nothing can redirect the write
+            Err(e) if e.kind() == io::ErrorKind::NotFound => break,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// Where `link` points when followed from `dir`, without touching the disk; `None` for absolute links
+fn resolve_lexically(dir: &Path, link: &Path) -> Option<PathBuf> {
+    let mut resolved = dir.to_path_buf();
+    for component in link.components() {
+        match component {
+            Component::Normal(part) => resolved.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !resolved.pop() {
+                    return None;
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(resolved)
+}
+
+fn zip_time_to_system_time(time: zip::DateTime) -> Option<SystemTime> {
+    let naive = NaiveDate::from_ymd_opt(time.year() as i32, time.month() as u32, time.day() as u32)?
+        .and_hms_opt(time.hour() as u32, time.minute() as u32, time.second() as u32)?;
+    Local
+        .from_local_datetime(&naive)
+        .single()
+        .map(SystemTime::from)
+}
+
+fn to_io_error<E: std::fmt::Display>(error: E) -> io::Error {
+    io::Error::other(error.to_string())
+}
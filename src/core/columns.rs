@@ -0,0 +1,267 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{format_size, format_time, FileEntry, FileProperties};
+
+const COLUMNS_FILE: &str = "columns.json";
+
+/// Items in a folder for the Items column, counted in the background when the row is first shown
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ItemCount {
+    #[default]
+    Unknown,
+    Counting,
+    /// `None` when the folder can't be read
+    Counted(Option<usize>),
+}
+
+/// Columns the list view can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListColumn {
+    Name,
+    Size,
+    Type,
+    Modified,
+    Created,
+    Accessed,
+    Permissions,
+    Owner,
+    Group,
+    ItemCount,
+    Path,
+}
+
+impl ListColumn {
+    pub const ALL: [ListColumn; 11] = [
+        ListColumn::Name,
+        ListColumn::Size,
+        ListColumn::Type,
+        ListColumn::Modified,
+        ListColumn::Created,
+        ListColumn::Accessed,
+        ListColumn::Permissions,
+        ListColumn::Owner,
+        ListColumn::Group,
+        ListColumn::ItemCount,
+        ListColumn::Path,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ListColumn::Name => "Name",
+            ListColumn::Size => "Size",
+            ListColumn::Type => "Type",
+            ListColumn::Modified => "Modified",
+            ListColumn::Created => "Created",
+            ListColumn::Accessed => "Accessed",
+            ListColumn::Permissions => "Permissions",
+            ListColumn::Owner => "Owner",
+            ListColumn::Group => "Group",
+            ListColumn::ItemCount => "Items",
+            ListColumn::Path => "Location",
+        }
+    }
+
+    fn default_width(&self) -> i32 {
+        match self {
+            ListColumn::Name => 320,
+            ListColumn::Size | ListColumn::ItemCount => 90,
+            ListColumn::Permissions => 110,
+            ListColumn::Owner | ListColumn::Group => 100,
+            ListColumn::Type => 150,
+            ListColumn::Path => 260,
+            ListColumn::Modified | ListColumn::Created | ListColumn::Accessed => 150,
+        }
+    }
+
+    fn visible_by_default(&self) -> bool {
+        matches!(self, ListColumn::Name | ListColumn::Size | ListColumn::Type | ListColumn::Modified)
+    }
+
+    /// Numbers line up on the right
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, ListColumn::Size | ListColumn::ItemCount)
+    }
+
+    /// Cell text for an entry. Folders have no item count here; the view fills it in from
+    /// `count_items` once that has run off the UI thread.
+    pub fn text(&self, entry: &FileEntry) -> String {
+        let time = |time: Option<std::time::SystemTime>| {
+            time.map(format_time).unwrap_or_else(|| String::from("Unknown"))
+        };
+
+        match self {
+            ListColumn::Name => entry.name.clone(),
+            ListColumn::Size => {
+                if entry.is_directory {
+                    String::from("--")
+                } else {
+                    format_size(entry.size)
+                }
+            }
+            ListColumn::Type => entry.type_description.clone(),
+            ListColumn::Modified => time(entry.modified),
+            ListColumn::Created => time(entry.created),
+            ListColumn::Accessed => time(entry.accessed),
+            ListColumn::Permissions => entry.permissions_display(),
+            ListColumn::Owner => Self::cached_name(entry.uid, true),
+            ListColumn::Group => Self::cached_name(entry.gid, false),
+            ListColumn::ItemCount => {
+                if entry.is_directory {
+                    String::new()
+                } else {
+                    String::from("--")
+                }
+            }
+            ListColumn::Path => entry
+                .path
+                .parent()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Read a folder to count its items. Blocks, so call it off the UI thread.
+    pub fn count_items(path: &Path) -> Option<usize> {
+        fs::read_dir(path).ok().map(|children| children.count())
+    }
+
+    pub fn item_count_text(count: Option<usize>) -> String {
+        match count {
+            Some(1) => String::from("1 item"),
+            Some(n) => format!("{} items", n),
+            None => String::from("--"),
+        }
+    }
+
+    /// User and group lookups go through NSS, so remember them for the session
+    fn cached_name(id: u32, is_user: bool) -> String {
+        thread_local! {
+            static NAMES: RefCell<HashMap<(u32, bool), String>> = RefCell::new(HashMap::new());
+        }
+
+        NAMES.with(|names| {
+            names
+                .borrow_mut()
+                .entry((id, is_user))
+                .or_insert_with(|| {
+                    if is_user {
+                        FileProperties::user_name(id)
+                    } else {
+                        FileProperties::group_name(id)
+                    }
+                })
+                .clone()
+        })
+    }
+}
+
+/// Visibility and width of one list column
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnSetting {
+    pub column: ListColumn,
+    pub visible: bool,
+    pub width: i32,
+}
+
+/// Order, visibility and widths of the list view columns, saved in ~/.config/blink/columns.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnLayout {
+    pub columns: Vec<ColumnSetting>,
+}
+
+impl Default for ColumnLayout {
+    fn default() -> Self {
+        Self {
+            columns: ListColumn::ALL
+                .iter()
+                .map(|column| ColumnSetting {
+                    column: *column,
+                    visible: column.visible_by_default(),
+                    width: column.default_width(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ColumnLayout {
+    fn config_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("blink")
+            .join(COLUMNS_FILE)
+    }
+
+    pub fn load() -> Self {
+        let mut layout = match fs::read_to_string(Self::config_path()) {
+            Ok(content) => serde_json::from_str::<ColumnLayout>(&content).unwrap_or_else(|e| {
+                log::warn!("Failed to parse column layout: {}", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        };
+        layout.normalize();
+        layout
+    }
+
+    pub fn save(&self) -> Result<(), std::io::Error> {
+        let config_path = Self::config_path();
+        if let Some(parent) = config_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        fs::write(&config_path, content)
+    }
+
+    /// Drop duplicates, append columns added since the file was written, and keep Name visible
+    fn normalize(&mut self) {
+        let mut seen = Vec::new();
+        self.columns.retain(|setting| {
+            let first = !seen.contains(&setting.column);
+            seen.push(setting.column);
+            first
+        });
+
+        for column in ListColumn::ALL {
+            if !seen.contains(&column) {
+                self.columns.push(ColumnSetting {
+                    column,
+                    visible: false,
+                    width: column.default_width(),
+                });
+            }
+        }
+
+        for setting in &mut self.columns {
+            if setting.column == ListColumn::Name {
+                setting.visible = true;
+            }
+            if setting.width <= 0 {
+                setting.width = setting.column.default_width();
+            }
+        }
+    }
+
+    pub fn setting_mut(&mut self, column: ListColumn) -> Option<&mut ColumnSetting> {
+        self.columns.iter_mut().find(|setting| setting.column == column)
+    }
+
+    /// Move a column one place up (negative) or down (positive)
+    pub fn move_column(&mut self, column: ListColumn, offset: i32) {
+        let Some(index) = self.columns.iter().position(|setting| setting.column == column) else {
+            return;
+        };
+        let target = index as i32 + offset;
+        if target < 0 || target >= self.columns.len() as i32 {
+            return;
+        }
+        self.columns.swap(index, target as usize);
+    }
+}
@@ -155,14 +155,14 @@ impl KeybindConfig {
                 Err(_) => {
                     // If read fails, use defaults and save them
                     if let Err(e) = Self::save(&keybinds) {
-                        eprintln!("Failed to save default keybinds: {}", e);
+                        log::warn!("Failed to save default keybinds: {}", e);
                     }
                 }
             }
         } else {
             // Save defaults
             if let Err(e) = Self::save(&keybinds) {
-                eprintln!("Failed to save default keybinds: {}", e);
+                log::warn!("Failed to save default keybinds: {}", e);
             }
         }
 
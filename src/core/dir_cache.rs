@@ -0,0 +1,128 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+use crate::core::SortKey;
+
+/// Folders kept beyond this are dropped, least recently left first
+const MAX_CACHED_DIRECTORIES: usize = 16;
+/// Items across all cached folders; keeps a few huge folders from holding on to memory
+const MAX_CACHED_ITEMS: usize = 200_000;
+
+/// A folder's listing as it was when the view left it
+pub struct CachedListing<T> {
+    /// In display order for `sort_key` and `sort_descending`
+    pub items: Vec<T>,
+    pub show_hidden: bool,
+    pub sort_key: SortKey,
+    pub sort_descending: bool,
+    /// Hidden items left out
+    pub hidden: usize,
+    /// Vertical scroll position
+    pub scroll: f64,
+    pub selection: Vec<PathBuf>,
+}
+
+/// Listings of recently visited folders, so going back to one shows it at once
+pub struct DirectoryCache<T> {
+    /// Most recently stored first
+    listings: VecDeque<(PathBuf, CachedListing<T>)>,
+}
+
+impl<T> Default for DirectoryCache<T> {
+    fn default() -> Self {
+        Self { listings: VecDeque::new() }
+    }
+}
+
+impl<T> DirectoryCache<T> {
+    /// Remember a listing, replacing any older one of the same folder
+    pub fn insert(&mut self, path: PathBuf, listing: CachedListing<T>) {
+        self.remove(&path);
+        if listing.items.len() > MAX_CACHED_ITEMS {
+            return;
+        }
+        self.listings.push_front((path, listing));
+
+        let mut total: usize = self.listings.iter().map(|(_, listing)| listing.items.len()).sum();
+        while self.listings.len() > MAX_CACHED_DIRECTORIES || total > MAX_CACHED_ITEMS {
+            let Some((_, evicted)) = self.listings.pop_back() else { break };
+            total -= evicted.items.len();
+        }
+    }
+
+    /// Take the listing of a folder out of the cache; it is stored again when the view leaves it
+    pub fn take(&mut self, path: &Path) -> Option<CachedListing<T>> {
+        let position = self.listings.iter().position(|(cached, _)| cached == path)?;
+        self.listings.remove(position).map(|(_, listing)| listing)
+    }
+
+    pub fn remove(&mut self, path: &Path) {
+        self.listings.retain(|(cached, _)| cached != path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listing(items: usize) -> CachedListing<u32> {
+        CachedListing {
+            items: vec![0; items],
+            show_hidden: false,
+            sort_key: SortKey::Name,
+            sort_descending: false,
+            hidden: 0,
+            scroll: 0.0,
+            selection: Vec::new(),
+        }
+    }
+
+    fn folder(index: usize) -> PathBuf {
+        PathBuf::from(format!("/folder{}", index))
+    }
+
+    #[test]
+    fn least_recently_stored_folder_is_dropped_first() {
+        let mut cache = DirectoryCache::default();
+        for index in 0..MAX_CACHED_DIRECTORIES {
+            cache.insert(folder(index), listing(1));
+        }
+        // Storing the first folder again makes it the most recent one
+        let first = cache.take(&folder(0)).unwrap();
+        cache.insert(folder(0), first);
+        cache.insert(folder(MAX_CACHED_DIRECTORIES), listing(1));
+
+        assert!(cache.take(&folder(1)).is_none());
+        assert!(cache.take(&folder(0)).is_some());
+        assert!(cache.take(&folder(MAX_CACHED_DIRECTORIES)).is_some());
+    }
+
+    #[test]
+    fn storing_a_folder_again_replaces_it() {
+        let mut cache = DirectoryCache::default();
+        cache.insert(folder(0), listing(1));
+        cache.insert(folder(0), listing(2));
+        assert_eq!(cache.take(&folder(0)).unwrap().items.len(), 2);
+        assert!(cache.take(&folder(0)).is_none());
+    }
+
+    #[test]
+    fn item_limit_evicts_old_folders() {
+        let mut cache = DirectoryCache::default();
+        cache.insert(folder(0), listing(MAX_CACHED_ITEMS / 2));
+        cache.insert(folder(1), listing(MAX_CACHED_ITEMS / 2));
+        cache.insert(folder(2), listing(1));
+
+        assert!(cache.take(&folder(0)).is_none());
+        assert!(cache.take(&folder(1)).is_some());
+        assert!(cache.take(&folder(2)).is_some());
+    }
+
+    #[test]
+    fn folders_over_the_item_limit_are_not_kept() {
+        let mut cache = DirectoryCache::default();
+        cache.insert(folder(0), listing(1));
+        cache.insert(folder(0), listing(MAX_CACHED_ITEMS + 1));
+        assert!(cache.take(&folder(0)).is_none());
+    }
+}
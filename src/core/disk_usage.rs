@@ -0,0 +1,266 @@
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Upper bound on scan threads; directory reads are mostly waiting on the disk
+const MAX_SCAN_THREADS: usize = 8;
+
+/// One item in a directory listing
+#[derive(Clone, Debug)]
+pub struct UsageEntry {
+    pub name: OsString,
+    pub is_dir: bool,
+    /// Apparent size in bytes
+    pub size: u64,
+    /// Bytes taken on disk; zero for hard links already counted elsewhere
+    pub allocated: u64,
+}
+
+/// Contents of one directory as read by the scanner
+#[derive(Clone, Debug)]
+pub struct DirListing {
+    pub path: PathBuf,
+    pub entries: Vec<UsageEntry>,
+    /// Set when the directory could not be read
+    pub error: Option<String>,
+}
+
+/// Directories waiting to be read, and how many are being read right now
+struct WorkQueue {
+    pending: Vec<PathBuf>,
+    active: usize,
+}
+
+/// Parallel recursive directory walk that reports each directory as soon as it is read
+pub struct DiskUsageScanner;
+
+impl DiskUsageScanner {
+    /// Walk `root` on worker threads without following symlinks, handing every directory's
+    /// listing to `on_listing`. With `one_filesystem`, other filesystems mounted below `root`
+    /// are left out. Returns early when `cancel` is set.
+    pub fn walk<F: Fn(DirListing) + Sync>(root: &Path, one_filesystem: bool, cancel: &AtomicBool, on_listing: F) {
+        let Ok(root_metadata) = fs::symlink_metadata(root) else { return };
+        let root_device = one_filesystem.then(|| root_metadata.dev());
+
+        let queue = Mutex::new(WorkQueue { pending: vec![root.to_path_buf()], active: 0 });
+        let queue_changed = Condvar::new();
+        let seen_links: Mutex<HashSet<(u64, u64)>> = Mutex::new(HashSet::new());
+        let threads = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4)
+            .min(MAX_SCAN_THREADS);
+
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| loop {
+                    let Some(dir) = Self::next_dir(&queue, &queue_changed, cancel) else { return };
+                    let (listing, subdirs) = Self::read_dir(&dir, root_device, &seen_links);
+
+                    let mut queue_guard = queue.lock().unwrap();
+                    queue_guard.pending.extend(subdirs);
+                    queue_guard.active -= 1;
+                    drop(queue_guard);
+                    queue_changed.notify_all();
+
+                    on_listing(listing);
+                });
+            }
+        });
+    }
+
+    /// Wait for a directory to read; `None` once everything is read or the walk is cancelled
+    fn next_dir(queue: &Mutex<WorkQueue>, queue_changed: &Condvar, cancel: &AtomicBool) -> Option<PathBuf> {
+        let mut queue_guard = queue.lock().unwrap();
+        loop {
+            if cancel.load(Ordering::Relaxed) {
+                return None;
+            }
+            if let Some(dir) = queue_guard.pending.pop() {
+                queue_guard.active += 1;
+                return Some(dir);
+            }
+            if queue_guard.active == 0 {
+                return None;
+            }
+            // Time out now and then to notice cancellation
+            queue_guard = queue_changed
+                .wait_timeout(queue_guard, Duration::from_millis(100))
+                .unwrap()
+                .0;
+        }
+    }
+
+    fn read_dir(
+        dir: &Path,
+        root_device: Option<u64>,
+        seen_links: &Mutex<HashSet<(u64, u64)>>,
+    ) -> (DirListing, Vec<PathBuf>) {
+        let mut listing = DirListing { path: dir.to_path_buf(), entries: Vec::new(), error: None };
+        let mut subdirs = Vec::new();
+
+        let read = match fs::read_dir(dir) {
+            Ok(read) => read,
+            Err(e) => {
+                listing.error = Some(e.to_string());
+                return (listing, subdirs);
+            }
+        };
+
+        // Entries that vanish or can't be stat'ed are skipped rather than failing the directory
+        for entry in read.flatten() {
+            let Ok(metadata) = entry.metadata() else { continue };
+            let is_dir = metadata.is_dir();
+            if is_dir {
+                if root_device.is_some_and(|device| device != metadata.dev()) {
+                    continue;
+                }
+                subdirs.push(entry.path());
+            }
+
+            let mut allocated = metadata.blocks() * 512;
+            if !is_dir && metadata.nlink() > 1 && !seen_links.lock().unwrap().insert((metadata.dev(), metadata.ino())) {
+                allocated = 0;
+            }
+
+            listing.entries.push(UsageEntry {
+                name: entry.file_name(),
+                is_dir,
+                size: metadata.len(),
+                allocated,
+            });
+        }
+
+        (listing, subdirs)
+    }
+}
+
+/// A file or directory in a `DiskUsageTree`
+#[derive(Clone, Debug)]
+pub struct UsageNode {
+    /// The file name; the full path for the root. See `DiskUsageTree::path`.
+    pub name: OsString,
+    pub is_dir: bool,
+    /// Bytes on disk, including everything below a directory read so far
+    pub size: u64,
+    /// Files below a directory read so far
+    pub files: u64,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    /// Whether a directory's own listing has arrived
+    pub scanned: bool,
+    pub error: Option<String>,
+}
+
+/// Sizes of everything below a directory, built up from scanner listings as they arrive
+#[derive(Debug)]
+pub struct DiskUsageTree {
+    nodes: Vec<UsageNode>,
+    dirs: HashMap<PathBuf, usize>,
+    scanned_dirs: usize,
+}
+
+impl DiskUsageTree {
+    pub fn new(root: &Path) -> Self {
+        let root_node = UsageNode {
+            name: root.as_os_str().to_os_string(),
+            is_dir: true,
+            size: 0,
+            files: 0,
+            parent: None,
+            children: Vec::new(),
+            scanned: false,
+            error: None,
+        };
+        Self {
+            nodes: vec![root_node],
+            dirs: HashMap::from([(root.to_path_buf(), 0)]),
+            scanned_dirs: 0,
+        }
+    }
+
+    pub fn root(&self) -> usize {
+        0
+    }
+
+    pub fn node(&self, index: usize) -> &UsageNode {
+        &self.nodes[index]
+    }
+
+    /// Full path of a node, put together from the names up to the root
+    pub fn path(&self, index: usize) -> PathBuf {
+        let mut names = Vec::new();
+        let mut current = Some(index);
+        while let Some(i) = current {
+            names.push(&self.nodes[i].name);
+            current = self.nodes[i].parent;
+        }
+        names.into_iter().rev().collect()
+    }
+
+    /// Number of directories read so far
+    pub fn scanned_dirs(&self) -> usize {
+        self.scanned_dirs
+    }
+
+    /// Add a directory's contents, growing the totals of it and its parents.
+    /// Returns the directory's node, or `None` if it is not part of the tree (any more).
+    pub fn insert(&mut self, listing: DirListing) -> Option<usize> {
+        let index = *self.dirs.get(&listing.path)?;
+        self.nodes[index].scanned = true;
+        self.scanned_dirs += 1;
+        self.nodes[index].error = listing.error;
+
+        let mut size = 0;
+        let mut files = 0;
+        for entry in listing.entries {
+            let child = self.nodes.len();
+            if entry.is_dir {
+                self.dirs.insert(listing.path.join(&entry.name), child);
+            } else {
+                files += 1;
+            }
+            size += entry.allocated;
+            self.nodes.push(UsageNode {
+                name: entry.name,
+                is_dir: entry.is_dir,
+                size: entry.allocated,
+                files: 0,
+                parent: Some(index),
+                children: Vec::new(),
+                scanned: !entry.is_dir,
+                error: None,
+            });
+            self.nodes[index].children.push(child);
+        }
+
+        self.add_to_ancestors(Some(index), size as i64, files as i64);
+        Some(index)
+    }
+
+    /// Take a deleted item out of the tree and out of its parents' totals
+    pub fn remove(&mut self, index: usize) {
+        let Some(parent) = self.nodes[index].parent else { return };
+        let (size, files) = (self.nodes[index].size, self.nodes[index].files + u64::from(!self.nodes[index].is_dir));
+        self.nodes[parent].children.retain(|&child| child != index);
+        self.add_to_ancestors(Some(parent), -(size as i64), -(files as i64));
+
+        // Listings still on their way for the removed directories are dropped
+        let path = self.path(index);
+        self.dirs.retain(|dir, _| !dir.starts_with(&path));
+    }
+
+    fn add_to_ancestors(&mut self, mut index: Option<usize>, size: i64, files: i64) {
+        while let Some(i) = index {
+            let node = &mut self.nodes[i];
+            node.size = node.size.saturating_add_signed(size);
+            node.files = node.files.saturating_add_signed(files);
+            index = node.parent;
+        }
+    }
+}
@@ -1,19 +1,46 @@
+use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::time::Duration;
+
+use gtk4::gio;
+use gtk4::prelude::*;
 
 #[derive(Clone, Debug)]
 #[allow(dead_code)]
 pub struct DriveInfo {
     pub name: String,
-    pub mount_point: PathBuf,
+    /// `None` while the volume is not mounted
+    pub mount_point: Option<PathBuf>,
     pub device: String,
     pub fs_type: String,
+    /// Sizes stay zero until read with `DriveScanner::get_disk_usage`
     pub total_size: u64,
     pub available_size: u64,
     pub icon_name: String,
+    /// Mount reported by the volume monitor, for unmount and eject
+    pub mount: Option<gio::Mount>,
+    /// Volume that can be mounted on demand
+    pub volume: Option<gio::Volume>,
 }
 
 impl DriveInfo {
+    /// Share of a drive in use from which it is shown as nearly full
+    pub const NEARLY_FULL: f64 = 0.9;
+
+    pub fn is_mounted(&self) -> bool {
+        self.mount_point.is_some()
+    }
+
+    pub fn can_eject(&self) -> bool {
+        self.mount.as_ref().is_some_and(|mount| mount.can_eject())
+    }
+
+    pub fn can_unmount(&self) -> bool {
+        self.mount.as_ref().is_some_and(|mount| mount.can_unmount())
+    }
+
     pub fn size_display(&self) -> String {
         if self.total_size == 0 {
             return String::new();
@@ -25,54 +52,158 @@ impl DriveInfo {
 
         format!("{:.1} GB free of {:.1} GB", available_gb, total_gb)
     }
+
+    /// Share of the drive in use, from 0 to 1
+    pub fn used_fraction(&self) -> f64 {
+        if self.total_size == 0 {
+            return 0.0;
+        }
+        self.total_size.saturating_sub(self.available_size) as f64 / self.total_size as f64
+    }
+
+    pub fn is_nearly_full(&self) -> bool {
+        self.total_size > 0 && self.used_fraction() >= Self::NEARLY_FULL
+    }
 }
 
 pub struct DriveScanner;
 
 impl DriveScanner {
+    /// Mounted filesystems followed by volumes that can be mounted. Removable media come
+    /// from the volume monitor, so this has to run on the main thread. Disk usage is left
+    /// out since a stalled network mount would block; read it in the background.
     pub fn scan() -> Vec<DriveInfo> {
         let mut drives = Vec::new();
+        let proc_mounts = Self::read_proc_mounts();
+        let monitor = gio::VolumeMonitor::get();
 
-        // Read /proc/mounts for mounted filesystems
-        if let Ok(content) = fs::read_to_string("/proc/mounts") {
-            for line in content.lines() {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() < 4 {
-                    continue;
-                }
+        // Mounts the volume monitor knows about can be unmounted and ejected
+        for mount in monitor.mounts() {
+            if mount.is_shadowed() {
+                continue;
+            }
+            let Some(mount_path) = mount.root().path() else { continue };
+            let (device, fs_type) = proc_mounts
+                .iter()
+                .find(|(_, mount_point, _)| Path::new(mount_point) == mount_path)
+                .map(|(device, _, fs_type)| (device.clone(), fs_type.clone()))
+                .or_else(|| {
+                    let device = mount.volume()?.identifier(gio::VOLUME_IDENTIFIER_KIND_UNIX_DEVICE)?;
+                    Some((device.to_string(), String::new()))
+                })
+                .unwrap_or_default();
+            let icon = Self::themed_icon_name(&mount.symbolic_icon())
+                .unwrap_or_else(|| Self::get_drive_icon(&device, &fs_type, &mount_path.to_string_lossy()));
 
-                let device = parts[0];
-                let mount_point = parts[1];
-                let fs_type = parts[2];
-
-                // Filter out virtual filesystems and system mounts
-                if Self::should_show_mount(device, mount_point, fs_type) {
-                    let mount_path = PathBuf::from(mount_point);
-                    
-                    // Get disk usage
-                    let (total, available) = Self::get_disk_usage(&mount_path);
-
-                    let name = Self::get_drive_name(device, mount_point);
-                    let icon = Self::get_drive_icon(device, fs_type, mount_point);
-
-                    drives.push(DriveInfo {
-                        name,
-                        mount_point: mount_path,
-                        device: device.to_string(),
-                        fs_type: fs_type.to_string(),
-                        total_size: total,
-                        available_size: available,
-                        icon_name: icon,
-                    });
-                }
+            drives.push(DriveInfo {
+                name: mount.name().to_string(),
+                mount_point: Some(mount_path),
+                device,
+                fs_type,
+                total_size: 0,
+                available_size: 0,
+                icon_name: icon,
+                volume: mount.volume(),
+                mount: Some(mount),
+            });
+        }
+
+        // Other real filesystems from /proc/mounts
+        for (device, mount_point, fs_type) in &proc_mounts {
+            let mount_path = PathBuf::from(mount_point);
+            if !Self::should_show_mount(device, mount_point, fs_type)
+                || drives.iter().any(|drive| drive.mount_point.as_ref() == Some(&mount_path))
+            {
+                continue;
             }
+
+            drives.push(DriveInfo {
+                name: Self::get_drive_name(device, mount_point),
+                mount_point: Some(mount_path),
+                device: device.clone(),
+                fs_type: fs_type.clone(),
+                total_size: 0,
+                available_size: 0,
+                icon_name: Self::get_drive_icon(device, fs_type, mount_point),
+                mount: None,
+                volume: None,
+            });
         }
 
         // Sort by mount point
         drives.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+
+        // Partitions and media that are present but not mounted yet
+        let mut unmounted: Vec<DriveInfo> = monitor
+            .volumes()
+            .into_iter()
+            .filter(|volume| volume.get_mount().is_none() && volume.can_mount())
+            .map(|volume| {
+                let device = volume
+                    .identifier(gio::VOLUME_IDENTIFIER_KIND_UNIX_DEVICE)
+                    .map(|d| d.to_string())
+                    .unwrap_or_default();
+                let icon = Self::themed_icon_name(&volume.symbolic_icon())
+                    .unwrap_or_else(|| String::from("drive-harddisk"));
+                DriveInfo {
+                    name: volume.name().to_string(),
+                    mount_point: None,
+                    device,
+                    fs_type: String::new(),
+                    total_size: 0,
+                    available_size: 0,
+                    icon_name: icon,
+                    mount: None,
+                    volume: Some(volume),
+                }
+            })
+            .collect();
+        unmounted.sort_by(|a, b| a.name.cmp(&b.name));
+        drives.extend(unmounted);
         drives
     }
 
+    /// Device, mount point and filesystem type of every line in /proc/mounts
+    fn read_proc_mounts() -> Vec<(String, String, String)> {
+        let Ok(content) = fs::read_to_string("/proc/mounts") else {
+            return Vec::new();
+        };
+        content
+            .lines()
+            .filter_map(|line| {
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                if parts.len() < 4 {
+                    return None;
+                }
+                Some((parts[0].to_string(), Self::unescape_mount_path(parts[1]), parts[2].to_string()))
+            })
+            .collect()
+    }
+
+    /// /proc/mounts writes spaces and tabs in mount points as octal escapes
+    fn unescape_mount_path(path: &str) -> String {
+        path.replace("\\040", " ").replace("\\011", "\t").replace("\\134", "\\")
+    }
+
+    fn themed_icon_name(icon: &gio::Icon) -> Option<String> {
+        let themed = icon.downcast_ref::<gio::ThemedIcon>()?;
+        themed.names().first().map(|name| name.to_string())
+    }
+
+    /// Human-readable message for a failed mount, unmount or eject.
+    /// `None` when the user already saw the problem, e.g. in the "device busy" dialog.
+    pub fn error_message(error: &gtk4::glib::Error) -> Option<String> {
+        if error.matches(gio::IOErrorEnum::FailedHandled) || error.matches(gio::IOErrorEnum::Cancelled) {
+            return None;
+        }
+        if error.matches(gio::IOErrorEnum::Busy) {
+            return Some(String::from(
+                "The device is busy. Close any files or terminals using it and try again.",
+            ));
+        }
+        Some(error.message().to_string())
+    }
+
     fn should_show_mount(device: &str, mount_point: &str, fs_type: &str) -> bool {
         // Skip virtual filesystems
         let virtual_fs = [
@@ -165,26 +296,42 @@ impl DriveScanner {
         String::from("drive-harddisk")
     }
 
-    fn get_disk_usage(path: &PathBuf) -> (u64, u64) {
-        // Use df command to get disk usage
-        if let Ok(output) = std::process::Command::new("df")
-            .arg("-B1")
-            .arg(path)
-            .output()
-        {
-            if let Ok(stdout) = String::from_utf8(output.stdout) {
-                let lines: Vec<&str> = stdout.lines().collect();
-                if lines.len() >= 2 {
-                    let parts: Vec<&str> = lines[1].split_whitespace().collect();
-                    if parts.len() >= 4 {
-                        let total = parts[1].parse::<u64>().unwrap_or(0);
-                        let available = parts[3].parse::<u64>().unwrap_or(0);
-                        return (total, available);
-                    }
-                }
+    /// Like `get_disk_usage`, but gives up after `timeout`, e.g. on a network mount that
+    /// stopped answering. Such a mount is skipped until its pending lookup returns.
+    pub fn get_disk_usage_within(path: &Path, timeout: Duration) -> Option<(u64, u64)> {
+        static PENDING: OnceLock<Mutex<HashSet<PathBuf>>> = OnceLock::new();
+        let pending = PENDING.get_or_init(Default::default);
+        if !pending.lock().unwrap_or_else(|e| e.into_inner()).insert(path.to_path_buf()) {
+            return None;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let path_clone = path.to_path_buf();
+        std::thread::spawn(move || {
+            let usage = Self::get_disk_usage(&path_clone);
+            pending.lock().unwrap_or_else(|e| e.into_inner()).remove(&path_clone);
+            let _ = tx.send(usage);
+        });
+        match rx.recv_timeout(timeout) {
+            Ok(usage) => Some(usage),
+            Err(_) => {
+                log::warn!("Reading disk usage of {} timed out", path.display());
+                None
             }
         }
+    }
 
-        (0, 0)
+    /// Total and available bytes of the filesystem holding `path`, or zeros if unknown
+    pub fn get_disk_usage(path: &Path) -> (u64, u64) {
+        match nix::sys::statvfs::statvfs(path) {
+            Ok(stat) => {
+                let fragment_size = stat.fragment_size();
+                (stat.blocks() * fragment_size, stat.blocks_available() * fragment_size)
+            }
+            Err(e) => {
+                log::warn!("Failed to read disk usage of {}: {}", path.display(), e);
+                (0, 0)
+            }
+        }
     }
 }
@@ -1,9 +1,12 @@
 use std::fs;
 use std::io;
 use std::io::{Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
+use crate::core::{Archive, DiskUsageScanner};
+
 pub struct FileOperations;
 
 pub struct ProgressInfo {
@@ -14,8 +17,36 @@ pub struct ProgressInfo {
     pub total_files: usize,
 }
 
+/// What to do when a pasted item's name is already taken
+#[derive(Debug, Clone)]
+pub enum ConflictResolution {
+    Replace,
+    /// Paste it under this name instead
+    Rename(String),
+    /// Stop pasting
+    Cancel,
+}
+
 impl FileOperations {
+    /// Replace a file in one step, so readers never see it half written
+    pub fn write_atomically(path: &Path, content: &str) -> io::Result<()> {
+        let Some(name) = path.file_name() else {
+            return fs::write(path, content);
+        };
+        let temp = path.with_file_name(format!(".{}.{}.tmp", name.to_string_lossy(), std::process::id()));
+        fs::write(&temp, content)?;
+        fs::rename(&temp, path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp);
+        })
+    }
+
     pub fn copy_file(source: &Path, destination: &Path) -> io::Result<()> {
+        if !source.exists() {
+            if let Some((archive, inner)) = Archive::split_path(source) {
+                return Archive::extract_member(&archive, &inner, destination, None);
+            }
+        }
+
         if source.is_dir() {
             Self::copy_dir_recursive(source, destination)
         } else {
@@ -42,33 +73,39 @@ impl FileOperations {
         Ok(())
     }
 
-    // Calculate total size and file count for progress tracking
+    // Calculate total size and file count for progress tracking.
+    // Directories are walked in parallel.
     pub fn calculate_total_size(paths: &[std::path::PathBuf]) -> (u64, usize) {
-        let mut total_size = 0u64;
-        let mut total_files = 0usize;
-        
+        let total_size = AtomicU64::new(0);
+        let total_files = AtomicUsize::new(0);
+        let cancel = AtomicBool::new(false);
+
         for path in paths {
-            Self::calculate_size_recursive(path, &mut total_size, &mut total_files);
-        }
-        
-        (total_size, total_files)
-    }
-    
-    fn calculate_size_recursive(path: &Path, total_size: &mut u64, total_files: &mut usize) {
-        if let Ok(metadata) = path.metadata() {
+            // Members of an archive don't exist on disk, ask the archive instead
+            if !path.exists() {
+                if let Some((archive, inner)) = Archive::split_path(path) {
+                    let (size, files) = Archive::member_size(&archive, &inner);
+                    total_size.fetch_add(size, Ordering::Relaxed);
+                    total_files.fetch_add(files, Ordering::Relaxed);
+                }
+                continue;
+            }
+
+            let Ok(metadata) = path.metadata() else { continue };
             if metadata.is_dir() {
-                if let Ok(entries) = fs::read_dir(path) {
-                    for entry in entries {
-                        if let Ok(entry) = entry {
-                            Self::calculate_size_recursive(&entry.path(), total_size, total_files);
-                        }
+                DiskUsageScanner::walk(path, false, &cancel, |listing| {
+                    for entry in listing.entries.iter().filter(|entry| !entry.is_dir) {
+                        total_size.fetch_add(entry.size, Ordering::Relaxed);
+                        total_files.fetch_add(1, Ordering::Relaxed);
                     }
-                }
+                });
             } else {
-                *total_size += metadata.len();
-                *total_files += 1;
+                total_size.fetch_add(metadata.len(), Ordering::Relaxed);
+                total_files.fetch_add(1, Ordering::Relaxed);
             }
         }
+
+        (total_size.into_inner(), total_files.into_inner())
     }
 
     // Async copy with progress reporting
@@ -77,6 +114,13 @@ impl FileOperations {
         destination: &Path,
         progress: Option<Arc<Mutex<ProgressInfo>>>,
     ) -> io::Result<()> {
+        // Copying out of an archive extracts the member
+        if !source.exists() {
+            if let Some((archive, inner)) = Archive::split_path(source) {
+                return Archive::extract_member(&archive, &inner, destination, progress);
+            }
+        }
+
         if source.is_dir() {
             Self::copy_dir_recursive_with_progress(source, destination, progress)
         } else {
@@ -227,4 +271,17 @@ impl FileOperations {
         fs::File::create(path)?;
         Ok(())
     }
+
+    /// Build a path in `dir` that doesn't exist yet, appending " (n)" before the extension if needed
+    pub fn unique_path(dir: &Path, stem: &str, extension: &str) -> PathBuf {
+        let mut candidate = dir.join(format!("{}{}", stem, extension));
+        let mut counter = 1;
+
+        while candidate.exists() {
+            candidate = dir.join(format!("{} ({}){}", stem, counter, extension));
+            counter += 1;
+        }
+
+        candidate
+    }
 }
@@ -0,0 +1,217 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+const RECENT_LOCATIONS_FILE: &str = "recent-locations.json";
+/// Least useful entries are dropped beyond this
+const MAX_RECENT_LOCATIONS: usize = 200;
+
+/// Back/forward history of one window
+#[derive(Debug, Clone, Default)]
+pub struct NavigationHistory {
+    entries: Vec<PathBuf>,
+    index: usize,
+}
+
+impl NavigationHistory {
+    /// Rebuild a history, e.g. from a saved session
+    pub fn from_parts(entries: Vec<PathBuf>, index: usize) -> Self {
+        let index = index.min(entries.len().saturating_sub(1));
+        Self { entries, index }
+    }
+
+    pub fn entries(&self) -> &[PathBuf] {
+        &self.entries
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Record a visit; anything forward of the current location is dropped
+    pub fn push(&mut self, path: PathBuf) {
+        if self.entries.get(self.index) == Some(&path) {
+            return;
+        }
+        if !self.entries.is_empty() {
+            self.entries.truncate(self.index + 1);
+        }
+        self.entries.push(path);
+        self.index = self.entries.len() - 1;
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.index > 0
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        self.index + 1 < self.entries.len()
+    }
+
+    /// Move to another entry, returning its location
+    pub fn go_to(&mut self, index: usize) -> Option<PathBuf> {
+        let path = self.entries.get(index)?.clone();
+        self.index = index;
+        Some(path)
+    }
+
+    pub fn back(&mut self) -> Option<PathBuf> {
+        if !self.can_go_back() {
+            return None;
+        }
+        self.go_to(self.index - 1)
+    }
+
+    pub fn forward(&mut self) -> Option<PathBuf> {
+        if !self.can_go_forward() {
+            return None;
+        }
+        self.go_to(self.index + 1)
+    }
+
+    /// Entries behind the current one, nearest first, with their indexes
+    pub fn back_entries(&self) -> Vec<(usize, PathBuf)> {
+        (0..self.index).rev().map(|i| (i, self.entries[i].clone())).collect()
+    }
+
+    /// Entries ahead of the current one, nearest first, with their indexes
+    pub fn forward_entries(&self) -> Vec<(usize, PathBuf)> {
+        (self.index + 1..self.entries.len()).map(|i| (i, self.entries[i].clone())).collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecentLocation {
+    path: PathBuf,
+    visits: u32,
+    /// Seconds since the epoch
+    last_visit: u64,
+}
+
+impl RecentLocation {
+    /// Frecency: visit count weighted by how long ago the last visit was
+    fn score(&self, now: u64) -> u64 {
+        const DAY: u64 = 24 * 60 * 60;
+        let age = now.saturating_sub(self.last_visit);
+        let weight = match age {
+            a if a < 4 * DAY => 100,
+            a if a < 14 * DAY => 70,
+            a if a < 31 * DAY => 50,
+            a if a < 90 * DAY => 30,
+            _ => 10,
+        };
+        self.visits as u64 * weight
+    }
+}
+
+/// Visited directories ranked by frecency, saved in ~/.local/state/blink/recent-locations.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecentLocations {
+    locations: Vec<RecentLocation>,
+}
+
+impl RecentLocations {
+    fn state_path() -> PathBuf {
+        dirs::state_dir()
+            .or_else(|| dirs::home_dir().map(|home| home.join(".local").join("state")))
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("blink")
+            .join(RECENT_LOCATIONS_FILE)
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+
+    /// Load the list as saved; `missing` finds the directories deleted since
+    pub fn load() -> Self {
+        match fs::read_to_string(Self::state_path()) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("Failed to parse recent locations: {}", e);
+                RecentLocations::default()
+            }),
+            Err(_) => RecentLocations::default(),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), std::io::Error> {
+        let state_path = Self::state_path();
+        if let Some(parent) = state_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string(self).map_err(std::io::Error::other)?;
+        fs::write(&state_path, content)
+    }
+
+    /// Save on a worker thread, so a slow disk doesn't hold up navigation.
+    /// Only the newest of the lists waiting to be written is saved.
+    pub fn save_in_background(&self) {
+        static SAVER: OnceLock<mpsc::Sender<RecentLocations>> = OnceLock::new();
+        let saver = SAVER.get_or_init(|| {
+            let (tx, rx) = mpsc::channel::<RecentLocations>();
+            std::thread::spawn(move || {
+                while let Ok(mut latest) = rx.recv() {
+                    while let Ok(newer) = rx.try_recv() {
+                        latest = newer;
+                    }
+                    if let Err(e) = latest.save() {
+                        log::warn!("Failed to save recent locations: {}", e);
+                    }
+                }
+            });
+            tx
+        });
+        let _ = saver.send(self.clone());
+    }
+
+    /// Recorded directories that no longer exist. Checks each one, so call it off the UI thread.
+    pub fn missing(&self) -> Vec<PathBuf> {
+        self.locations
+            .iter()
+            .filter(|location| !location.path.is_dir())
+            .map(|location| location.path.clone())
+            .collect()
+    }
+
+    pub fn forget(&mut self, paths: &[PathBuf]) {
+        self.locations.retain(|location| !paths.contains(&location.path));
+    }
+
+    pub fn record(&mut self, path: &Path) {
+        let now = Self::now();
+        match self.locations.iter_mut().find(|location| location.path == path) {
+            Some(location) => {
+                location.visits += 1;
+                location.last_visit = now;
+            }
+            None => self.locations.push(RecentLocation {
+                path: path.to_path_buf(),
+                visits: 1,
+                last_visit: now,
+            }),
+        }
+
+        if self.locations.len() > MAX_RECENT_LOCATIONS {
+            self.locations.sort_by_key(|location| std::cmp::Reverse(location.score(now)));
+            self.locations.truncate(MAX_RECENT_LOCATIONS);
+        }
+    }
+
+    /// Highest ranked locations first
+    pub fn ranked(&self, limit: usize) -> Vec<PathBuf> {
+        let now = Self::now();
+        let mut ranked: Vec<&RecentLocation> = self.locations.iter().collect();
+        ranked.sort_by_key(|location| {
+            (std::cmp::Reverse(location.score(now)), std::cmp::Reverse(location.last_visit))
+        });
+        ranked.into_iter().take(limit).map(|location| location.path.clone()).collect()
+    }
+}
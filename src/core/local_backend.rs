@@ -0,0 +1,94 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use gtk4::gio;
+
+use crate::core::{Archive, FileEntry, FileOperations, Listing, Scanner, VfsBackend, VfsWatch};
+
+/// The local disk, with archives browsed as read-only folders
+pub struct LocalBackend;
+
+impl VfsBackend for LocalBackend {
+    fn list(&self, dir: &Path, show_hidden: bool) -> io::Result<Listing> {
+        Scanner::scan_counting_hidden(dir, show_hidden)
+    }
+
+    fn list_batches(
+        &self,
+        dir: &Path,
+        show_hidden: bool,
+        batch_size: usize,
+        on_batch: &mut dyn FnMut(Vec<FileEntry>) -> bool,
+    ) -> io::Result<usize> {
+        Scanner::scan_batches(dir, show_hidden, batch_size, on_batch)
+    }
+
+    /// Only the names are read, unlike a listing
+    fn count_hidden(&self, dir: &Path) -> io::Result<usize> {
+        if Archive::split_path(dir).is_some() {
+            return self.list(dir, false).map(|(_, hidden)| hidden);
+        }
+        Ok(fs::read_dir(dir)?
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with('.'))
+            .count())
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<FileEntry> {
+        FileEntry::from_path(path)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        fs::symlink_metadata(path).is_ok()
+    }
+
+    /// Archive members are read from a temporary copy
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(self.local_path(path)?)
+    }
+
+    fn write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        fs::write(path, data)
+    }
+
+    fn is_read_only(&self, path: &Path) -> bool {
+        Archive::split_path(path).is_some()
+    }
+
+    /// Archive members are extracted to a temporary folder
+    fn local_path(&self, path: &Path) -> io::Result<PathBuf> {
+        if self.exists(path) {
+            return Ok(path.to_path_buf());
+        }
+        match Archive::split_path(path) {
+            Some((archive, inner)) => Archive::extract_to_temp(&archive, &inner),
+            None => Err(io::Error::new(io::ErrorKind::NotFound, format!("{} does not exist", path.display()))),
+        }
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<()> {
+        FileOperations::copy_file(from, to)
+    }
+
+    fn move_to(&self, from: &Path, to: &Path) -> io::Result<()> {
+        FileOperations::move_file(from, to)
+    }
+
+    fn delete(&self, path: &Path) -> io::Result<()> {
+        FileOperations::delete(path).map_err(|e| io::Error::other(e.to_string()))
+    }
+
+    fn rename(&self, path: &Path, new_name: &str) -> io::Result<PathBuf> {
+        FileOperations::rename(path, new_name)?;
+        Ok(path.with_file_name(new_name))
+    }
+
+    fn mkdir(&self, path: &Path) -> io::Result<()> {
+        FileOperations::create_directory(path)
+    }
+
+    fn watch(&self, dir: &Path) -> io::Result<VfsWatch> {
+        VfsWatch::monitor(&gio::File::for_path(dir))
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+const LOG_FILE: &str = "blink.log";
+/// The log is moved aside once it grows past this
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+/// Older logs kept as blink.log.1 (newest) to blink.log.N
+const ROTATED_LOGS: usize = 3;
+/// Lines kept in memory for the debug info
+const RECENT_LINES: usize = 200;
+
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+
+/// Leveled log written to the state folder and, from a terminal, to stderr.
+/// `BLINK_LOG` sets the levels, e.g. `debug` or `warn,blink::widgets::file_view=trace`;
+/// `--verbose` turns on debug messages.
+pub struct Logger {
+    default_level: LevelFilter,
+    /// Levels for module paths, longest first so the most specific one wins
+    targets: Vec<(String, LevelFilter)>,
+    path: PathBuf,
+    to_stderr: bool,
+    state: Mutex<LogState>,
+}
+
+struct LogState {
+    file: Option<File>,
+    written: u64,
+    recent: VecDeque<String>,
+}
+
+impl Logger {
+    /// Install the logger; call once, before anything logs
+    pub fn init(verbose: bool) {
+        let spec = std::env::var("BLINK_LOG").unwrap_or_default();
+        let (mut default_level, targets) = Self::parse_spec(&spec);
+        if verbose {
+            default_level = default_level.max(LevelFilter::Debug);
+        }
+
+        let path = Self::log_path();
+        let file = Self::open_log(&path);
+        let written = file.as_ref().and_then(|f| f.metadata().ok()).map_or(0, |m| m.len());
+        let max_level = targets.iter().map(|(_, level)| *level).fold(default_level, Ord::max);
+
+        let logger = LOGGER.get_or_init(|| Logger {
+            default_level,
+            targets,
+            path,
+            to_stderr: file.is_none() || std::io::stderr().is_terminal(),
+            state: Mutex::new(LogState { file, written, recent: VecDeque::new() }),
+        });
+        if log::set_logger(logger).is_ok() {
+            log::set_max_level(max_level);
+        }
+    }
+
+    /// Where the log is written
+    pub fn log_path() -> PathBuf {
+        dirs::state_dir()
+            .or_else(|| dirs::home_dir().map(|home| home.join(".local").join("state")))
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("blink")
+            .join(LOG_FILE)
+    }
+
+    /// Versions, session details and the latest log lines, for pasting into a bug report
+    pub fn debug_info() -> String {
+        let os = fs::read_to_string("/etc/os-release")
+            .ok()
+            .and_then(|release| {
+                release
+                    .lines()
+                    .find_map(|line| line.strip_prefix("PRETTY_NAME="))
+                    .map(|name| name.trim_matches('"').to_string())
+            })
+            .unwrap_or_else(|| std::env::consts::OS.to_string());
+        let env = |name: &str| std::env::var(name).unwrap_or_else(|_| String::from("unset"));
+
+        let mut info = format!(
+            "Blink {}\nGTK {}.{}.{}\nlibadwaita {}.{}.{}\nOS: {}\nDesktop: {} ({})\nBLINK_LOG: {}\nLog file: {}\n",
+            env!("CARGO_PKG_VERSION"),
+            gtk4::major_version(),
+            gtk4::minor_version(),
+            gtk4::micro_version(),
+            libadwaita::major_version(),
+            libadwaita::minor_version(),
+            libadwaita::micro_version(),
+            os,
+            env("XDG_CURRENT_DESKTOP"),
+            env("XDG_SESSION_TYPE"),
+            env("BLINK_LOG"),
+            Self::log_path().display(),
+        );
+
+        if let Some(logger) = LOGGER.get() {
+            let state = logger.state.lock().unwrap_or_else(|e| e.into_inner());
+            if !state.recent.is_empty() {
+                info.push_str("\nRecent log:\n");
+                for line in &state.recent {
+                    info.push_str(line);
+                    info.push('\n');
+                }
+            }
+        }
+        info
+    }
+
+    /// Read `level` or `target=level` items separated by commas; a bare level sets the default
+    fn parse_spec(spec: &str) -> (LevelFilter, Vec<(String, LevelFilter)>) {
+        let mut default_level = LevelFilter::Warn;
+        let mut targets = Vec::new();
+        for item in spec.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            match item.split_once('=') {
+                Some((target, level)) => match level.trim().parse() {
+                    Ok(level) => targets.push((target.trim().to_string(), level)),
+                    Err(_) => eprintln!("BLINK_LOG: unknown level in '{}'", item),
+                },
+                None => match item.parse() {
+                    Ok(level) => default_level = level,
+                    Err(_) => eprintln!("BLINK_LOG: unknown level '{}'", item),
+                },
+            }
+        }
+        targets.sort_by_key(|(target, _)| std::cmp::Reverse(target.len()));
+        (default_level, targets)
+    }
+
+    fn level_for(&self, target: &str) -> LevelFilter {
+        self.targets
+            .iter()
+            .find(|(prefix, _)| {
+                target.strip_prefix(prefix.as_str()).is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .map_or(self.default_level, |(_, level)| *level)
+    }
+
+    /// Open the log for appending, rotating it first if it is already full
+    fn open_log(path: &Path) -> Option<File> {
+        fs::create_dir_all(path.parent()?).ok()?;
+        if fs::metadata(path).is_ok_and(|m| m.len() >= MAX_LOG_BYTES) {
+            Self::rotate(path);
+        }
+        OpenOptions::new().create(true).append(true).open(path).ok()
+    }
+
+    /// Shift blink.log to blink.log.1, blink.log.1 to blink.log.2 and so on, dropping the oldest
+    fn rotate(path: &Path) {
+        let numbered = |n: usize| PathBuf::from(format!("{}.{}", path.display(), n));
+        let _ = fs::remove_file(numbered(ROTATED_LOGS));
+        for n in (1..ROTATED_LOGS).rev() {
+            let _ = fs::rename(numbered(n), numbered(n + 1));
+        }
+        let _ = fs::rename(path, numbered(1));
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level_for(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format!(
+            "{} {:<5} {}: {}",
+            chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.3f"),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        if self.to_stderr {
+            eprintln!("{}", line);
+        }
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.written >= MAX_LOG_BYTES {
+            state.file = None;
+            Self::rotate(&self.path);
+            state.file = OpenOptions::new().create(true).append(true).open(&self.path).ok();
+            state.written = 0;
+        }
+        if let Some(file) = state.file.as_mut() {
+            if writeln!(file, "{}", line).is_ok() {
+                state.written += line.len() as u64 + 1;
+            }
+        }
+        if state.recent.len() == RECENT_LINES {
+            state.recent.pop_front();
+        }
+        state.recent.push_back(line);
+    }
+
+    fn flush(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(file) = state.file.as_mut() {
+            let _ = file.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logger(spec: &str) -> Logger {
+        let (default_level, targets) = Logger::parse_spec(spec);
+        Logger {
+            default_level,
+            targets,
+            path: PathBuf::new(),
+            to_stderr: false,
+            state: Mutex::new(LogState { file: None, written: 0, recent: VecDeque::new() }),
+        }
+    }
+
+    #[test]
+    fn parse_spec_reads_levels_and_targets() {
+        let (default_level, targets) = Logger::parse_spec(" debug , blink::core=trace,,blink=error ");
+        assert_eq!(default_level, LevelFilter::Debug);
+        assert_eq!(
+            targets,
+            [(String::from("blink::core"), LevelFilter::Trace), (String::from("blink"), LevelFilter::Error)]
+        );
+    }
+
+    #[test]
+    fn parse_spec_skips_unknown_levels() {
+        let (default_level, targets) = Logger::parse_spec("loud,blink=loud");
+        assert_eq!(default_level, LevelFilter::Warn);
+        assert!(targets.is_empty());
+    }
+
+    #[test]
+    fn most_specific_target_wins() {
+        let logger = logger("info,blink=error,blink::widgets=debug,blink::widgets::file_view=trace");
+        assert_eq!(logger.level_for("blink::widgets::file_view"), LevelFilter::Trace);
+        assert_eq!(logger.level_for("blink::widgets::sidebar"), LevelFilter::Debug);
+        assert_eq!(logger.level_for("blink::core"), LevelFilter::Error);
+        assert_eq!(logger.level_for("gtk4"), LevelFilter::Info);
+        // Prefixes only match whole module names
+        assert_eq!(logger.level_for("blinker"), LevelFilter::Info);
+    }
+
+    #[test]
+    fn rotate_shifts_logs_and_drops_the_oldest() {
+        let dir = std::env::temp_dir().join(format!("blink-logger-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(LOG_FILE);
+        let numbered = |n: usize| PathBuf::from(format!("{}.{}", path.display(), n));
+        fs::write(&path, "current").unwrap();
+        for n in 1..=ROTATED_LOGS {
+            fs::write(numbered(n), format!("old {}", n)).unwrap();
+        }
+
+        Logger::rotate(&path);
+
+        assert!(!path.exists());
+        assert_eq!(fs::read_to_string(numbered(1)).unwrap(), "current");
+        for n in 2..=ROTATED_LOGS {
+            assert_eq!(fs::read_to_string(numbered(n)).unwrap(), format!("old {}", n - 1));
+        }
+        assert!(!numbered(ROTATED_LOGS + 1).exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
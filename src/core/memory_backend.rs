@@ -0,0 +1,376 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use crate::core::{FileEntry, Listing, Mime, VfsBackend, VfsWatch};
+
+/// A file or folder held in memory
+#[derive(Clone, Debug)]
+struct MemoryNode {
+    is_dir: bool,
+    data: Vec<u8>,
+    modified: SystemTime,
+}
+
+/// Files kept in memory, for exercising the core logic and the views without touching the disk.
+/// Paths are absolute; `/` always exists.
+#[derive(Default)]
+pub struct MemoryBackend {
+    nodes: Mutex<BTreeMap<PathBuf, MemoryNode>>,
+    watchers: Mutex<Vec<(PathBuf, async_channel::Sender<PathBuf>)>>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        let backend = Self::default();
+        backend.nodes.lock().unwrap().insert(PathBuf::from("/"), Self::node(true, Vec::new()));
+        backend
+    }
+
+    /// Add a file, creating the folders above it
+    pub fn add_file(&self, path: &Path, data: &[u8]) {
+        self.add_parents(path);
+        self.nodes.lock().unwrap().insert(path.to_path_buf(), Self::node(false, data.to_vec()));
+        self.notify(path);
+    }
+
+    /// Add a folder, creating the folders above it
+    pub fn add_dir(&self, path: &Path) {
+        self.add_parents(path);
+        self.nodes.lock().unwrap().insert(path.to_path_buf(), Self::node(true, Vec::new()));
+        self.notify(path);
+    }
+
+    fn add_parents(&self, path: &Path) {
+        let mut nodes = self.nodes.lock().unwrap();
+        for ancestor in path.ancestors().skip(1) {
+            nodes.entry(ancestor.to_path_buf()).or_insert_with(|| Self::node(true, Vec::new()));
+        }
+    }
+
+    fn node(is_dir: bool, data: Vec<u8>) -> MemoryNode {
+        MemoryNode { is_dir, data, modified: SystemTime::now() }
+    }
+
+    fn not_found(path: &Path) -> io::Error {
+        io::Error::new(io::ErrorKind::NotFound, format!("{} does not exist", path.display()))
+    }
+
+    fn entry(path: &Path, node: &MemoryNode) -> FileEntry {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+        let mime = Mime::for_name(&name, node.is_dir);
+        FileEntry {
+            is_hidden: name.starts_with('.'),
+            path: path.to_path_buf(),
+            is_directory: node.is_dir,
+            size: if node.is_dir { 0 } else { node.data.len() as u64 },
+            modified: Some(node.modified),
+            mode: if node.is_dir { 0o755 } else { 0o644 },
+            icon_name: mime.icon_name,
+            content_type: mime.content_type,
+            type_description: mime.description,
+            name,
+            ..Default::default()
+        }
+    }
+
+    /// Paths of `path` and everything below it
+    fn subtree(nodes: &BTreeMap<PathBuf, MemoryNode>, path: &Path) -> Vec<PathBuf> {
+        nodes.keys().filter(|p| p.starts_with(path)).cloned().collect()
+    }
+
+    fn check_parent(nodes: &BTreeMap<PathBuf, MemoryNode>, path: &Path) -> io::Result<()> {
+        let parent = path.parent().ok_or_else(|| Self::not_found(path))?;
+        match nodes.get(parent) {
+            Some(node) if node.is_dir => Ok(()),
+            _ => Err(Self::not_found(parent)),
+        }
+    }
+
+    fn check_free(nodes: &BTreeMap<PathBuf, MemoryNode>, path: &Path) -> io::Result<()> {
+        if nodes.contains_key(path) {
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", path.display())));
+        }
+        Self::check_parent(nodes, path)
+    }
+
+    /// Tell the watchers of the folder holding `path`
+    fn notify(&self, path: &Path) {
+        let Some(parent) = path.parent() else { return };
+        let mut watchers = self.watchers.lock().unwrap();
+        watchers.retain(|(_, tx)| !tx.is_closed());
+        for (dir, tx) in watchers.iter() {
+            if dir == parent {
+                let _ = tx.try_send(path.to_path_buf());
+            }
+        }
+    }
+}
+
+impl VfsBackend for MemoryBackend {
+    fn list(&self, dir: &Path, show_hidden: bool) -> io::Result<Listing> {
+        let nodes = self.nodes.lock().unwrap();
+        match nodes.get(dir) {
+            Some(node) if node.is_dir => {}
+            Some(_) => return Err(io::Error::new(io::ErrorKind::NotADirectory, format!("{} is a file", dir.display()))),
+            None => return Err(Self::not_found(dir)),
+        }
+
+        let mut entries = Vec::new();
+        let mut hidden = 0;
+        for (path, node) in nodes.iter().filter(|(path, _)| path.parent() == Some(dir)) {
+            let entry = Self::entry(path, node);
+            if entry.is_hidden && !show_hidden {
+                hidden += 1;
+            } else {
+                entries.push(entry);
+            }
+        }
+        Ok((entries, hidden))
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<FileEntry> {
+        let nodes = self.nodes.lock().unwrap();
+        let node = nodes.get(path).ok_or_else(|| Self::not_found(path))?;
+        Ok(Self::entry(path, node))
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let nodes = self.nodes.lock().unwrap();
+        match nodes.get(path) {
+            Some(node) if !node.is_dir => Ok(node.data.clone()),
+            Some(_) => Err(io::Error::new(io::ErrorKind::IsADirectory, format!("{} is a folder", path.display()))),
+            None => Err(Self::not_found(path)),
+        }
+    }
+
+    fn write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        {
+            let mut nodes = self.nodes.lock().unwrap();
+            Self::check_parent(&nodes, path)?;
+            if nodes.get(path).is_some_and(|node| node.is_dir) {
+                return Err(io::Error::new(io::ErrorKind::IsADirectory, format!("{} is a folder", path.display())));
+            }
+            nodes.insert(path.to_path_buf(), Self::node(false, data.to_vec()));
+        }
+        self.notify(path);
+        Ok(())
+    }
+
+    /// Files are written out to a temporary folder
+    fn local_path(&self, path: &Path) -> io::Result<PathBuf> {
+        let data = self.read(path)?;
+        let dir = std::env::temp_dir().join(format!("blink-memory-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let local = dir.join(path.file_name().ok_or_else(|| Self::not_found(path))?);
+        fs::write(&local, data)?;
+        Ok(local)
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<()> {
+        {
+            let mut nodes = self.nodes.lock().unwrap();
+            if !nodes.contains_key(from) {
+                return Err(Self::not_found(from));
+            }
+            Self::check_free(&nodes, to)?;
+            for path in Self::subtree(&nodes, from) {
+                let target = to.join(path.strip_prefix(from).unwrap_or(&path));
+                let node = MemoryNode { modified: SystemTime::now(), ..nodes[&path].clone() };
+                nodes.insert(target, node);
+            }
+        }
+        self.notify(to);
+        Ok(())
+    }
+
+    fn move_to(&self, from: &Path, to: &Path) -> io::Result<()> {
+        {
+            let mut nodes = self.nodes.lock().unwrap();
+            if !nodes.contains_key(from) {
+                return Err(Self::not_found(from));
+            }
+            Self::check_free(&nodes, to)?;
+            if to.starts_with(from) {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "Cannot move a folder into itself"));
+            }
+            for path in Self::subtree(&nodes, from) {
+                if let Some(node) = nodes.remove(&path) {
+                    nodes.insert(to.join(path.strip_prefix(from).unwrap_or(&path)), node);
+                }
+            }
+        }
+        self.notify(from);
+        self.notify(to);
+        Ok(())
+    }
+
+    fn delete(&self, path: &Path) -> io::Result<()> {
+        {
+            let mut nodes = self.nodes.lock().unwrap();
+            if path.parent().is_none() || !nodes.contains_key(path) {
+                return Err(Self::not_found(path));
+            }
+            for path in Self::subtree(&nodes, path) {
+                nodes.remove(&path);
+            }
+        }
+        self.notify(path);
+        Ok(())
+    }
+
+    fn rename(&self, path: &Path, new_name: &str) -> io::Result<PathBuf> {
+        let new_path = path.with_file_name(new_name);
+        self.move_to(path, &new_path)?;
+        Ok(new_path)
+    }
+
+    fn mkdir(&self, path: &Path) -> io::Result<()> {
+        {
+            let mut nodes = self.nodes.lock().unwrap();
+            Self::check_free(&nodes, path)?;
+            nodes.insert(path.to_path_buf(), Self::node(true, Vec::new()));
+        }
+        self.notify(path);
+        Ok(())
+    }
+
+    fn watch(&self, dir: &Path) -> io::Result<VfsWatch> {
+        let (tx, rx) = async_channel::unbounded();
+        self.watchers.lock().unwrap().push((dir.to_path_buf(), tx));
+        Ok(VfsWatch::new(rx, Box::new(())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(listing: &Listing) -> Vec<String> {
+        listing.0.iter().map(|entry| entry.name.clone()).collect()
+    }
+
+    fn fixture() -> MemoryBackend {
+        let backend = MemoryBackend::new();
+        backend.add_file(Path::new("/docs/a.txt"), b"alpha");
+        backend.add_file(Path::new("/docs/.hidden"), b"");
+        backend.add_file(Path::new("/docs/sub/b.txt"), b"beta");
+        backend.add_dir(Path::new("/empty"));
+        backend
+    }
+
+    #[test]
+    fn list_counts_hidden_items() {
+        let backend = fixture();
+        let listing = backend.list(Path::new("/docs"), false).unwrap();
+        assert_eq!(names(&listing), ["a.txt", "sub"]);
+        assert_eq!(listing.1, 1);
+
+        let listing = backend.list(Path::new("/docs"), true).unwrap();
+        assert_eq!(names(&listing), [".hidden", "a.txt", "sub"]);
+        assert_eq!(listing.1, 0);
+    }
+
+    #[test]
+    fn list_rejects_files_and_missing_folders() {
+        let backend = fixture();
+        assert_eq!(backend.list(Path::new("/docs/a.txt"), false).unwrap_err().kind(), io::ErrorKind::NotADirectory);
+        assert_eq!(backend.list(Path::new("/nowhere"), false).unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn write_then_read_round_trips() {
+        let backend = fixture();
+        backend.write(Path::new("/docs/new.txt"), b"gamma").unwrap();
+        assert_eq!(backend.read(Path::new("/docs/new.txt")).unwrap(), b"gamma");
+
+        backend.write(Path::new("/docs/a.txt"), b"replaced").unwrap();
+        assert_eq!(backend.read(Path::new("/docs/a.txt")).unwrap(), b"replaced");
+        assert_eq!(backend.stat(Path::new("/docs/a.txt")).unwrap().size, 8);
+    }
+
+    #[test]
+    fn write_needs_an_existing_folder() {
+        let backend = fixture();
+        assert_eq!(
+            backend.write(Path::new("/missing/new.txt"), b"").unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        assert_eq!(backend.write(Path::new("/docs/sub"), b"").unwrap_err().kind(), io::ErrorKind::IsADirectory);
+        assert_eq!(backend.read(Path::new("/docs/sub")).unwrap_err().kind(), io::ErrorKind::IsADirectory);
+    }
+
+    #[test]
+    fn copy_duplicates_a_whole_folder() {
+        let backend = fixture();
+        backend.copy(Path::new("/docs"), Path::new("/backup")).unwrap();
+        assert_eq!(backend.stat(Path::new("/backup/sub/b.txt")).unwrap().size, 4);
+        assert!(backend.exists(Path::new("/docs/sub/b.txt")));
+        assert_eq!(
+            backend.copy(Path::new("/docs/a.txt"), Path::new("/backup/a.txt")).unwrap_err().kind(),
+            io::ErrorKind::AlreadyExists
+        );
+    }
+
+    #[test]
+    fn move_takes_the_folder_contents_along() {
+        let backend = fixture();
+        backend.move_to(Path::new("/docs/sub"), Path::new("/moved")).unwrap();
+        assert!(backend.exists(Path::new("/moved/b.txt")));
+        assert!(!backend.exists(Path::new("/docs/sub")));
+        assert!(!backend.exists(Path::new("/docs/sub/b.txt")));
+        assert!(backend.move_to(Path::new("/docs"), Path::new("/docs/inner")).is_err());
+    }
+
+    #[test]
+    fn delete_removes_everything_below() {
+        let backend = fixture();
+        backend.delete(Path::new("/docs/sub")).unwrap();
+        assert!(!backend.exists(Path::new("/docs/sub/b.txt")));
+        assert!(backend.exists(Path::new("/docs/a.txt")));
+        assert_eq!(backend.delete(Path::new("/docs/sub")).unwrap_err().kind(), io::ErrorKind::NotFound);
+        assert!(backend.delete(Path::new("/")).is_err());
+    }
+
+    #[test]
+    fn rename_stays_in_the_same_folder() {
+        let backend = fixture();
+        let renamed = backend.rename(Path::new("/docs/a.txt"), "c.txt").unwrap();
+        assert_eq!(renamed, Path::new("/docs/c.txt"));
+        assert!(backend.exists(&renamed));
+        assert!(!backend.exists(Path::new("/docs/a.txt")));
+    }
+
+    #[test]
+    fn mkdir_needs_a_parent_and_a_free_name() {
+        let backend = fixture();
+        backend.mkdir(Path::new("/docs/new")).unwrap();
+        assert!(backend.stat(Path::new("/docs/new")).unwrap().is_directory);
+        assert_eq!(backend.mkdir(Path::new("/docs/new")).unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(backend.mkdir(Path::new("/missing/new")).unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn local_path_writes_a_temporary_copy() {
+        let backend = fixture();
+        let local = backend.local_path(Path::new("/docs/a.txt")).unwrap();
+        assert_eq!(fs::read(&local).unwrap(), b"alpha");
+        assert_eq!(backend.local_path(Path::new("/docs")).unwrap_err().kind(), io::ErrorKind::IsADirectory);
+    }
+
+    #[test]
+    fn watch_reports_changes_in_the_folder() {
+        let backend = fixture();
+        let watch = backend.watch(Path::new("/docs")).unwrap();
+        backend.add_file(Path::new("/docs/new.txt"), b"");
+        backend.add_file(Path::new("/elsewhere.txt"), b"");
+        assert_eq!(watch.changes.try_recv().unwrap(), Path::new("/docs/new.txt"));
+        assert!(watch.changes.try_recv().is_err());
+    }
+}
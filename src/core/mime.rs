@@ -0,0 +1,110 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use gtk4::gio;
+use gtk4::prelude::*;
+
+/// Leading bytes of a file that are sniffed for its type
+const SNIFF_BYTES: u64 = 4096;
+
+/// Content type of a file as known to shared-mime-info
+#[derive(Clone, Debug)]
+pub struct MimeInfo {
+    /// GIO content type (a MIME type such as "image/png" on Linux)
+    pub content_type: String,
+    /// Human readable description, e.g. "PNG image"
+    pub description: String,
+    /// Generic themed icon name that every icon theme provides, e.g. "image-x-generic"
+    pub icon_name: String,
+}
+
+pub struct Mime;
+
+impl Mime {
+    /// Guess a local file's type from its name and leading bytes, so that a PNG named
+    /// "x.txt" is still an image. Only regular files are read, FIFOs and devices go by name.
+    pub fn for_path(path: &Path, is_directory: bool) -> MimeInfo {
+        if is_directory {
+            return Self::directory(path);
+        }
+
+        let mut head = Vec::with_capacity(SNIFF_BYTES as usize);
+        if fs::metadata(path).is_ok_and(|metadata| metadata.is_file()) {
+            if let Ok(file) = fs::File::open(path) {
+                let _ = file.take(SNIFF_BYTES).read_to_end(&mut head);
+            }
+        }
+        Self::for_data(path, &head)
+    }
+
+    /// Guess a file's type from its name and already read leading bytes
+    pub fn for_data(path: &Path, data: &[u8]) -> MimeInfo {
+        let (content_type, _) = gio::content_type_guess(Some(path), data);
+        Self::from_content_type(&content_type)
+    }
+
+    /// Guess from the name only, for entries that aren't read (archive members, remote files)
+    pub fn for_name(name: &str, is_directory: bool) -> MimeInfo {
+        if is_directory {
+            return Self::directory(Path::new(name));
+        }
+
+        let (content_type, _) = gio::content_type_guess(Some(name), &[]);
+        Self::from_content_type(&content_type)
+    }
+
+    /// MIME type of a content type, e.g. for matching "image/" prefixes
+    pub fn mime_type(content_type: &str) -> String {
+        gio::content_type_get_mime_type(content_type)
+            .map(|m| m.to_string())
+            .unwrap_or_else(|| content_type.to_string())
+    }
+
+    /// Themed icon for an entry, falling back from the specific type to the generic one
+    pub fn icon(content_type: &str, icon_name: &str, is_directory: bool) -> gio::Icon {
+        if is_directory {
+            return gio::ThemedIcon::from_names(&[icon_name, "folder"]).upcast();
+        }
+        gio::content_type_get_icon(content_type)
+    }
+
+    fn directory(path: &Path) -> MimeInfo {
+        let content_type = "inode/directory";
+        MimeInfo {
+            content_type: content_type.to_string(),
+            description: gio::content_type_get_description(content_type).to_string(),
+            icon_name: Self::folder_icon_name(path).to_string(),
+        }
+    }
+
+    /// Themed folder icons for the well-known user directories
+    fn folder_icon_name(path: &Path) -> &'static str {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        match name.as_str() {
+            "documents" => "folder-documents",
+            "downloads" => "folder-download",
+            "music" => "folder-music",
+            "pictures" => "folder-pictures",
+            "videos" => "folder-videos",
+            "desktop" => "user-desktop",
+            "templates" => "folder-templates",
+            "public" => "folder-publicshare",
+            _ => "folder",
+        }
+    }
+
+    fn from_content_type(content_type: &str) -> MimeInfo {
+        MimeInfo {
+            content_type: content_type.to_string(),
+            description: gio::content_type_get_description(content_type).to_string(),
+            icon_name: gio::content_type_get_generic_icon_name(content_type)
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| String::from("text-x-generic")),
+        }
+    }
+}
@@ -1,22 +1,60 @@
+mod applications;
+mod archive;
 mod clipboard;
 mod color_config;
+mod columns;
 mod config;
+mod dir_cache;
+mod disk_usage;
 mod drives;
 mod file_ops;
+mod history;
+mod local_backend;
+mod logger;
+#[cfg(test)]
+mod memory_backend;
+mod mime;
 mod pinned;
+mod preview;
+mod properties;
+mod recent_files;
+mod remote;
 mod scanner;
 mod search;
+mod session;
 mod sidebar_prefs;
+mod thumbnails;
+mod vfs;
+mod view_settings;
 
+pub use applications::Applications;
+pub use archive::{Archive, ArchiveFormat};
 pub use clipboard::{Clipboard, ClipboardMode};
 pub use color_config::ColorConfig;
-pub use file_ops::{FileOperations, ProgressInfo};
-pub use scanner::{FileEntry, Scanner};
+pub use columns::{ColumnLayout, ItemCount, ListColumn};
+pub use dir_cache::{CachedListing, DirectoryCache};
+pub use disk_usage::{DirListing, DiskUsageScanner, DiskUsageTree};
+pub use file_ops::{ConflictResolution, FileOperations, ProgressInfo};
+pub use history::{NavigationHistory, RecentLocations};
+pub use local_backend::LocalBackend;
+pub use logger::Logger;
+pub use mime::Mime;
+pub use preview::{Preview, PreviewContent};
+pub use properties::{ChecksumKind, FileProperties, SizeTotals};
+pub use recent_files::{RecentBackend, RecentFiles};
+pub use remote::{Remote, RemoteBackend};
+pub use scanner::{format_size, format_time, FileEntry, Scanner};
+pub use session::{Session, WindowState};
 
 // These are available but not currently used in the Nautilus clone
 #[allow(unused_imports)]
 pub use config::{Keybind, KeybindAction, KeybindConfig};
 #[allow(unused_imports)]
 pub use drives::{DriveInfo, DriveScanner};
-pub use pinned::{PinnedFolderObject, PinnedFolderStore};
-pub use sidebar_prefs::SidebarPrefs;
\ No newline at end of file
+#[cfg(test)]
+pub use memory_backend::MemoryBackend;
+pub use pinned::{PinColor, PinStyle, PinnedFolderObject, PinnedFolderStore};
+pub use sidebar_prefs::SidebarPrefs;
+pub use thumbnails::{ThumbnailPool, ThumbnailSize};
+pub use vfs::{Listing, ListingFuture, Vfs, VfsBackend, VfsWatch};
+pub use view_settings::{SortKey, ViewMode, ViewSettings, ViewSettingsStore};
\ No newline at end of file
@@ -2,11 +2,17 @@ use gtk4::glib::{self, Object};
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
 use gtk4::gio;
-use std::cell::RefCell;
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::core::FileOperations;
 
 const PINNED_FILE: &str = ".blink_pinned";
+const PIN_SETTINGS_FILE: &str = "pin-settings.json";
 
 // ============================================================================
 // PinnedFolderObject - GObject wrapper for use in gio::ListStore
@@ -19,6 +25,8 @@ mod imp {
     pub struct PinnedFolderObject {
         pub path: RefCell<String>,
         pub name: RefCell<String>,
+        pub style: RefCell<PinStyle>,
+        pub missing: Cell<bool>,
     }
 
     #[glib::object_subclass]
@@ -62,6 +70,210 @@ impl PinnedFolderObject {
     pub fn set_name(&self, name: &str) {
         *self.imp().name.borrow_mut() = name.to_string();
     }
+
+    /// Network locations (sftp://, smb://, ...) are kept as URIs
+    pub fn is_remote(&self) -> bool {
+        is_uri(&self.imp().path.borrow())
+    }
+
+    /// Local folders that are gone or on a drive that isn't mounted, as of the last
+    /// `PinnedFolderStore::check_missing`
+    pub fn is_missing(&self) -> bool {
+        self.imp().missing.get()
+    }
+
+    pub fn style(&self) -> PinStyle {
+        self.imp().style.borrow().clone()
+    }
+
+    fn set_style(&self, style: PinStyle) {
+        self.imp().style.replace(style);
+    }
+}
+
+/// Accent colours a pin's icon can be given
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PinColor {
+    Blue,
+    Green,
+    Yellow,
+    Orange,
+    Red,
+    Purple,
+}
+
+impl PinColor {
+    pub const ALL: [PinColor; 6] = [
+        PinColor::Blue,
+        PinColor::Green,
+        PinColor::Yellow,
+        PinColor::Orange,
+        PinColor::Red,
+        PinColor::Purple,
+    ];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            PinColor::Blue => "blue",
+            PinColor::Green => "green",
+            PinColor::Yellow => "yellow",
+            PinColor::Orange => "orange",
+            PinColor::Red => "red",
+            PinColor::Purple => "purple",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<PinColor> {
+        PinColor::ALL.into_iter().find(|color| color.id() == id)
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            PinColor::Blue => "Blue",
+            PinColor::Green => "Green",
+            PinColor::Yellow => "Yellow",
+            PinColor::Orange => "Orange",
+            PinColor::Red => "Red",
+            PinColor::Purple => "Purple",
+        }
+    }
+
+    pub fn css_class(&self) -> String {
+        format!("pin-color-{}", self.id())
+    }
+}
+
+/// How a pin is shown, beyond its name
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PinStyle {
+    pub icon_name: Option<String>,
+    pub color: Option<PinColor>,
+    /// Group the pin is listed under; ungrouped pins come first
+    pub group: Option<String>,
+}
+
+/// A named, collapsible set of pins
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinGroup {
+    pub name: String,
+    pub collapsed: bool,
+}
+
+fn is_uri(path: &str) -> bool {
+    path.contains("://")
+}
+
+/// Name shown for a pin without a label of its own
+fn default_name(path: &str) -> String {
+    let trimmed = path.trim_end_matches('/');
+    match trimmed.rsplit_once('/') {
+        Some((_, name)) if !name.is_empty() => name.to_string(),
+        _ => path.to_string(),
+    }
+}
+
+/// Where pins are kept, saved in ~/.config/blink/pin-settings.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct PinSettings {
+    /// Share pins with GTK file choosers through ~/.config/gtk-3.0/bookmarks.
+    /// Off until the user turns it on, as it rewrites a file other programs own.
+    use_gtk_bookmarks: bool,
+    /// blink's own pins have been copied into the bookmarks file
+    migrated: bool,
+    /// In display order
+    groups: Vec<PinGroup>,
+    /// Icons, colours and groups by pin location. Kept here since the bookmarks file can't hold them.
+    styles: HashMap<String, PinStyle>,
+}
+
+impl PinSettings {
+    fn config_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("blink")
+            .join(PIN_SETTINGS_FILE)
+    }
+
+    fn load() -> Self {
+        match fs::read_to_string(Self::config_path()) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("Failed to parse pin settings: {}", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn save(&self) -> Result<(), std::io::Error> {
+        let config_path = Self::config_path();
+        if let Some(parent) = config_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        fs::write(&config_path, content)
+    }
+}
+
+/// The GTK bookmarks file: one `URI [label]` per line
+struct GtkBookmarks;
+
+impl GtkBookmarks {
+    fn path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("gtk-3.0")
+            .join("bookmarks")
+    }
+
+    /// (path or URI, label) pairs; local files are turned into paths, other URIs are kept as they are
+    fn parse(content: &str) -> Vec<(String, Option<String>)> {
+        content
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() {
+                    return None;
+                }
+                let (uri, label) = match line.split_once(' ') {
+                    Some((uri, label)) => (uri, Some(label.trim().to_string()).filter(|l| !l.is_empty())),
+                    None => (line, None),
+                };
+                // file:// URIs naming another host have no local path
+                let location = if uri.starts_with("file://") {
+                    gio::File::for_uri(uri)
+                        .path()
+                        .map(|path| path.to_string_lossy().to_string())
+                        .unwrap_or_else(|| uri.to_string())
+                } else {
+                    uri.to_string()
+                };
+                Some((location, label))
+            })
+            .collect()
+    }
+
+    fn format(entries: &[(String, String)]) -> String {
+        let mut content = String::new();
+        for (location, name) in entries {
+            let uri = if is_uri(location) {
+                location.clone()
+            } else {
+                gio::File::for_path(location).uri().to_string()
+            };
+            content.push_str(&uri);
+            // Like GTK, only labels that differ from the folder name are written
+            if *name != default_name(location) {
+                content.push(' ');
+                content.push_str(name);
+            }
+            content.push('\n');
+        }
+        content
+    }
 }
 
 // ============================================================================
@@ -71,16 +283,153 @@ impl PinnedFolderObject {
 #[derive(Clone)]
 pub struct PinnedFolderStore {
     store: gio::ListStore,
+    settings: Rc<RefCell<PinSettings>>,
+    /// Watches the bookmarks file for edits made by other programs
+    monitor: Rc<RefCell<Option<gio::FileMonitor>>>,
+    /// What blink itself last wrote there, so its own saves aren't reloaded
+    last_written: Rc<RefCell<Option<String>>>,
 }
 
 impl PinnedFolderStore {
     pub fn new() -> Self {
         let store = gio::ListStore::new::<PinnedFolderObject>();
-        let instance = Self { store };
+        let mut settings = PinSettings::load();
+        if settings.use_gtk_bookmarks && !settings.migrated {
+            Self::migrate_to_gtk_bookmarks(&mut settings);
+        }
+
+        let instance = Self {
+            store,
+            settings: Rc::new(RefCell::new(settings)),
+            monitor: Rc::new(RefCell::new(None)),
+            last_written: Rc::new(RefCell::new(None)),
+        };
         instance.load_from_file();
+        instance.update_monitor();
         instance
     }
 
+    /// Whether pins are shared through the GTK bookmarks file
+    pub fn uses_gtk_bookmarks(&self) -> bool {
+        self.settings.borrow().use_gtk_bookmarks
+    }
+
+    /// Switch where pins are kept. The current pins carry over to the new place.
+    pub fn set_use_gtk_bookmarks(&self, enabled: bool) -> Result<(), std::io::Error> {
+        if enabled == self.uses_gtk_bookmarks() {
+            return Ok(());
+        }
+
+        {
+            let mut settings = self.settings.borrow_mut();
+            settings.use_gtk_bookmarks = enabled;
+            if enabled && !settings.migrated {
+                Self::migrate_to_gtk_bookmarks(&mut settings);
+            }
+            settings.save()?;
+        }
+
+        if enabled {
+            // Bookmarks made elsewhere join the pins
+            self.store.remove_all();
+            self.load_from_file();
+        } else {
+            self.save_to_file()?;
+        }
+        self.update_monitor();
+        Ok(())
+    }
+
+    /// Copy blink's own pins into the bookmarks file, once
+    fn migrate_to_gtk_bookmarks(settings: &mut PinSettings) {
+        let bookmarks_path = GtkBookmarks::path();
+        let existing = fs::read_to_string(&bookmarks_path).unwrap_or_default();
+        let mut entries: Vec<(String, String)> = GtkBookmarks::parse(&existing)
+            .into_iter()
+            .map(|(location, label)| {
+                let name = label.unwrap_or_else(|| default_name(&location));
+                (location, name)
+            })
+            .collect();
+
+        let pins = fs::read_to_string(Self::config_path()).unwrap_or_default();
+        for (location, name) in Self::parse_blink_pins(&pins) {
+            if !entries.iter().any(|(l, _)| *l == location) {
+                entries.push((location, name));
+            }
+        }
+
+        let result = bookmarks_path
+            .parent()
+            .map(fs::create_dir_all)
+            .unwrap_or(Ok(()))
+            .and_then(|_| FileOperations::write_atomically(&bookmarks_path, &GtkBookmarks::format(&entries)));
+        if let Err(e) = result {
+            log::warn!("Failed to migrate pins to GTK bookmarks: {}", e);
+            return;
+        }
+
+        settings.migrated = true;
+        if let Err(e) = settings.save() {
+            log::warn!("Failed to save pin settings: {}", e);
+        }
+    }
+
+    /// Watch the bookmarks file while it's in use and reload the pins when it changes
+    fn update_monitor(&self) {
+        if let Some(monitor) = self.monitor.borrow_mut().take() {
+            monitor.cancel();
+        }
+        if !self.uses_gtk_bookmarks() {
+            return;
+        }
+
+        let file = gio::File::for_path(GtkBookmarks::path());
+        let monitor = match file.monitor_file(gio::FileMonitorFlags::NONE, gio::Cancellable::NONE) {
+            Ok(monitor) => monitor,
+            Err(e) => {
+                log::warn!("Failed to watch GTK bookmarks: {}", e);
+                return;
+            }
+        };
+
+        let store = self.store.downgrade();
+        let settings = self.settings.clone();
+        let last_written = self.last_written.clone();
+        monitor.connect_changed(move |_, _, _, event| {
+            if !matches!(
+                event,
+                gio::FileMonitorEvent::ChangesDoneHint | gio::FileMonitorEvent::Created | gio::FileMonitorEvent::Deleted
+            ) {
+                return;
+            }
+            let Some(store) = store.upgrade() else { return };
+            let content = fs::read_to_string(GtkBookmarks::path()).unwrap_or_default();
+            if last_written.borrow().as_deref() == Some(content.as_str()) {
+                return;
+            }
+            store.remove_all();
+            Self::append_bookmarks(&store, &content, &settings.borrow());
+            Self::check_store_missing(&store);
+        });
+        self.monitor.replace(Some(monitor));
+    }
+
+    fn append_bookmarks(store: &gio::ListStore, content: &str, settings: &PinSettings) {
+        for (location, label) in GtkBookmarks::parse(content) {
+            let name = label.unwrap_or_else(|| default_name(&location));
+            store.append(&Self::styled_pin(&location, &name, settings));
+        }
+    }
+
+    fn styled_pin(location: &str, name: &str, settings: &PinSettings) -> PinnedFolderObject {
+        let obj = PinnedFolderObject::new(Path::new(location), name);
+        if let Some(style) = settings.styles.get(location) {
+            obj.set_style(style.clone());
+        }
+        obj
+    }
+
     pub fn store(&self) -> &gio::ListStore {
         &self.store
     }
@@ -94,6 +443,9 @@ impl PinnedFolderStore {
 
     /// Normalize path for comparison
     pub fn normalize_path(path: &Path) -> PathBuf {
+        if is_uri(&path.to_string_lossy()) {
+            return PathBuf::from(path.to_string_lossy().trim_end_matches('/'));
+        }
         if let Ok(canonical) = path.canonicalize() {
             return canonical;
         }
@@ -108,8 +460,38 @@ impl PinnedFolderStore {
         }
     }
 
+    /// Parse blink's own `path|name` pins file
+    fn parse_blink_pins(content: &str) -> Vec<(String, String)> {
+        let mut pins = Vec::new();
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let parts: Vec<&str> = trimmed.splitn(2, '|').collect();
+            let path_str = parts[0].trim();
+
+            let name = match parts.get(1) {
+                Some(name) => name.trim().to_string(),
+                None => default_name(path_str),
+            };
+            pins.push((path_str.to_string(), name));
+        }
+        pins
+    }
+
     /// Load pinned folders from file into store
     fn load_from_file(&self) {
+        // Pins to missing folders are kept and shown dimmed; the drive may just be unplugged
+        if self.uses_gtk_bookmarks() {
+            let content = fs::read_to_string(GtkBookmarks::path()).unwrap_or_default();
+            self.last_written.replace(Some(content.clone()));
+            Self::append_bookmarks(&self.store, &content, &self.settings.borrow());
+            self.check_missing();
+            return;
+        }
+
         let config_path = Self::config_path();
         
         if !config_path.exists() {
@@ -118,61 +500,93 @@ impl PinnedFolderStore {
 
         match fs::read_to_string(&config_path) {
             Ok(content) => {
-                for line in content.lines() {
-                    let trimmed = line.trim();
-                    if trimmed.is_empty() || trimmed.starts_with('#') {
-                        continue;
-                    }
-                    
-                    let parts: Vec<&str> = trimmed.splitn(2, '|').collect();
-                    if parts.is_empty() {
-                        continue;
-                    }
-                    
-                    let path_str = parts[0].trim();
-                    let path = PathBuf::from(path_str);
-                    
-                    if !path.exists() {
-                        continue;
-                    }
-                    
-                    let name = if parts.len() > 1 {
-                        parts[1].trim().to_string()
-                    } else {
-                        path.file_name()
-                            .map(|n| n.to_string_lossy().to_string())
-                            .unwrap_or_else(|| path.to_string_lossy().to_string())
-                    };
-                    
-                    let obj = PinnedFolderObject::new(&path, &name);
-                    self.store.append(&obj);
+                let settings = self.settings.borrow();
+                for (path, name) in Self::parse_blink_pins(&content) {
+                    self.store.append(&Self::styled_pin(&path, &name, &settings));
                 }
             }
             Err(e) => {
-                eprintln!("Failed to read pinned folders: {}", e);
+                log::warn!("Failed to read pinned folders: {}", e);
             }
         }
+        self.check_missing();
+    }
+
+    /// Look up which local pins are unavailable on a worker thread, then update the ones
+    /// that changed. Call it again when drives are mounted or unmounted.
+    pub fn check_missing(&self) {
+        Self::check_store_missing(&self.store);
+    }
+
+    fn check_store_missing(store: &gio::ListStore) {
+        let paths: Vec<String> = (0..store.n_items())
+            .filter_map(|i| store.item(i).and_downcast::<PinnedFolderObject>())
+            .filter(|pinned| !pinned.is_remote())
+            .map(|pinned| pinned.path_string())
+            .collect();
+        if paths.is_empty() {
+            return;
+        }
+
+        let (tx, rx) = async_channel::bounded(1);
+        std::thread::spawn(move || {
+            let missing: Vec<(String, bool)> = paths
+                .into_iter()
+                .map(|path| {
+                    let is_missing = !Path::new(&path).is_dir();
+                    (path, is_missing)
+                })
+                .collect();
+            let _ = tx.send_blocking(missing);
+        });
+
+        let store = store.downgrade();
+        glib::spawn_future_local(async move {
+            let Ok(missing) = rx.recv().await else { return };
+            let Some(store) = store.upgrade() else { return };
+            let missing: HashMap<String, bool> = missing.into_iter().collect();
+            for i in 0..store.n_items() {
+                let Some(pinned) = store.item(i).and_downcast::<PinnedFolderObject>() else { continue };
+                let Some(&is_missing) = missing.get(&pinned.path_string()) else { continue };
+                if pinned.imp().missing.replace(is_missing) != is_missing {
+                    store.items_changed(i, 1, 1);
+                }
+            }
+        });
+    }
+
+    fn entries(&self) -> Vec<(String, String)> {
+        (0..self.store.n_items())
+            .filter_map(|i| self.store.item(i).and_downcast::<PinnedFolderObject>())
+            .map(|pinned| (pinned.path_string(), pinned.name()))
+            .collect()
     }
 
     /// Save current store contents to file
     pub fn save_to_file(&self) -> Result<(), std::io::Error> {
-        let config_path = Self::config_path();
+        let config_path = if self.uses_gtk_bookmarks() {
+            GtkBookmarks::path()
+        } else {
+            Self::config_path()
+        };
         
         if let Some(parent) = config_path.parent() {
             fs::create_dir_all(parent)?;
         }
         
-        let mut content = String::from("# Blink pinned folders\n");
-        
-        for i in 0..self.store.n_items() {
-            if let Some(obj) = self.store.item(i) {
-                if let Ok(pinned) = obj.downcast::<PinnedFolderObject>() {
-                    content.push_str(&format!("{}|{}\n", pinned.path_string(), pinned.name()));
-                }
+        let content = if self.uses_gtk_bookmarks() {
+            let content = GtkBookmarks::format(&self.entries());
+            self.last_written.replace(Some(content.clone()));
+            content
+        } else {
+            let mut content = String::from("# Blink pinned folders\n");
+            for (path, name) in self.entries() {
+                content.push_str(&format!("{}|{}\n", path, name));
             }
-        }
+            content
+        };
         
-        fs::write(&config_path, content)
+        FileOperations::write_atomically(&config_path, &content)
     }
 
     /// Check if a path is already pinned
@@ -200,7 +614,8 @@ impl PinnedFolderStore {
 
     /// Add a folder to pinned list
     pub fn add(&self, path: &Path) -> Result<(), std::io::Error> {
-        if !path.exists() {
+        // Servers can't be checked without connecting; that is up to the caller
+        if !is_uri(&path.to_string_lossy()) && !path.exists() {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::NotFound,
                 format!("Path does not exist: {:?}", path),
@@ -220,6 +635,7 @@ impl PinnedFolderStore {
         
         let obj = PinnedFolderObject::new(&normalized, &name);
         self.store.append(&obj);
+        self.check_missing();
         self.save_to_file()
     }
 
@@ -228,6 +644,10 @@ impl PinnedFolderStore {
         let normalized = Self::normalize_path(path);
         
         if let Some(index) = self.find_index(&normalized) {
+            if let Some(pinned) = self.store.item(index).and_downcast::<PinnedFolderObject>() {
+                self.settings.borrow_mut().styles.remove(&pinned.path_string());
+                self.save_settings();
+            }
             self.store.remove(index);
             self.save_to_file()
         } else {
@@ -259,6 +679,7 @@ impl PinnedFolderStore {
             if let Some(obj) = self.store.item(index) {
                 if let Ok(pinned) = obj.downcast::<PinnedFolderObject>() {
                     pinned.set_name(new_name);
+                    self.store.items_changed(index, 1, 1);
                     return self.save_to_file();
                 }
             }
@@ -269,6 +690,143 @@ impl PinnedFolderStore {
             format!("Path not found in pinned list: {:?}", path),
         ))
     }
+
+    fn save_settings(&self) {
+        if let Err(e) = self.settings.borrow().save() {
+            log::warn!("Failed to save pin settings: {}", e);
+        }
+    }
+
+    /// Change a pin's icon, colour or group
+    pub fn set_style(&self, path: &Path, style: PinStyle) {
+        let Some(index) = self.find_index(path) else { return };
+        let Some(pinned) = self.store.item(index).and_downcast::<PinnedFolderObject>() else { return };
+        self.remember_style(&pinned, style);
+        self.store.items_changed(index, 1, 1);
+    }
+
+    /// Give a pin a style and save it, without notifying the views
+    fn remember_style(&self, pinned: &PinnedFolderObject, style: PinStyle) {
+        {
+            let mut settings = self.settings.borrow_mut();
+            if style == PinStyle::default() {
+                settings.styles.remove(&pinned.path_string());
+            } else {
+                settings.styles.insert(pinned.path_string(), style.clone());
+            }
+        }
+        self.save_settings();
+        pinned.set_style(style);
+    }
+
+    /// Move a pin in front of another one, taking on its group.
+    /// Without a target it goes to the end of `group`.
+    pub fn move_pin(&self, path: &Path, before: Option<&Path>, group: Option<String>) -> Result<(), std::io::Error> {
+        let Some(index) = self.find_index(path) else { return Ok(()) };
+        let Some(pinned) = self.store.item(index).and_downcast::<PinnedFolderObject>() else { return Ok(()) };
+
+        let target_index = before.and_then(|before| self.find_index(before));
+        if target_index == Some(index) {
+            return Ok(());
+        }
+        let target = target_index.and_then(|target| self.store.item(target).and_downcast::<PinnedFolderObject>());
+        let group = match &target {
+            Some(target) => target.style().group,
+            None => group,
+        };
+
+        self.remember_style(&pinned, PinStyle { group, ..pinned.style() });
+
+        // Reorder with a single splice so views lay the pins out once
+        let mut pins: Vec<PinnedFolderObject> = (0..self.store.n_items())
+            .filter_map(|i| self.store.item(i).and_downcast::<PinnedFolderObject>())
+            .filter(|other| *other != pinned)
+            .collect();
+        let position = target
+            .and_then(|target| pins.iter().position(|other| *other == target))
+            .unwrap_or(pins.len());
+        pins.insert(position, pinned);
+        self.store.splice(0, self.store.n_items(), &pins);
+        self.save_to_file()
+    }
+
+    /// Groups in display order, with whether they're collapsed
+    pub fn groups(&self) -> Vec<PinGroup> {
+        self.settings.borrow().groups.clone()
+    }
+
+    /// Ask the views to lay the pins out again, e.g. after groups changed
+    fn relayout(&self) {
+        let n = self.store.n_items();
+        self.store.items_changed(0, n, n);
+    }
+
+    pub fn add_group(&self, name: &str) {
+        {
+            let mut settings = self.settings.borrow_mut();
+            if settings.groups.iter().any(|group| group.name == name) {
+                return;
+            }
+            settings.groups.push(PinGroup { name: name.to_string(), collapsed: false });
+        }
+        self.save_settings();
+        self.relayout();
+    }
+
+    pub fn rename_group(&self, old_name: &str, new_name: &str) {
+        {
+            let mut settings = self.settings.borrow_mut();
+            if settings.groups.iter().any(|group| group.name == new_name) {
+                return;
+            }
+            for group in settings.groups.iter_mut().filter(|group| group.name == old_name) {
+                group.name = new_name.to_string();
+            }
+            for style in settings.styles.values_mut() {
+                if style.group.as_deref() == Some(old_name) {
+                    style.group = Some(new_name.to_string());
+                }
+            }
+        }
+        self.save_settings();
+        self.restyle();
+    }
+
+    /// Drop a group; its pins become ungrouped
+    pub fn remove_group(&self, name: &str) {
+        {
+            let mut settings = self.settings.borrow_mut();
+            settings.groups.retain(|group| group.name != name);
+            for style in settings.styles.values_mut() {
+                if style.group.as_deref() == Some(name) {
+                    style.group = None;
+                }
+            }
+            settings.styles.retain(|_, style| *style != PinStyle::default());
+        }
+        self.save_settings();
+        self.restyle();
+    }
+
+    pub fn set_group_collapsed(&self, name: &str, collapsed: bool) {
+        for group in self.settings.borrow_mut().groups.iter_mut().filter(|group| group.name == name) {
+            group.collapsed = collapsed;
+        }
+        self.save_settings();
+        self.relayout();
+    }
+
+    /// Re-apply the saved styles to every pin
+    fn restyle(&self) {
+        {
+            let settings = self.settings.borrow();
+            for i in 0..self.store.n_items() {
+                let Some(pinned) = self.store.item(i).and_downcast::<PinnedFolderObject>() else { continue };
+                pinned.set_style(settings.styles.get(&pinned.path_string()).cloned().unwrap_or_default());
+            }
+        }
+        self.relayout();
+    }
 }
 
 impl Default for PinnedFolderStore {
@@ -0,0 +1,287 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Local};
+use gtk4::{gdk_pixbuf, gio, glib};
+use syntect::easy::HighlightLines;
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+use crate::core::{format_size, Mime, VfsBackend};
+
+/// Text files are only read up to this many bytes
+const MAX_TEXT_BYTES: usize = 64 * 1024;
+/// Leading bytes sniffed for the type and to tell text from binary files
+const SNIFF_BYTES: usize = 8 * 1024;
+
+/// What the preview pane should show for a file
+#[derive(Clone, Debug)]
+pub enum PreviewContent {
+    Image { width: i32, height: i32 },
+    /// Pango markup, already highlighted
+    Text { markup: String, truncated: bool },
+    /// The total size is left to the caller, as measuring a big tree can take a long time
+    Directory { items: usize },
+    /// Audio, video and PDF files; their details are in `Preview::properties`
+    Metadata,
+    None,
+}
+
+#[derive(Clone, Debug)]
+pub struct Preview {
+    pub name: String,
+    pub content_type: String,
+    pub content: PreviewContent,
+    /// (label, value) rows shown under the preview
+    pub properties: Vec<(String, String)>,
+}
+
+impl Preview {
+    /// Build a preview for a path. This reads the file and may be slow, call it off the UI thread.
+    pub fn load(backend: &dyn VfsBackend, path: &Path, dark_theme: bool) -> io::Result<Preview> {
+        let entry = backend.stat(path)?;
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+
+        let mut properties = Vec::new();
+        let modified = entry
+            .modified
+            .map(|t| DateTime::<Local>::from(t).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+
+        if entry.is_directory {
+            // Counting names on disk is cheaper than a listing; archives and servers can only be listed
+            let items = fs::read_dir(path)
+                .map(|entries| entries.count())
+                .or_else(|_| backend.list(path, true).map(|(entries, _)| entries.len()))
+                .unwrap_or(0);
+            properties.push(("Contents".to_string(), format!("{} items", items)));
+            properties.push(("Modified".to_string(), modified));
+
+            return Ok(Preview {
+                name,
+                content_type: "inode/directory".to_string(),
+                content: PreviewContent::Directory { items },
+                properties,
+            });
+        }
+
+        // Small files are read whole through the backend and shown from that; of bigger ones
+        // only the start is read, for the type and the text preview
+        let head = if entry.size <= MAX_TEXT_BYTES as u64 {
+            backend.read(path)?
+        } else {
+            let mut head = Vec::with_capacity(MAX_TEXT_BYTES + 1);
+            if let Ok(file) = fs::File::open(path) {
+                file.take(MAX_TEXT_BYTES as u64 + 1).read_to_end(&mut head)?;
+            }
+            head
+        };
+        let sniffed = &head[..head.len().min(SNIFF_BYTES)];
+        let mime = Mime::for_data(path, sniffed);
+        let content_type = mime.content_type;
+        let mime_type = Mime::mime_type(&content_type);
+
+        properties.push(("Type".to_string(), mime.description));
+        properties.push(("Size".to_string(), format_size(entry.size)));
+        properties.push(("Modified".to_string(), modified));
+
+        let content = if mime_type.starts_with("image/") {
+            match gdk_pixbuf::Pixbuf::file_info(path) {
+                Some((format, width, height)) => {
+                    properties.insert(1, ("Dimensions".to_string(), format!("{} × {}", width, height)));
+                    if let Some(format_name) = format.name() {
+                        properties.push(("Format".to_string(), format_name.to_uppercase()));
+                    }
+                    PreviewContent::Image { width, height }
+                }
+                None => PreviewContent::None,
+            }
+        } else if mime_type == "application/pdf" {
+            properties.extend(Self::pdf_properties(path));
+            PreviewContent::Metadata
+        } else if mime_type.starts_with("audio/") || mime_type.starts_with("video/") {
+            properties.extend(Self::media_properties(path));
+            PreviewContent::Metadata
+        } else if gio::content_type_is_a(&content_type, "text/plain") && !sniffed.contains(&0) {
+            let (markup, truncated) = Self::highlighted_text(path, head, dark_theme);
+            PreviewContent::Text { markup, truncated }
+        } else {
+            PreviewContent::None
+        };
+
+        Ok(Preview {
+            name,
+            content_type,
+            content,
+            properties,
+        })
+    }
+
+    /// Highlight the start of a text file as Pango markup
+    fn highlighted_text(path: &Path, mut bytes: Vec<u8>, dark_theme: bool) -> (String, bool) {
+        static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+        static THEMES: OnceLock<ThemeSet> = OnceLock::new();
+
+        let truncated = bytes.len() > MAX_TEXT_BYTES;
+        bytes.truncate(MAX_TEXT_BYTES);
+
+        let mut text = String::from_utf8_lossy(&bytes).to_string();
+        if truncated {
+            // Don't end on a partial line
+            if let Some(last_newline) = text.rfind('\n') {
+                text.truncate(last_newline + 1);
+            }
+        }
+
+        let syntaxes = SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines);
+        let themes = THEMES.get_or_init(ThemeSet::load_defaults);
+        let theme_name = if dark_theme { "base16-ocean.dark" } else { "InspiredGitHub" };
+
+        let syntax = syntaxes
+            .find_syntax_for_file(path)
+            .ok()
+            .flatten()
+            .or_else(|| text.lines().next().and_then(|line| syntaxes.find_syntax_by_first_line(line)))
+            .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+
+        let mut highlighter = HighlightLines::new(syntax, &themes.themes[theme_name]);
+        let mut markup = String::with_capacity(text.len() * 2);
+
+        for line in LinesWithEndings::from(&text) {
+            match highlighter.highlight_line(line, syntaxes) {
+                Ok(ranges) => {
+                    for (style, piece) in ranges {
+                        let color = style.foreground;
+                        markup.push_str(&format!(
+                            "<span foreground=\"#{:02x}{:02x}{:02x}\">{}</span>",
+                            color.r,
+                            color.g,
+                            color.b,
+                            glib::markup_escape_text(piece)
+                        ));
+                    }
+                }
+                Err(_) => markup.push_str(&glib::markup_escape_text(line)),
+            }
+        }
+
+        (markup, truncated)
+    }
+
+    /// Duration, codec and tags of an audio or video file
+    fn media_properties(path: &Path) -> Vec<(String, String)> {
+        use symphonia::core::formats::FormatOptions;
+        use symphonia::core::io::MediaSourceStream;
+        use symphonia::core::meta::{MetadataOptions, StandardTagKey};
+        use symphonia::core::probe::Hint;
+
+        let mut properties = Vec::new();
+        let Ok(file) = fs::File::open(path) else {
+            return properties;
+        };
+
+        let stream = MediaSourceStream::new(Box::new(file), Default::default());
+        let mut hint = Hint::new();
+        if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
+            hint.with_extension(extension);
+        }
+
+        let Ok(mut probed) = symphonia::default::get_probe().format(
+            &hint,
+            stream,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        ) else {
+            return properties;
+        };
+
+        if let Some(track) = probed.format.default_track() {
+            let params = &track.codec_params;
+            if let (Some(time_base), Some(frames)) = (params.time_base, params.n_frames) {
+                let seconds = time_base.calc_time(frames).seconds;
+                properties.push((
+                    "Duration".to_string(),
+                    format!("{}:{:02}:{:02}", seconds / 3600, (seconds / 60) % 60, seconds % 60),
+                ));
+            }
+            if let Some(codec) = symphonia::default::get_codecs().get_codec(params.codec) {
+                properties.push(("Codec".to_string(), codec.long_name.to_string()));
+            }
+            if let Some(sample_rate) = params.sample_rate {
+                properties.push(("Sample rate".to_string(), format!("{} Hz", sample_rate)));
+            }
+            if let Some(channels) = params.channels {
+                properties.push(("Channels".to_string(), channels.count().to_string()));
+            }
+        }
+
+        // Tags can live in the container or in a separate metadata block (e.g. ID3)
+        let mut tags = Vec::new();
+        if let Some(revision) = probed.format.metadata().current() {
+            tags.extend(revision.tags().to_vec());
+        }
+        if let Some(revision) = probed.metadata.get().as_ref().and_then(|m| m.current()) {
+            tags.extend(revision.tags().to_vec());
+        }
+
+        for (key, label) in [
+            (StandardTagKey::TrackTitle, "Title"),
+            (StandardTagKey::Artist, "Artist"),
+            (StandardTagKey::Album, "Album"),
+        ] {
+            if let Some(tag) = tags.iter().find(|t| t.std_key == Some(key)) {
+                properties.push((label.to_string(), tag.value.to_string()));
+            }
+        }
+
+        properties
+    }
+
+    /// Page count and document info of a PDF
+    fn pdf_properties(path: &Path) -> Vec<(String, String)> {
+        let mut properties = Vec::new();
+        let Ok(document) = lopdf::Document::load(path) else {
+            return properties;
+        };
+
+        properties.push(("Pages".to_string(), document.get_pages().len().to_string()));
+
+        let info = document
+            .trailer
+            .get(b"Info")
+            .and_then(|info| document.dereference(info))
+            .and_then(|(_, info)| info.as_dict());
+
+        if let Ok(info) = info {
+            for (key, label) in [("Title", "Title"), ("Author", "Author"), ("Creator", "Creator")] {
+                if let Ok(value) = info.get(key.as_bytes()).and_then(|v| v.as_str()) {
+                    let value = Self::decode_pdf_string(value);
+                    if !value.trim().is_empty() {
+                        properties.push((label.to_string(), value));
+                    }
+                }
+            }
+        }
+
+        properties
+    }
+
+    /// PDF text strings are either UTF-16BE with a BOM or PDFDocEncoding (close enough to Latin-1)
+    fn decode_pdf_string(bytes: &[u8]) -> String {
+        if let Some(utf16) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+            let units: Vec<u16> = utf16
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        } else {
+            bytes.iter().map(|&b| b as char).collect()
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use std::fs;
+use std::io::{self, Read};
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use gtk4::glib;
+use nix::unistd::{Gid, Group, Uid, User};
+
+use crate::core::{DiskUsageScanner, FileEntry};
+
+/// Everything the properties dialog shows about a single item
+#[derive(Clone, Debug)]
+pub struct FileProperties {
+    pub path: PathBuf,
+    pub is_directory: bool,
+    pub content_type: String,
+    pub type_description: String,
+    pub symlink_target: Option<PathBuf>,
+    pub size: u64,
+    pub created: Option<SystemTime>,
+    pub modified: Option<SystemTime>,
+    pub accessed: Option<SystemTime>,
+    /// Permission bits only (e.g. 0o755)
+    pub mode: u32,
+    pub owner: String,
+    pub group: String,
+}
+
+/// Running totals of a recursive size scan
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SizeTotals {
+    pub size: u64,
+    pub files: usize,
+    pub directories: usize,
+}
+
+/// Hash algorithms offered by the properties dialog
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChecksumKind {
+    Md5,
+    Sha256,
+}
+
+impl ChecksumKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChecksumKind::Md5 => "MD5",
+            ChecksumKind::Sha256 => "SHA-256",
+        }
+    }
+}
+
+impl FileProperties {
+    pub fn load(path: &Path) -> io::Result<FileProperties> {
+        let entry = FileEntry::from_path(path)?;
+
+        Ok(FileProperties {
+            path: entry.path,
+            is_directory: entry.is_directory,
+            content_type: entry.content_type,
+            type_description: entry.type_description,
+            symlink_target: entry.symlink_target,
+            size: entry.size,
+            created: entry.created,
+            modified: entry.modified,
+            accessed: entry.accessed,
+            mode: entry.mode,
+            owner: Self::user_name(entry.uid),
+            group: Self::group_name(entry.gid),
+        })
+    }
+
+    pub fn user_name(uid: u32) -> String {
+        User::from_uid(Uid::from_raw(uid))
+            .ok()
+            .flatten()
+            .map(|user| user.name)
+            .unwrap_or_else(|| uid.to_string())
+    }
+
+    pub fn group_name(gid: u32) -> String {
+        Group::from_gid(Gid::from_raw(gid))
+            .ok()
+            .flatten()
+            .map(|group| group.name)
+            .unwrap_or_else(|| gid.to_string())
+    }
+
+    /// Add up the given paths with `DiskUsageScanner::walk`, reporting totals as they grow.
+    /// Symlinks aren't followed. Stops early when `cancel` is set.
+    pub fn measure<F: FnMut(SizeTotals) + Send>(paths: &[PathBuf], cancel: &AtomicBool, on_progress: F) -> SizeTotals {
+        // Totals, entries counted since the last report, and the reporter
+        let state = Mutex::new((SizeTotals::default(), 0usize, on_progress));
+
+        for path in paths {
+            if cancel.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let Ok(metadata) = fs::symlink_metadata(path) else { continue };
+            if !metadata.is_dir() {
+                let mut state = state.lock().unwrap();
+                state.0.files += 1;
+                state.0.size += metadata.len();
+                continue;
+            }
+
+            DiskUsageScanner::walk(path, false, cancel, |listing| {
+                let mut state = state.lock().unwrap();
+                let (totals, unreported, on_progress) = &mut *state;
+                totals.directories += 1;
+                for entry in listing.entries.iter().filter(|entry| !entry.is_dir) {
+                    totals.files += 1;
+                    totals.size += entry.size;
+                }
+
+                *unreported += listing.entries.len();
+                if *unreported >= 500 {
+                    *unreported = 0;
+                    on_progress(*totals);
+                }
+            });
+        }
+
+        let (totals, _, mut on_progress) = state.into_inner().unwrap();
+        on_progress(totals);
+        totals
+    }
+
+    /// Change permission bits. With `recursive`, everything inside a directory gets the same
+    /// read/write bits while files keep their own execute bits.
+    pub fn set_mode(path: &Path, mode: u32, recursive: bool) -> io::Result<()> {
+        if recursive && fs::symlink_metadata(path)?.is_dir() {
+            // Keep the folder open to us while its contents change; its own mode comes last,
+            // as it may take away the read or search permission the walk needs
+            fs::set_permissions(path, fs::Permissions::from_mode(mode | 0o700))?;
+            for entry in fs::read_dir(path)? {
+                let entry = entry?;
+                let metadata = entry.metadata()?;
+                if metadata.file_type().is_symlink() {
+                    continue;
+                }
+
+                if metadata.is_dir() {
+                    Self::set_mode(&entry.path(), mode, true)?;
+                } else {
+                    let file_mode = (mode & !0o111) | (metadata.mode() & 0o111);
+                    fs::set_permissions(entry.path(), fs::Permissions::from_mode(file_mode))?;
+                }
+            }
+        }
+
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))
+    }
+
+    /// Hash a file's contents as lowercase hex
+    pub fn checksum(path: &Path, kind: ChecksumKind) -> io::Result<String> {
+        let checksum_type = match kind {
+            ChecksumKind::Md5 => glib::ChecksumType::Md5,
+            ChecksumKind::Sha256 => glib::ChecksumType::Sha256,
+        };
+        let mut checksum = glib::Checksum::new(checksum_type)
+            .ok_or_else(|| io::Error::other("checksum type not supported"))?;
+
+        let mut file = fs::File::open(path)?;
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let read = file.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            checksum.update(&buffer[..read]);
+        }
+
+        Ok(checksum.string().map(|s| s.to_string()).unwrap_or_default())
+    }
+}
@@ -0,0 +1,143 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use gtk4::gio;
+use gtk4::prelude::*;
+
+use crate::core::{FileEntry, Listing, ListingFuture, Remote, VfsBackend, VfsWatch};
+
+/// Files in the shared recently-used list (~/.local/share/recently-used.xbel).
+/// The list is only reachable through GTK, so everything but `entries` runs on the main thread.
+pub struct RecentFiles;
+
+impl RecentFiles {
+    /// Virtual location the file view shows the list at
+    pub const LOCATION: &'static str = "recent:///";
+
+    pub fn location() -> PathBuf {
+        PathBuf::from(Self::LOCATION)
+    }
+
+    pub fn is_recent(path: &Path) -> bool {
+        path == Path::new(Self::LOCATION)
+    }
+
+    /// Local files in the list with when they were last used, most recent first
+    pub fn items() -> Vec<(PathBuf, Option<SystemTime>)> {
+        let mut items: Vec<(PathBuf, Option<SystemTime>)> = gtk4::RecentManager::default()
+            .items()
+            .into_iter()
+            .filter(|info| info.is_local())
+            .filter_map(|info| {
+                let path = gio::File::for_uri(&info.uri()).path()?;
+                let visited = u64::try_from(info.visited().to_unix())
+                    .ok()
+                    .map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+                Some((path, visited))
+            })
+            .collect();
+        items.sort_by_key(|(_, visited)| std::cmp::Reverse(*visited));
+        items
+    }
+
+    /// Entries for the listed files that still exist, with their last use as the access time
+    pub fn entries(items: Vec<(PathBuf, Option<SystemTime>)>, show_hidden: bool) -> Vec<FileEntry> {
+        items
+            .into_iter()
+            .filter_map(|(path, visited)| {
+                let mut entry = FileEntry::from_path(&path).ok()?;
+                if entry.is_hidden && !show_hidden {
+                    return None;
+                }
+                if visited.is_some() {
+                    entry.accessed = visited;
+                }
+                Some(entry)
+            })
+            .collect()
+    }
+
+    /// Record that a file was opened. Only local files are listed, so remote ones are left out.
+    pub fn add(path: &Path) {
+        if Remote::is_remote(path) {
+            return;
+        }
+        let uri = gio::File::for_path(path).uri();
+        if !gtk4::RecentManager::default().add_item(&uri) {
+            log::warn!("Failed to add {} to recent files", path.display());
+        }
+    }
+
+    pub fn remove(path: &Path) -> Result<(), gtk4::glib::Error> {
+        let uri = gio::File::for_path(path).uri();
+        gtk4::RecentManager::default().remove_item(&uri)
+    }
+}
+
+/// The recent list as a read-only folder of the files in it
+pub struct RecentBackend;
+
+impl RecentBackend {
+    fn read_only() -> io::Error {
+        io::Error::new(io::ErrorKind::Unsupported, "Recent files can't be changed here")
+    }
+}
+
+impl VfsBackend for RecentBackend {
+    /// The list is only reachable on the main thread; see `list_async`
+    fn list(&self, _dir: &Path, _show_hidden: bool) -> io::Result<Listing> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "Recent files are listed on the main thread"))
+    }
+
+    /// Most recently used first; the files in the list are looked at on a worker thread
+    fn list_async(&self, _dir: &Path, show_hidden: bool) -> Option<ListingFuture> {
+        let items = RecentFiles::items();
+        Some(Box::pin(async move {
+            gio::spawn_blocking(move || (RecentFiles::entries(items, show_hidden), 0))
+                .await
+                .map_err(|_| io::Error::other("Listing recent files was abandoned"))
+        }))
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<FileEntry> {
+        FileEntry::from_path(path)
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
+
+    fn write(&self, _path: &Path, _data: &[u8]) -> io::Result<()> {
+        Err(Self::read_only())
+    }
+
+    fn is_read_only(&self, _path: &Path) -> bool {
+        true
+    }
+
+    fn copy(&self, _from: &Path, _to: &Path) -> io::Result<()> {
+        Err(Self::read_only())
+    }
+
+    fn move_to(&self, _from: &Path, _to: &Path) -> io::Result<()> {
+        Err(Self::read_only())
+    }
+
+    fn delete(&self, _path: &Path) -> io::Result<()> {
+        Err(Self::read_only())
+    }
+
+    fn rename(&self, _path: &Path, _new_name: &str) -> io::Result<PathBuf> {
+        Err(Self::read_only())
+    }
+
+    fn mkdir(&self, _path: &Path) -> io::Result<()> {
+        Err(Self::read_only())
+    }
+
+    fn watch(&self, _dir: &Path) -> io::Result<VfsWatch> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "The recent list is not watched"))
+    }
+}
@@ -0,0 +1,499 @@
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, UNIX_EPOCH};
+
+use gtk4::gio;
+use gtk4::glib;
+use gtk4::prelude::*;
+
+use crate::core::{ConflictResolution, FileEntry, Listing, ListingFuture, Mime, ProgressInfo, VfsBackend, VfsWatch};
+
+/// Attributes read for each remote item
+const ATTRIBUTES: &str = "standard::*,time::*,unix::*";
+/// Items requested from the server per round trip
+const ENUMERATE_BATCH: i32 = 100;
+
+/// Locations reached through GIO/GVfs (sftp://, smb://, dav://, ftp://, ...).
+/// Remote locations are carried around as `PathBuf`s holding the URI, like the Recent list;
+/// all I/O on them is asynchronous and runs on the main loop.
+pub struct Remote;
+
+impl Remote {
+    /// Protocols offered when connecting to a server, with their display names
+    pub const SCHEMES: [(&'static str, &'static str); 5] = [
+        ("sftp", "SSH (SFTP)"),
+        ("smb", "Windows Share (SMB)"),
+        ("davs", "Secure WebDAV"),
+        ("dav", "WebDAV"),
+        ("ftp", "FTP"),
+    ];
+
+    /// Whether `path` holds a remote URI rather than a local path or a virtual location
+    pub fn is_remote(path: &Path) -> bool {
+        let path = path.to_string_lossy();
+        let Some((scheme, _)) = path.split_once("://") else { return false };
+        !scheme.is_empty()
+            && scheme != "file"
+            && scheme != "recent"
+            && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    }
+
+    /// Turn typed text into a location: URIs are checked and normalized by GIO, `file://` becomes a path
+    pub fn parse_location(text: &str) -> Option<PathBuf> {
+        let path = PathBuf::from(text.trim());
+        if !text.contains("://") {
+            return Some(path);
+        }
+
+        let file = gio::File::for_uri(text.trim());
+        if let Some(local) = file.path() {
+            return Some(local);
+        }
+        Self::is_remote(&path).then(|| Self::location_of(&file))
+    }
+
+    /// The GIO file for a local path or a remote URI
+    pub fn file(path: &Path) -> gio::File {
+        if Self::is_remote(path) {
+            gio::File::for_uri(&path.to_string_lossy())
+        } else {
+            gio::File::for_path(path)
+        }
+    }
+
+    /// Location of a GIO file as the file view addresses it
+    pub fn location_of(file: &gio::File) -> PathBuf {
+        if let Some(path) = file.path().filter(|_| file.is_native()) {
+            return path;
+        }
+        // Keep the trailing slash of the server root only, so joining names works everywhere else
+        let uri = file.uri().to_string();
+        match uri.split_once("://") {
+            Some((_, rest)) if rest.trim_end_matches('/').contains('/') => PathBuf::from(uri.trim_end_matches('/')),
+            _ => PathBuf::from(uri),
+        }
+    }
+
+    /// Server root of a remote location, e.g. `sftp://user@host/`
+    pub fn root(path: &Path) -> PathBuf {
+        let path = path.to_string_lossy();
+        let Some((scheme, rest)) = path.split_once("://") else {
+            return PathBuf::from(path.as_ref());
+        };
+        let authority = rest.split('/').next().unwrap_or_default();
+        PathBuf::from(format!("{}://{}/", scheme, authority))
+    }
+
+    /// Name of a remote location as shown to the user, with URI escapes undone
+    pub fn display_name(path: &Path) -> String {
+        let path = path.to_string_lossy();
+        let trimmed = path.trim_end_matches('/');
+        let last = match trimmed.split_once("://") {
+            Some((_, rest)) => rest.rsplit('/').next().unwrap_or(rest),
+            None => trimmed.rsplit('/').next().unwrap_or(trimmed),
+        };
+        glib::uri_unescape_string(last, None::<&str>)
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| last.to_string())
+    }
+
+    /// Location of a child of a remote folder, escaping the name as needed
+    pub fn child(dir: &Path, name: &str) -> PathBuf {
+        Self::location_of(&Self::file(dir).child(name))
+    }
+
+    /// Mount the share holding `file`. Returns whether it was mounted now rather than already.
+    pub async fn mount(file: &gio::File, mount_operation: Option<&gio::MountOperation>) -> Result<bool, glib::Error> {
+        match file.mount_enclosing_volume_future(gio::MountMountFlags::NONE, mount_operation).await {
+            Ok(()) => Ok(true),
+            Err(e) if e.matches(gio::IOErrorEnum::AlreadyMounted) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn enumerate(dir: &gio::File) -> Result<gio::FileEnumerator, glib::Error> {
+        dir.enumerate_children_future(ATTRIBUTES, gio::FileQueryInfoFlags::NOFOLLOW_SYMLINKS, glib::Priority::DEFAULT)
+            .await
+    }
+
+    /// Build a file view entry from what the server reported about `file`
+    fn entry_for_info(file: &gio::File, info: &gio::FileInfo) -> FileEntry {
+        let name = info.display_name().to_string();
+        let is_directory = info.file_type() == gio::FileType::Directory;
+        let mime = Mime::for_name(&name, is_directory);
+        let time = |attribute: &str| {
+            let secs = info.attribute_uint64(attribute);
+            (secs > 0).then(|| UNIX_EPOCH + Duration::from_secs(secs))
+        };
+        let mode = info.attribute_uint32("unix::mode") & 0o7777;
+
+        FileEntry {
+            is_hidden: info.is_hidden() || name.starts_with('.'),
+            path: Self::location_of(file),
+            is_directory,
+            size: if is_directory { 0 } else { info.size().max(0) as u64 },
+            modified: time("time::modified"),
+            created: time("time::created"),
+            changed: time("time::changed"),
+            accessed: time("time::access"),
+            mode: if mode != 0 { mode } else if is_directory { 0o755 } else { 0o644 },
+            uid: info.attribute_uint32("unix::uid"),
+            gid: info.attribute_uint32("unix::gid"),
+            is_symlink: info.is_symlink(),
+            symlink_target: info.symlink_target(),
+            icon_name: mime.icon_name,
+            content_type: mime.content_type,
+            type_description: mime.description,
+            name,
+            ..Default::default()
+        }
+    }
+
+    /// Items of a remote folder, read in batches on the main loop; also returns how many hidden ones were left out
+    pub async fn list_dir(dir: &Path, show_hidden: bool) -> Result<Listing, glib::Error> {
+        let dir = Self::file(dir);
+        let enumerator = Self::enumerate(&dir).await?;
+        let mut entries = Vec::new();
+        let mut hidden = 0;
+        loop {
+            let infos = enumerator.next_files_future(ENUMERATE_BATCH, glib::Priority::DEFAULT).await?;
+            if infos.is_empty() {
+                break;
+            }
+            for info in &infos {
+                let entry = Self::entry_for_info(&dir.child(info.name()), info);
+                if entry.is_hidden && !show_hidden {
+                    hidden += 1;
+                } else {
+                    entries.push(entry);
+                }
+            }
+        }
+        Ok((entries, hidden))
+    }
+
+    /// Total size and number of files below the given items, read from the server
+    pub async fn measure(paths: &[PathBuf]) -> (u64, usize) {
+        let mut totals = (0, 0);
+        for path in paths {
+            Self::measure_file(Self::file(path), &mut totals).await;
+        }
+        totals
+    }
+
+    fn measure_file<'a>(file: gio::File, totals: &'a mut (u64, usize)) -> Pin<Box<dyn Future<Output = ()> + 'a>> {
+        Box::pin(async move {
+            let Ok(info) = file
+                .query_info_future(ATTRIBUTES, gio::FileQueryInfoFlags::NOFOLLOW_SYMLINKS, glib::Priority::DEFAULT)
+                .await
+            else {
+                return;
+            };
+            if info.file_type() != gio::FileType::Directory {
+                totals.0 += info.size().max(0) as u64;
+                totals.1 += 1;
+                return;
+            }
+            for child in Self::children(&file).await.unwrap_or_default() {
+                Self::measure_file(child, totals).await;
+            }
+        })
+    }
+
+    async fn children(dir: &gio::File) -> Result<Vec<gio::File>, glib::Error> {
+        let enumerator = Self::enumerate(dir).await?;
+        let mut children = Vec::new();
+        loop {
+            let infos = enumerator.next_files_future(ENUMERATE_BATCH, glib::Priority::DEFAULT).await?;
+            if infos.is_empty() {
+                break;
+            }
+            children.extend(infos.iter().map(|info| dir.child(info.name())));
+        }
+        Ok(children)
+    }
+
+    /// Copy or move items into `destination`, where either side may be remote.
+    /// `resolve_conflict` is asked what to do with names that are taken; cancelling stops
+    /// before the item. Progress is reported through `progress`.
+    /// A failed item doesn't stop the others; each is returned with its error.
+    pub async fn transfer<F, Fut>(
+        sources: &[PathBuf],
+        destination: &Path,
+        is_move: bool,
+        progress: Arc<Mutex<ProgressInfo>>,
+        resolve_conflict: F,
+    ) -> Vec<(PathBuf, glib::Error)>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = ConflictResolution>,
+    {
+        let dest_dir = Self::file(destination);
+        let mut failures = Vec::new();
+        for source in sources {
+            let source_file = Self::file(source);
+            let name = source_file
+                .basename()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| Self::display_name(source));
+
+            let mut target = dest_dir.child(&name);
+            let mut overwrite = false;
+            while Self::exists(&target).await {
+                let taken = target.basename().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                match resolve_conflict(taken).await {
+                    ConflictResolution::Replace => {
+                        overwrite = true;
+                        break;
+                    }
+                    ConflictResolution::Rename(new_name) => target = dest_dir.child(new_name),
+                    ConflictResolution::Cancel => return failures,
+                }
+            }
+            let flags = if overwrite { gio::FileCopyFlags::OVERWRITE } else { gio::FileCopyFlags::NONE };
+
+            // A move within one server is a rename; anything else is copied, then deleted
+            if is_move {
+                let (from, to) = (source_file.clone(), target.clone());
+                let renamed = gio::spawn_blocking(move || {
+                    from.move_(&to, flags | gio::FileCopyFlags::NO_FALLBACK_FOR_MOVE, gio::Cancellable::NONE, None)
+                })
+                .await;
+                if let Ok(Ok(())) = renamed {
+                    progress.lock().unwrap().files_copied += 1;
+                    continue;
+                }
+            }
+
+            let mut result = Self::copy_tree(source_file.clone(), target, flags, progress.clone()).await;
+            if is_move && result.is_ok() {
+                result = Self::delete_tree(source_file).await;
+            }
+            if let Err(e) = result {
+                failures.push((source.clone(), e));
+            }
+        }
+        failures
+    }
+
+    async fn exists(file: &gio::File) -> bool {
+        file.query_info_future("standard::type", gio::FileQueryInfoFlags::NOFOLLOW_SYMLINKS, glib::Priority::DEFAULT)
+            .await
+            .is_ok()
+    }
+
+    /// Copy a file or folder; `flags` may carry `OVERWRITE` to replace what is in the way
+    fn copy_tree(
+        source: gio::File,
+        target: gio::File,
+        flags: gio::FileCopyFlags,
+        progress: Arc<Mutex<ProgressInfo>>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), glib::Error>>>> {
+        Box::pin(async move {
+            let info = source
+                .query_info_future(ATTRIBUTES, gio::FileQueryInfoFlags::NOFOLLOW_SYMLINKS, glib::Priority::DEFAULT)
+                .await?;
+
+            // GIO copies single files only; folders are recreated and filled item by item
+            if info.file_type() == gio::FileType::Directory {
+                match target.make_directory_future(glib::Priority::DEFAULT).await {
+                    Err(e) if !e.matches(gio::IOErrorEnum::Exists) => return Err(e),
+                    _ => {}
+                }
+                for child in Self::children(&source).await? {
+                    let name = child.basename().unwrap_or_default();
+                    Self::copy_tree(child, target.child(name), flags, progress.clone()).await?;
+                }
+                return Ok(());
+            }
+
+            let base = {
+                let mut progress = progress.lock().unwrap();
+                progress.current_file = info.display_name().to_string();
+                progress.bytes_copied
+            };
+            let progress_clone = progress.clone();
+            let (tx, rx) = async_channel::bounded(1);
+            source.copy_async(
+                &target,
+                flags | gio::FileCopyFlags::NOFOLLOW_SYMLINKS,
+                glib::Priority::DEFAULT,
+                gio::Cancellable::NONE,
+                Some(Box::new(move |current, _total| {
+                    progress_clone.lock().unwrap().bytes_copied = base + current.max(0) as u64;
+                })),
+                move |result| {
+                    let _ = tx.send_blocking(result);
+                },
+            );
+            rx.recv()
+                .await
+                .unwrap_or_else(|_| Err(glib::Error::new(gio::IOErrorEnum::Cancelled, "Copy was abandoned")))?;
+
+            let mut progress = progress.lock().unwrap();
+            progress.bytes_copied = base + info.size().max(0) as u64;
+            progress.files_copied += 1;
+            Ok(())
+        })
+    }
+
+    /// Delete an item permanently, emptying folders first
+    fn delete_tree(file: gio::File) -> Pin<Box<dyn Future<Output = Result<(), glib::Error>>>> {
+        Box::pin(async move {
+            let info = file
+                .query_info_future("standard::type", gio::FileQueryInfoFlags::NOFOLLOW_SYMLINKS, glib::Priority::DEFAULT)
+                .await?;
+            if info.file_type() == gio::FileType::Directory {
+                for child in Self::children(&file).await? {
+                    Self::delete_tree(child).await?;
+                }
+            }
+            file.delete_future(glib::Priority::DEFAULT).await
+        })
+    }
+}
+
+/// Remote locations through GIO's blocking calls, which are safe off the main thread.
+/// Shares must be mounted first (`Remote::mount`); until then calls fail with `NotConnected`.
+pub struct RemoteBackend;
+
+impl RemoteBackend {
+    fn io_error(error: glib::Error) -> io::Error {
+        let kind = match error.kind::<gio::IOErrorEnum>() {
+            Some(gio::IOErrorEnum::NotFound) => io::ErrorKind::NotFound,
+            Some(gio::IOErrorEnum::Exists) => io::ErrorKind::AlreadyExists,
+            Some(gio::IOErrorEnum::PermissionDenied) => io::ErrorKind::PermissionDenied,
+            Some(gio::IOErrorEnum::NotMounted) => io::ErrorKind::NotConnected,
+            Some(gio::IOErrorEnum::TimedOut) => io::ErrorKind::TimedOut,
+            Some(gio::IOErrorEnum::NotSupported) => io::ErrorKind::Unsupported,
+            _ => io::ErrorKind::Other,
+        };
+        io::Error::new(kind, error.message().to_string())
+    }
+
+    fn children(dir: &gio::File) -> Result<Vec<(gio::File, gio::FileInfo)>, glib::Error> {
+        let enumerator = dir.enumerate_children(
+            ATTRIBUTES,
+            gio::FileQueryInfoFlags::NOFOLLOW_SYMLINKS,
+            gio::Cancellable::NONE,
+        )?;
+        let mut children = Vec::new();
+        while let Some(info) = enumerator.next_file(gio::Cancellable::NONE)? {
+            children.push((dir.child(info.name()), info));
+        }
+        enumerator.close(gio::Cancellable::NONE)?;
+        Ok(children)
+    }
+
+    fn query(file: &gio::File) -> Result<gio::FileInfo, glib::Error> {
+        file.query_info(ATTRIBUTES, gio::FileQueryInfoFlags::NOFOLLOW_SYMLINKS, gio::Cancellable::NONE)
+    }
+
+    fn copy_tree(source: &gio::File, target: &gio::File) -> Result<(), glib::Error> {
+        if Self::query(source)?.file_type() != gio::FileType::Directory {
+            return source.copy(target, gio::FileCopyFlags::NOFOLLOW_SYMLINKS, gio::Cancellable::NONE, None);
+        }
+        match target.make_directory(gio::Cancellable::NONE) {
+            Err(e) if !e.matches(gio::IOErrorEnum::Exists) => return Err(e),
+            _ => {}
+        }
+        for (child, info) in Self::children(source)? {
+            Self::copy_tree(&child, &target.child(info.name()))?;
+        }
+        Ok(())
+    }
+
+    fn delete_tree(file: &gio::File) -> Result<(), glib::Error> {
+        if Self::query(file)?.file_type() == gio::FileType::Directory {
+            for (child, _) in Self::children(file)? {
+                Self::delete_tree(&child)?;
+            }
+        }
+        file.delete(gio::Cancellable::NONE)
+    }
+}
+
+impl VfsBackend for RemoteBackend {
+    fn list(&self, dir: &Path, show_hidden: bool) -> io::Result<Listing> {
+        let children = Self::children(&Remote::file(dir)).map_err(Self::io_error)?;
+        let mut entries = Vec::with_capacity(children.len());
+        let mut hidden = 0;
+        for (child, info) in &children {
+            let entry = Remote::entry_for_info(child, info);
+            if entry.is_hidden && !show_hidden {
+                hidden += 1;
+            } else {
+                entries.push(entry);
+            }
+        }
+        Ok((entries, hidden))
+    }
+
+    /// Servers can be slow to answer, so the view doesn't tie up a thread waiting for them
+    fn list_async(&self, dir: &Path, show_hidden: bool) -> Option<ListingFuture> {
+        let dir = dir.to_path_buf();
+        Some(Box::pin(async move { Remote::list_dir(&dir, show_hidden).await.map_err(Self::io_error) }))
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<FileEntry> {
+        let file = Remote::file(path);
+        let info = Self::query(&file).map_err(Self::io_error)?;
+        Ok(Remote::entry_for_info(&file, &info))
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let (data, _) = Remote::file(path).load_contents(gio::Cancellable::NONE).map_err(Self::io_error)?;
+        Ok(data.to_vec())
+    }
+
+    fn write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        Remote::file(path)
+            .replace_contents(data, None, false, gio::FileCreateFlags::REPLACE_DESTINATION, gio::Cancellable::NONE)
+            .map(|_| ())
+            .map_err(Self::io_error)
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<()> {
+        Self::copy_tree(&Remote::file(from), &Remote::file(to)).map_err(Self::io_error)
+    }
+
+    fn move_to(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let (source, target) = (Remote::file(from), Remote::file(to));
+        if source
+            .move_(&target, gio::FileCopyFlags::NO_FALLBACK_FOR_MOVE, gio::Cancellable::NONE, None)
+            .is_ok()
+        {
+            return Ok(());
+        }
+        Self::copy_tree(&source, &target)
+            .and_then(|()| Self::delete_tree(&source))
+            .map_err(Self::io_error)
+    }
+
+    fn delete(&self, path: &Path) -> io::Result<()> {
+        // Few servers have a trash
+        let file = Remote::file(path);
+        match file.trash(gio::Cancellable::NONE) {
+            Err(e) if e.matches(gio::IOErrorEnum::NotSupported) => Self::delete_tree(&file).map_err(Self::io_error),
+            result => result.map_err(Self::io_error),
+        }
+    }
+
+    fn rename(&self, path: &Path, new_name: &str) -> io::Result<PathBuf> {
+        let renamed = Remote::file(path)
+            .set_display_name(new_name, gio::Cancellable::NONE)
+            .map_err(Self::io_error)?;
+        Ok(Remote::location_of(&renamed))
+    }
+
+    fn mkdir(&self, path: &Path) -> io::Result<()> {
+        Remote::file(path).make_directory(gio::Cancellable::NONE).map_err(Self::io_error)
+    }
+
+    fn watch(&self, dir: &Path) -> io::Result<VfsWatch> {
+        VfsWatch::monitor(&Remote::file(dir))
+    }
+}
@@ -1,132 +1,324 @@
+use std::cmp::Ordering;
 use std::fs;
+use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use chrono::{DateTime, Local};
 
-#[derive(Clone, Debug)]
+use crate::core::{Archive, Mime, SortKey};
+
+/// One item in a directory listing. Values are kept raw; formatting happens when displayed.
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct FileEntry {
     pub name: String,
     pub path: PathBuf,
     pub is_directory: bool,
     pub size: u64,
-    pub modified: String,
+    pub modified: Option<SystemTime>,
+    /// Birth time, where the filesystem records one
+    pub created: Option<SystemTime>,
+    /// Inode change time (ctime)
+    pub changed: Option<SystemTime>,
+    pub accessed: Option<SystemTime>,
+    /// Permission bits only (e.g. 0o755)
+    pub mode: u32,
+    pub uid: u32,
+    pub gid: u32,
+    pub inode: u64,
+    pub device: u64,
+    pub is_symlink: bool,
+    pub symlink_target: Option<PathBuf>,
+    /// A symlink whose target doesn't exist
+    pub is_broken_link: bool,
+    pub is_hidden: bool,
     pub icon_name: String,
+    pub content_type: String,
+    pub type_description: String,
 }
 
 impl FileEntry {
+    /// Read an entry from disk. Symlinks are followed for type, size and times so that
+    /// links to folders behave like folders; broken links describe the link itself.
+    pub fn from_path(path: &Path) -> Result<FileEntry, std::io::Error> {
+        let link_metadata = fs::symlink_metadata(path)?;
+        let is_symlink = link_metadata.file_type().is_symlink();
+        let symlink_target = if is_symlink { fs::read_link(path).ok() } else { None };
+
+        let (metadata, is_broken_link) = if is_symlink {
+            match fs::metadata(path) {
+                Ok(metadata) => (metadata, false),
+                Err(_) => (link_metadata, true),
+            }
+        } else {
+            (link_metadata, false)
+        };
+
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+        let is_directory = metadata.is_dir();
+        let mime = if is_broken_link {
+            Mime::for_name(&name, false)
+        } else {
+            Mime::for_path(path, is_directory)
+        };
+
+        Ok(FileEntry {
+            is_hidden: name.starts_with('.'),
+            name,
+            path: path.to_path_buf(),
+            is_directory,
+            size: if is_directory { 0 } else { metadata.len() },
+            modified: metadata.modified().ok(),
+            created: metadata.created().ok(),
+            changed: u64::try_from(metadata.ctime())
+                .ok()
+                .map(|secs| UNIX_EPOCH + Duration::new(secs, metadata.ctime_nsec() as u32)),
+            accessed: metadata.accessed().ok(),
+            mode: metadata.mode() & 0o7777,
+            uid: metadata.uid(),
+            gid: metadata.gid(),
+            inode: metadata.ino(),
+            device: metadata.dev(),
+            is_symlink,
+            symlink_target,
+            is_broken_link,
+            icon_name: mime.icon_name,
+            content_type: mime.content_type,
+            type_description: mime.description,
+        })
+    }
+
     pub fn size_display(&self) -> String {
         if self.is_directory {
             return String::from("--");
         }
 
-        const KB: u64 = 1024;
-        const MB: u64 = KB * 1024;
-        const GB: u64 = MB * 1024;
+        format_size(self.size)
+    }
+
+    pub fn modified_display(&self) -> String {
+        self.modified.map(format_time).unwrap_or_else(|| String::from("Unknown"))
+    }
 
-        if self.size >= GB {
-            format!("{:.1} GB", self.size as f64 / GB as f64)
-        } else if self.size >= MB {
-            format!("{:.1} MB", self.size as f64 / MB as f64)
-        } else if self.size >= KB {
-            format!("{:.1} KB", self.size as f64 / KB as f64)
+    /// `ls -l` style permissions, e.g. "drwxr-xr-x"
+    pub fn permissions_display(&self) -> String {
+        let kind = if self.is_symlink {
+            'l'
+        } else if self.is_directory {
+            'd'
         } else {
-            format!("{} B", self.size)
+            '-'
+        };
+
+        let mut display = String::with_capacity(10);
+        display.push(kind);
+        for shift in [6, 3, 0] {
+            let bits = (self.mode >> shift) & 0o7;
+            display.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+            display.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+            display.push(if bits & 0o1 != 0 { 'x' } else { '-' });
         }
+        display
+    }
+}
+
+/// Human readable timestamp: "Today 14:05", "Yesterday 09:12" or "2024-03-01 18:30"
+pub fn format_time(time: SystemTime) -> String {
+    let datetime: DateTime<Local> = time.into();
+    let today = Local::now().date_naive();
+
+    if datetime.date_naive() == today {
+        format!("Today {}", datetime.format("%H:%M"))
+    } else if today.pred_opt() == Some(datetime.date_naive()) {
+        format!("Yesterday {}", datetime.format("%H:%M"))
+    } else {
+        datetime.format("%Y-%m-%d %H:%M").to_string()
+    }
+}
+
+/// Human readable size, e.g. "1.5 MB"
+pub fn format_size(size: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+    const GB: u64 = MB * 1024;
+
+    if size >= GB {
+        format!("{:.1} GB", size as f64 / GB as f64)
+    } else if size >= MB {
+        format!("{:.1} MB", size as f64 / MB as f64)
+    } else if size >= KB {
+        format!("{:.1} KB", size as f64 / KB as f64)
+    } else {
+        format!("{} B", size)
     }
 }
 
 pub struct Scanner;
 
 impl Scanner {
-    pub fn scan(path: &Path) -> Result<Vec<FileEntry>, std::io::Error> {
-        Self::scan_with_hidden(path, false)
+    /// Items in `path` and how many hidden items were left out, in the order they were read
+    pub fn scan_counting_hidden(path: &Path, show_hidden: bool) -> Result<(Vec<FileEntry>, usize), std::io::Error> {
+        let mut entries = Vec::new();
+        let hidden = Self::scan_batches(path, show_hidden, usize::MAX, |batch| {
+            entries.extend(batch);
+            true
+        })?;
+        Ok((entries, hidden))
     }
 
-    pub fn scan_with_hidden(path: &Path, show_hidden: bool) -> Result<Vec<FileEntry>, std::io::Error> {
-        // Pre-allocate with reasonable capacity to reduce reallocations
-        let mut entries = Vec::with_capacity(64);
+    /// Read `path` in batches of up to `batch_size` items, handing each to `on_batch` as soon as it
+    /// is full so that only one batch is held at a time. Reading stops early when `on_batch` returns
+    /// false. Returns how many hidden items were left out.
+    pub fn scan_batches(
+        path: &Path,
+        show_hidden: bool,
+        batch_size: usize,
+        mut on_batch: impl FnMut(Vec<FileEntry>) -> bool,
+    ) -> Result<usize, std::io::Error> {
+        // Archives are browsed as read-only folders
+        if let Some((archive, inner)) = Archive::split_path(path) {
+            let mut entries = Archive::list_dir(&archive, &inner, true)?;
+            let total = entries.len();
+            if !show_hidden {
+                entries.retain(|e| !e.is_hidden);
+            }
+            let hidden = total - entries.len();
+            while !entries.is_empty() {
+                let rest = entries.split_off(batch_size.min(entries.len()));
+                if !on_batch(std::mem::replace(&mut entries, rest)) {
+                    break;
+                }
+            }
+            return Ok(hidden);
+        }
+
+        let mut batch = Vec::new();
+        let mut hidden = 0;
 
         for entry in fs::read_dir(path)? {
-            let entry = entry?;
-            let metadata = entry.metadata()?;
-            let file_name = entry.file_name().to_string_lossy().to_string();
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    log::warn!("Failed to read an entry of {:?}: {}", path, e);
+                    continue;
+                }
+            };
 
             // Skip hidden files if not showing hidden
-            if !show_hidden && file_name.starts_with('.') {
+            if !show_hidden && entry.file_name().to_string_lossy().starts_with('.') {
+                hidden += 1;
                 continue;
             }
 
-            let is_directory = metadata.is_dir();
-            let size = if is_directory { 0 } else { metadata.len() };
-
-            let modified = metadata
-                .modified()
-                .map(|t| {
-                    let datetime: DateTime<Local> = t.into();
-                    datetime.format("%Y-%m-%d %H:%M").to_string()
-                })
-                .unwrap_or_else(|_| String::from("Unknown"));
-
-            let icon_name = Self::get_icon_name(&file_name, is_directory);
-
-            entries.push(FileEntry {
-                name: file_name,
-                path: entry.path(),
-                is_directory,
-                size,
-                modified,
-                icon_name,
-            });
+            // One unreadable entry (e.g. vanished mid-scan) shouldn't fail the whole listing
+            match FileEntry::from_path(&entry.path()) {
+                Ok(file_entry) => batch.push(file_entry),
+                Err(e) => log::warn!("Failed to read {:?}: {}", entry.path(), e),
+            }
+
+            if batch.len() >= batch_size && !on_batch(std::mem::take(&mut batch)) {
+                return Ok(hidden);
+            }
         }
 
-        // Sort: directories first, then alphabetically (case-insensitive)
-        entries.sort_by_cached_key(|e| {
-            (!e.is_directory, e.name.to_lowercase())
-        });
+        if !batch.is_empty() {
+            on_batch(batch);
+        }
+        Ok(hidden)
+    }
 
-        Ok(entries)
+    /// Order entries by a key, folders first. Ties are broken by name.
+    pub fn sort(entries: &mut [FileEntry], key: SortKey, descending: bool) {
+        entries.sort_by(|a, b| Self::compare(a, b, key, descending));
     }
 
-    fn get_icon_name(name: &str, is_directory: bool) -> String {
-        if is_directory {
-            return String::from("folder");
+    /// The order `sort` puts two entries in, for placing a single entry in a sorted list
+    pub fn compare(a: &FileEntry, b: &FileEntry, key: SortKey, descending: bool) -> Ordering {
+        // Case-insensitive without allocating, as this runs for every comparison
+        let by_name = || {
+            a.name.chars().flat_map(char::to_lowercase).cmp(b.name.chars().flat_map(char::to_lowercase))
+        };
+        let order = match key {
+            SortKey::Name => Ordering::Equal,
+            SortKey::Size => a.size.cmp(&b.size),
+            SortKey::Type => a.type_description.cmp(&b.type_description),
+            SortKey::Modified => a.modified.cmp(&b.modified),
         }
+        .then_with(by_name);
 
-        let extension = name.rsplit('.').next().unwrap_or("").to_lowercase();
+        b.is_directory
+            .cmp(&a.is_directory)
+            .then(if descending { order.reverse() } else { order })
+    }
+}
 
-        match extension.as_str() {
-            // Documents
-            "pdf" => "application-pdf",
-            "doc" | "docx" | "odt" => "x-office-document",
-            "xls" | "xlsx" | "ods" => "x-office-spreadsheet",
-            "ppt" | "pptx" | "odp" => "x-office-presentation",
-            "txt" | "md" | "rst" => "text-x-generic",
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            // Images
-            "png" | "jpg" | "jpeg" | "gif" | "bmp" | "svg" | "webp" => "image-x-generic",
+    /// A fresh folder under the temporary directory holding `names`
+    fn folder_with(test: &str, names: &[&str]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("blink-scanner-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for name in names {
+            fs::write(dir.join(name), b"").unwrap();
+        }
+        dir
+    }
 
-            // Audio
-            "mp3" | "wav" | "flac" | "ogg" | "m4a" => "audio-x-generic",
+    fn entry(name: &str, is_directory: bool, size: u64) -> FileEntry {
+        FileEntry { name: name.to_string(), is_directory, size, ..Default::default() }
+    }
 
-            // Video
-            "mp4" | "mkv" | "avi" | "mov" | "webm" => "video-x-generic",
+    #[test]
+    fn scan_batches_hands_over_full_batches() {
+        let dir = folder_with("batches", &["a", "b", "c", "d", "e", ".hidden"]);
+        let mut sizes = Vec::new();
+        let hidden = Scanner::scan_batches(&dir, false, 2, |batch| {
+            sizes.push(batch.len());
+            true
+        })
+        .unwrap();
+        assert_eq!(sizes, [2, 2, 1]);
+        assert_eq!(hidden, 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
 
-            // Archives
-            "zip" | "tar" | "gz" | "bz2" | "xz" | "rar" | "7z" => "package-x-generic",
+    #[test]
+    fn scan_batches_stops_when_asked() {
+        let dir = folder_with("stop", &["a", "b", "c", "d", "e"]);
+        let mut batches = 0;
+        Scanner::scan_batches(&dir, true, 2, |_| {
+            batches += 1;
+            false
+        })
+        .unwrap();
+        assert_eq!(batches, 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
 
-            // Code
-            "rs" | "py" | "js" | "ts" | "c" | "cpp" | "h" | "java" | "go" | "rb" => {
-                "text-x-script"
-            }
-            "html" | "css" | "xml" | "json" | "yaml" | "yml" | "toml" => "text-x-script",
+    #[test]
+    fn scan_counting_hidden_keeps_hidden_items_when_shown() {
+        let dir = folder_with("hidden", &["a", ".b"]);
+        let (entries, hidden) = Scanner::scan_counting_hidden(&dir, true).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(hidden, 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
 
-            // Executables
-            "sh" | "bash" => "application-x-executable",
-            "exe" | "msi" => "application-x-executable",
-            "deb" | "rpm" | "AppImage" => "application-x-executable",
+    #[test]
+    fn sort_puts_folders_first_and_breaks_ties_by_name() {
+        let mut entries = vec![entry("b", false, 1), entry("Z", true, 0), entry("a", false, 1), entry("c", false, 0)];
+        Scanner::sort(&mut entries, SortKey::Size, false);
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Z", "c", "a", "b"]);
 
-            _ => "text-x-generic",
-        }
-        .to_string()
+        Scanner::sort(&mut entries, SortKey::Name, true);
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Z", "c", "b", "a"]);
     }
 }
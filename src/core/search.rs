@@ -108,29 +108,7 @@ impl GlobalSearch {
 
             // Check if name matches query
             if file_name.to_lowercase().contains(query) {
-                if let Ok(metadata) = entry.metadata() {
-                    let is_directory = metadata.is_dir();
-                    let size = if is_directory { 0 } else { metadata.len() };
-
-                    let modified = metadata
-                        .modified()
-                        .map(|t| {
-                            let datetime: chrono::DateTime<chrono::Local> = t.into();
-                            datetime.format("%Y-%m-%d %H:%M").to_string()
-                        })
-                        .unwrap_or_else(|_| String::from("Unknown"));
-
-                    let icon_name = Self::get_icon_name(&file_name, is_directory);
-
-                    let file_entry = FileEntry {
-                        name: file_name.clone(),
-                        path: entry.path(),
-                        is_directory,
-                        size,
-                        modified,
-                        icon_name,
-                    };
-
+                if let Ok(file_entry) = FileEntry::from_path(&entry.path()) {
                     results.lock().unwrap().push(file_entry);
                     *count += 1;
 
@@ -167,32 +145,6 @@ impl GlobalSearch {
         }
     }
 
-    fn get_icon_name(name: &str, is_directory: bool) -> String {
-        if is_directory {
-            return String::from("folder");
-        }
-
-        let extension = name.rsplit('.').next().unwrap_or("").to_lowercase();
-
-        match extension.as_str() {
-            "pdf" => "application-pdf",
-            "doc" | "docx" | "odt" => "x-office-document",
-            "xls" | "xlsx" | "ods" => "x-office-spreadsheet",
-            "txt" | "md" | "rst" => "text-x-generic",
-            "png" | "jpg" | "jpeg" | "gif" | "bmp" | "svg" | "webp" => "image-x-generic",
-            "mp3" | "wav" | "flac" | "ogg" | "m4a" => "audio-x-generic",
-            "mp4" | "mkv" | "avi" | "mov" | "webm" => "video-x-generic",
-            "zip" | "tar" | "gz" | "bz2" | "xz" | "rar" | "7z" => "package-x-generic",
-            "rs" | "py" | "js" | "ts" | "c" | "cpp" | "h" | "java" | "go" | "rb" => {
-                "text-x-script"
-            }
-            "html" | "css" | "xml" | "json" | "yaml" | "yml" | "toml" => "text-x-script",
-            "sh" | "bash" => "application-x-executable",
-            _ => "text-x-generic",
-        }
-        .to_string()
-    }
-
     pub fn cancel(&self) {
         *self.is_searching.lock().unwrap() = false;
     }
@@ -0,0 +1,83 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{FileOperations, ViewMode};
+
+const SESSION_FILE: &str = "session.json";
+
+/// Everything needed to reopen one window the way it was left
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowState {
+    pub width: i32,
+    pub height: i32,
+    pub maximized: bool,
+    pub location: PathBuf,
+    /// Back/forward history, oldest first
+    pub history: Vec<PathBuf>,
+    /// Position of `location` in `history`
+    pub history_index: usize,
+    pub view_mode: ViewMode,
+    pub selection: Vec<PathBuf>,
+    /// How far the view was scrolled down
+    #[serde(default)]
+    pub scroll: f64,
+}
+
+impl WindowState {
+    /// Drop history entries that no longer exist, keeping the index on the same location
+    pub fn pruned(mut self) -> Option<WindowState> {
+        let current = self.history.get(self.history_index).cloned();
+        self.history.retain(|path| path.is_dir());
+
+        if !self.location.is_dir() {
+            self.location = self.history.last().cloned()?;
+        }
+        self.history_index = current
+            .and_then(|current| self.history.iter().position(|p| *p == current))
+            .or_else(|| self.history.iter().position(|p| *p == self.location))
+            .unwrap_or_else(|| {
+                self.history.push(self.location.clone());
+                self.history.len() - 1
+            });
+        self.selection.retain(|path| path.exists());
+        Some(self)
+    }
+}
+
+/// Open windows at the time blink was last closed, saved in ~/.local/state/blink/session.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Session {
+    pub windows: Vec<WindowState>,
+}
+
+impl Session {
+    fn state_path() -> PathBuf {
+        dirs::state_dir()
+            .or_else(|| dirs::home_dir().map(|home| home.join(".local").join("state")))
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("blink")
+            .join(SESSION_FILE)
+    }
+
+    pub fn load() -> Session {
+        match fs::read_to_string(Self::state_path()) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("Failed to parse session: {}", e);
+                Session::default()
+            }),
+            Err(_) => Session::default(),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), std::io::Error> {
+        let state_path = Self::state_path();
+        if let Some(parent) = state_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        FileOperations::write_atomically(&state_path, &content)
+    }
+}
@@ -0,0 +1,363 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use gtk4::{gdk_pixbuf, gio, glib};
+use gtk4::prelude::*;
+
+use crate::core::Mime;
+
+/// External thumbnailers still running after this are killed
+const THUMBNAILER_TIMEOUT: Duration = Duration::from_secs(10);
+/// How often a running thumbnailer is checked on
+const THUMBNAILER_POLL: Duration = Duration::from_millis(50);
+
+/// Thumbnail sizes from the freedesktop thumbnail spec
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThumbnailSize {
+    Normal,
+    Large,
+}
+
+impl ThumbnailSize {
+    pub fn pixels(&self) -> i32 {
+        match self {
+            ThumbnailSize::Normal => 128,
+            ThumbnailSize::Large => 256,
+        }
+    }
+
+    fn dir_name(&self) -> &'static str {
+        match self {
+            ThumbnailSize::Normal => "normal",
+            ThumbnailSize::Large => "large",
+        }
+    }
+}
+
+/// An installed `.thumbnailer` entry (e.g. from totem or evince)
+#[derive(Debug, Clone)]
+struct ThumbnailerEntry {
+    exec: String,
+    mime_types: Vec<String>,
+}
+
+/// Reads and writes the shared thumbnail cache in ~/.cache/thumbnails
+pub struct Thumbnails;
+
+impl Thumbnails {
+    fn cache_root() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from("/tmp"))
+            .join("thumbnails")
+    }
+
+    /// Cache file name: MD5 of the file's URI
+    fn cache_name(uri: &str) -> String {
+        let hash = glib::compute_checksum_for_string(glib::ChecksumType::Md5, uri)
+            .map(|h| h.to_string())
+            .unwrap_or_default();
+        format!("{}.png", hash)
+    }
+
+    fn mtime_secs(path: &Path) -> Option<u64> {
+        fs::metadata(path)
+            .ok()?
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .map(|d| d.as_secs())
+    }
+
+    /// Whether a cached thumbnail still matches the file's modification time
+    fn is_valid(thumbnail: &Path, mtime: u64) -> bool {
+        gdk_pixbuf::Pixbuf::from_file(thumbnail)
+            .ok()
+            .and_then(|pixbuf| pixbuf.option("tEXt::Thumb::MTime"))
+            .and_then(|value| value.parse::<u64>().ok())
+            == Some(mtime)
+    }
+
+    /// Return an up to date thumbnail for a file, generating it if needed.
+    /// Returns None for files that can't be thumbnailed. Slow, run on a worker thread.
+    pub fn get_or_create(path: &Path, size: ThumbnailSize) -> Option<PathBuf> {
+        let mtime = Self::mtime_secs(path)?;
+        if path.is_dir() {
+            return None;
+        }
+
+        let uri = gio::File::for_path(path).uri().to_string();
+        let name = Self::cache_name(&uri);
+        let root = Self::cache_root();
+
+        let cached = root.join(size.dir_name()).join(&name);
+        if cached.exists() && Self::is_valid(&cached, mtime) {
+            return Some(cached);
+        }
+
+        // Don't retry files that failed before and haven't changed since
+        let failed = root.join("fail").join("blink").join(&name);
+        if failed.exists() && Self::is_valid(&failed, mtime) {
+            return None;
+        }
+
+        let mime_type = Mime::mime_type(&Mime::for_path(path, false).content_type);
+        let pixbuf = if mime_type.starts_with("image/") {
+            Self::scale_image(path, size)
+        } else {
+            match Self::thumbnailer_for(&mime_type) {
+                Some(thumbnailer) => Self::run_thumbnailer(&thumbnailer, path, &uri, size),
+                None => return None,
+            }
+        };
+
+        let options = [
+            ("tEXt::Thumb::URI", uri.clone()),
+            ("tEXt::Thumb::MTime", mtime.to_string()),
+            ("tEXt::Thumb::Mimetype", mime_type),
+            ("tEXt::Software", "blink".to_string()),
+        ];
+
+        match pixbuf {
+            Some(pixbuf) => {
+                let mut options = options.to_vec();
+                if let Ok(metadata) = fs::metadata(path) {
+                    options.push(("tEXt::Thumb::Size", metadata.len().to_string()));
+                }
+                Self::save(&pixbuf, &cached, &options).ok()?;
+                Some(cached)
+            }
+            None => {
+                // Record the failure so other file managers (and we) skip it next time
+                if let Some(marker) = gdk_pixbuf::Pixbuf::new(gdk_pixbuf::Colorspace::Rgb, true, 8, 1, 1) {
+                    marker.fill(0);
+                    let _ = Self::save(&marker, &failed, &options);
+                }
+                None
+            }
+        }
+    }
+
+    /// Write a thumbnail atomically with private permissions, as the spec requires
+    fn save(pixbuf: &gdk_pixbuf::Pixbuf, destination: &Path, options: &[(&str, String)]) -> io::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = destination.parent().unwrap_or(Path::new("/"));
+        fs::create_dir_all(dir)?;
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o700))?;
+
+        let temp = dir.join(format!(
+            ".{}.{}.tmp",
+            destination.file_name().unwrap_or_default().to_string_lossy(),
+            std::process::id()
+        ));
+        let options: Vec<(&str, &str)> = options.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        pixbuf
+            .savev(&temp, "png", &options)
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        fs::set_permissions(&temp, fs::Permissions::from_mode(0o600))?;
+        fs::rename(&temp, destination)
+    }
+
+    /// Scale an image down to the thumbnail size (never up)
+    fn scale_image(path: &Path, size: ThumbnailSize) -> Option<gdk_pixbuf::Pixbuf> {
+        let (_, width, height) = gdk_pixbuf::Pixbuf::file_info(path)?;
+        let max = size.pixels();
+        if width <= max && height <= max {
+            gdk_pixbuf::Pixbuf::from_file(path).ok()
+        } else {
+            gdk_pixbuf::Pixbuf::from_file_at_scale(path, max, max, true).ok()
+        }
+    }
+
+    /// All installed thumbnailers, read once from $XDG_DATA_DIRS/thumbnailers
+    fn thumbnailers() -> &'static [ThumbnailerEntry] {
+        static THUMBNAILERS: OnceLock<Vec<ThumbnailerEntry>> = OnceLock::new();
+
+        THUMBNAILERS.get_or_init(|| {
+            let mut data_dirs = vec![glib::user_data_dir()];
+            data_dirs.extend(glib::system_data_dirs());
+
+            let mut entries = Vec::new();
+            for dir in data_dirs {
+                let Ok(files) = fs::read_dir(dir.join("thumbnailers")) else {
+                    continue;
+                };
+
+                for file in files.flatten() {
+                    let path = file.path();
+                    if path.extension().and_then(|e| e.to_str()) != Some("thumbnailer") {
+                        continue;
+                    }
+
+                    let key_file = glib::KeyFile::new();
+                    if key_file.load_from_file(&path, glib::KeyFileFlags::NONE).is_err() {
+                        continue;
+                    }
+                    let Ok(exec) = key_file.string("Thumbnailer Entry", "Exec") else {
+                        continue;
+                    };
+                    let mime_types = key_file
+                        .string_list("Thumbnailer Entry", "MimeType")
+                        .map(|list| list.iter().map(|m| m.to_string()).collect())
+                        .unwrap_or_default();
+
+                    entries.push(ThumbnailerEntry {
+                        exec: exec.to_string(),
+                        mime_types,
+                    });
+                }
+            }
+            entries
+        })
+    }
+
+    fn thumbnailer_for(mime_type: &str) -> Option<ThumbnailerEntry> {
+        Self::thumbnailers()
+            .iter()
+            .find(|entry| entry.mime_types.iter().any(|m| m == mime_type))
+            .cloned()
+    }
+
+    /// Run an external thumbnailer, substituting %u, %i, %o and %s in its Exec line
+    fn run_thumbnailer(
+        thumbnailer: &ThumbnailerEntry,
+        path: &Path,
+        uri: &str,
+        size: ThumbnailSize,
+    ) -> Option<gdk_pixbuf::Pixbuf> {
+        let output = std::env::temp_dir().join(format!(
+            "blink-thumbnail-{}-{}.png",
+            std::process::id(),
+            Self::cache_name(uri).trim_end_matches(".png")
+        ));
+
+        let args: Vec<String> = glib::shell_parse_argv(&thumbnailer.exec)
+            .ok()?
+            .iter()
+            .map(|arg| {
+                let arg = arg.to_string_lossy();
+                let mut result = String::new();
+                let mut chars = arg.chars();
+                while let Some(c) = chars.next() {
+                    if c != '%' {
+                        result.push(c);
+                        continue;
+                    }
+                    match chars.next() {
+                        Some('u') => result.push_str(uri),
+                        Some('i') => result.push_str(&path.to_string_lossy()),
+                        Some('o') => result.push_str(&output.to_string_lossy()),
+                        Some('s') => result.push_str(&size.pixels().to_string()),
+                        Some('%') => result.push('%'),
+                        _ => {}
+                    }
+                }
+                result
+            })
+            .collect();
+
+        let (program, rest) = args.split_first()?;
+        let success = Self::run_with_timeout(Command::new(program).args(rest));
+
+        let pixbuf = if success {
+            gdk_pixbuf::Pixbuf::from_file(&output).ok()
+        } else {
+            None
+        };
+        let _ = fs::remove_file(&output);
+        pixbuf
+    }
+
+    /// Run a thumbnailer, killing it if it hangs so it can't hold a worker forever
+    fn run_with_timeout(command: &mut Command) -> bool {
+        let Ok(mut child) = command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).spawn() else {
+            return false;
+        };
+
+        let deadline = Instant::now() + THUMBNAILER_TIMEOUT;
+        loop {
+            match child.try_wait() {
+                Ok(Some(status)) => return status.success(),
+                Ok(None) if Instant::now() < deadline => std::thread::sleep(THUMBNAILER_POLL),
+                Ok(None) => {
+                    log::warn!("Thumbnailer {:?} timed out", command.get_program());
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return false;
+                }
+                Err(_) => return false,
+            }
+        }
+    }
+}
+
+/// Generates thumbnails on a small pool of worker threads.
+/// Results are (source file, thumbnail png) pairs.
+pub struct ThumbnailPool {
+    requests: async_channel::Sender<(u64, PathBuf, ThumbnailSize)>,
+    results: async_channel::Receiver<(PathBuf, PathBuf)>,
+    generation: Arc<AtomicU64>,
+}
+
+impl ThumbnailPool {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = async_channel::unbounded::<(u64, PathBuf, ThumbnailSize)>();
+        let (result_tx, result_rx) = async_channel::unbounded::<(PathBuf, PathBuf)>();
+        let generation = Arc::new(AtomicU64::new(0));
+
+        let workers = std::thread::available_parallelism()
+            .map(|n| n.get().clamp(2, 4))
+            .unwrap_or(2);
+
+        for _ in 0..workers {
+            let request_rx = request_rx.clone();
+            let result_tx = result_tx.clone();
+            let generation = generation.clone();
+            std::thread::spawn(move || {
+                while let Ok((request_generation, path, size)) = request_rx.recv_blocking() {
+                    // Skip requests from a folder we already left
+                    if request_generation != generation.load(Ordering::Relaxed) {
+                        continue;
+                    }
+                    if let Some(thumbnail) = Thumbnails::get_or_create(&path, size) {
+                        if result_tx.send_blocking((path, thumbnail)).is_err() {
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+
+        Self {
+            requests: request_tx,
+            results: result_rx,
+            generation,
+        }
+    }
+
+    pub fn request(&self, path: PathBuf, size: ThumbnailSize) {
+        let generation = self.generation.load(Ordering::Relaxed);
+        let _ = self.requests.send_blocking((generation, path, size));
+    }
+
+    /// Drop all queued requests (e.g. after changing directory)
+    pub fn cancel_pending(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn results(&self) -> async_channel::Receiver<(PathBuf, PathBuf)> {
+        self.results.clone()
+    }
+}
+
+impl Default for ThumbnailPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,180 @@
+use std::any::Any;
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+
+use gtk4::gio;
+use gtk4::prelude::*;
+
+use crate::core::{FileEntry, LocalBackend, RecentBackend, RecentFiles, Remote, RemoteBackend};
+
+/// The items of a folder and how many hidden ones were left out
+pub type Listing = (Vec<FileEntry>, usize);
+
+/// A listing read on the main loop
+pub type ListingFuture = Pin<Box<dyn Future<Output = io::Result<Listing>>>>;
+
+/// Storage the file view browses and changes: the local disk, a server, or a fixture in memory.
+/// Everything but `list_async` and `watch` blocks, so call it from a worker thread.
+pub trait VfsBackend: Send + Sync {
+    /// Items in `dir`, leaving hidden ones out unless `show_hidden`; also returns how many were left out
+    fn list(&self, dir: &Path, show_hidden: bool) -> io::Result<Listing>;
+
+    /// `list` for backends whose I/O runs on the main loop; call it on the main thread.
+    /// `None` means the backend lists with `list` on a worker thread.
+    fn list_async(&self, _dir: &Path, _show_hidden: bool) -> Option<ListingFuture> {
+        None
+    }
+
+    /// `list`, handing the items to `on_batch` in batches of up to `batch_size` as they are read.
+    /// Stops early when `on_batch` returns false. Returns how many hidden items were left out.
+    fn list_batches(
+        &self,
+        dir: &Path,
+        show_hidden: bool,
+        batch_size: usize,
+        on_batch: &mut dyn FnMut(Vec<FileEntry>) -> bool,
+    ) -> io::Result<usize> {
+        let (entries, hidden) = self.list(dir, show_hidden)?;
+        let mut entries = entries.into_iter();
+        loop {
+            let batch: Vec<FileEntry> = entries.by_ref().take(batch_size).collect();
+            if batch.is_empty() || !on_batch(batch) {
+                break;
+            }
+        }
+        Ok(hidden)
+    }
+
+    /// How many hidden items `dir` holds, for keeping the count of items left out up to date
+    fn count_hidden(&self, dir: &Path) -> io::Result<usize> {
+        self.list(dir, false).map(|(_, hidden)| hidden)
+    }
+
+    fn stat(&self, path: &Path) -> io::Result<FileEntry>;
+
+    fn exists(&self, path: &Path) -> bool {
+        self.stat(path).is_ok()
+    }
+
+    /// The whole contents of a file
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+    /// Create or replace a file
+    fn write(&self, path: &Path, data: &[u8]) -> io::Result<()>;
+
+    /// Whether items at `path` can only be looked at, e.g. inside an archive
+    fn is_read_only(&self, _path: &Path) -> bool {
+        false
+    }
+
+    /// A location applications can open the item from, e.g. a temporary copy of an archive member
+    fn local_path(&self, path: &Path) -> io::Result<PathBuf> {
+        Ok(path.to_path_buf())
+    }
+
+    /// Copy a file or a whole folder to `to`
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<()>;
+
+    fn move_to(&self, from: &Path, to: &Path) -> io::Result<()>;
+
+    /// Move an item to the trash, or delete it for good where there is none
+    fn delete(&self, path: &Path) -> io::Result<()>;
+
+    /// Give an item a new name in the same folder, returning its new location
+    fn rename(&self, path: &Path, new_name: &str) -> io::Result<PathBuf>;
+
+    fn mkdir(&self, path: &Path) -> io::Result<()>;
+
+    /// Report changes to the items of `dir` until the returned watch is dropped.
+    /// Call this on the main thread.
+    fn watch(&self, dir: &Path) -> io::Result<VfsWatch>;
+}
+
+/// Changes to a watched folder, as the locations of the items that changed
+pub struct VfsWatch {
+    pub changes: async_channel::Receiver<PathBuf>,
+    /// Whatever keeps the changes coming, e.g. a file monitor
+    _source: Box<dyn Any>,
+}
+
+impl VfsWatch {
+    pub fn new(changes: async_channel::Receiver<PathBuf>, source: Box<dyn Any>) -> Self {
+        Self { changes, _source: source }
+    }
+
+    /// Watch a folder with a GIO file monitor
+    pub fn monitor(dir: &gio::File) -> io::Result<Self> {
+        let monitor = dir
+            .monitor_directory(gio::FileMonitorFlags::WATCH_MOVES, gio::Cancellable::NONE)
+            .map_err(|e| io::Error::other(e.to_string()))?;
+
+        let (tx, rx) = async_channel::unbounded();
+        monitor.connect_changed(move |_, file, other_file, event| {
+            // Writes come as a burst of Changed events; wait for the hint that they are done
+            if event == gio::FileMonitorEvent::Changed {
+                return;
+            }
+            for file in std::iter::once(file).chain(other_file) {
+                let _ = tx.send_blocking(Remote::location_of(file));
+            }
+        });
+
+        Ok(Self::new(rx, Box::new(monitor)))
+    }
+}
+
+/// Picks the backend for a location. Views take one, so tests can hand them a fixture.
+#[derive(Clone, Default)]
+pub struct Vfs {
+    fixed: Option<Arc<dyn VfsBackend>>,
+}
+
+impl Vfs {
+    /// Use `backend` for every location, e.g. a `MemoryBackend`
+    #[cfg(test)]
+    pub fn fixed(backend: Arc<dyn VfsBackend>) -> Self {
+        Self { fixed: Some(backend) }
+    }
+
+    pub fn backend(&self, path: &Path) -> Arc<dyn VfsBackend> {
+        self.fixed.clone().unwrap_or_else(|| Self::for_path(path))
+    }
+
+    pub fn for_path(path: &Path) -> Arc<dyn VfsBackend> {
+        if Remote::is_remote(path) {
+            Arc::new(RemoteBackend)
+        } else if RecentFiles::is_recent(path) {
+            Arc::new(RecentBackend)
+        } else {
+            Arc::new(LocalBackend)
+        }
+    }
+
+    /// Location of the item called `name` in `dir`, escaped as remote URIs need
+    pub fn child(dir: &Path, name: &str) -> PathBuf {
+        if Remote::is_remote(dir) {
+            Remote::child(dir, name)
+        } else {
+            dir.join(name)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::MemoryBackend;
+
+    #[test]
+    fn fixed_vfs_uses_the_backend_everywhere() {
+        let memory = MemoryBackend::new();
+        memory.add_file(Path::new("/docs/a.txt"), b"");
+        let vfs = Vfs::fixed(Arc::new(memory));
+        for path in ["/docs", "sftp://example.com/home", "recent:///"] {
+            assert!(vfs.backend(Path::new(path)).exists(Path::new("/docs/a.txt")));
+        }
+    }
+}
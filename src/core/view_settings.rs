@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::SidebarPrefs;
+
+const VIEW_SETTINGS_FILE: &str = "view-settings.json";
+
+/// Icon sizes of the grid zoom steps
+const GRID_ICON_SIZES: [i32; 7] = [32, 48, 64, 96, 128, 192, 256];
+/// Icon sizes of the list zoom steps
+const LIST_ICON_SIZES: [i32; 5] = [16, 24, 32, 48, 64];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViewMode {
+    Grid,
+    List,
+}
+
+/// What directory listings are ordered by. Folders always come first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    Name,
+    Size,
+    Type,
+    Modified,
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 4] = [SortKey::Name, SortKey::Size, SortKey::Type, SortKey::Modified];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            SortKey::Name => "name",
+            SortKey::Size => "size",
+            SortKey::Type => "type",
+            SortKey::Modified => "modified",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<SortKey> {
+        SortKey::ALL.into_iter().find(|key| key.id() == id)
+    }
+}
+
+/// How one directory is displayed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewSettings {
+    pub view_mode: ViewMode,
+    pub sort_key: SortKey,
+    pub sort_descending: bool,
+    /// Zoom step for the grid view, 0 is the smallest
+    pub grid_zoom: usize,
+    /// Zoom step for the list view
+    pub list_zoom: usize,
+    pub show_hidden: bool,
+}
+
+impl Default for ViewSettings {
+    fn default() -> Self {
+        Self {
+            view_mode: ViewMode::Grid,
+            sort_key: SortKey::Name,
+            sort_descending: false,
+            grid_zoom: 2,
+            list_zoom: 2,
+            show_hidden: false,
+        }
+    }
+}
+
+impl ViewSettings {
+    fn icon_sizes(mode: ViewMode) -> &'static [i32] {
+        match mode {
+            ViewMode::Grid => &GRID_ICON_SIZES,
+            ViewMode::List => &LIST_ICON_SIZES,
+        }
+    }
+
+    /// Zoom step of the current view mode
+    pub fn zoom(&self) -> usize {
+        match self.view_mode {
+            ViewMode::Grid => self.grid_zoom,
+            ViewMode::List => self.list_zoom,
+        }
+    }
+
+    /// Set the zoom step of the current view mode, clamped to the available steps
+    pub fn set_zoom(&mut self, zoom: usize) {
+        let zoom = zoom.min(Self::icon_sizes(self.view_mode).len() - 1);
+        match self.view_mode {
+            ViewMode::Grid => self.grid_zoom = zoom,
+            ViewMode::List => self.list_zoom = zoom,
+        }
+    }
+
+    pub fn can_zoom_in(&self) -> bool {
+        self.zoom() + 1 < Self::icon_sizes(self.view_mode).len()
+    }
+
+    pub fn can_zoom_out(&self) -> bool {
+        self.zoom() > 0
+    }
+
+    /// Icon size in pixels for a view mode at its zoom step
+    pub fn icon_size(&self, mode: ViewMode) -> i32 {
+        let sizes = Self::icon_sizes(mode);
+        let zoom = match mode {
+            ViewMode::Grid => self.grid_zoom,
+            ViewMode::List => self.list_zoom,
+        };
+        sizes[zoom.min(sizes.len() - 1)]
+    }
+}
+
+/// Per-directory view settings with a global default, saved in ~/.config/blink/view-settings.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewSettingsStore {
+    pub default: ViewSettings,
+    /// Directories without their own settings use their nearest configured ancestor's
+    pub inherit_from_parent: bool,
+    directories: HashMap<PathBuf, ViewSettings>,
+}
+
+impl Default for ViewSettingsStore {
+    fn default() -> Self {
+        Self {
+            default: ViewSettings {
+                show_hidden: SidebarPrefs::show_hidden_files(),
+                ..ViewSettings::default()
+            },
+            inherit_from_parent: false,
+            directories: HashMap::new(),
+        }
+    }
+}
+
+impl ViewSettingsStore {
+    fn config_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("blink")
+            .join(VIEW_SETTINGS_FILE)
+    }
+
+    pub fn load() -> Self {
+        match fs::read_to_string(Self::config_path()) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("Failed to parse view settings: {}", e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), std::io::Error> {
+        let config_path = Self::config_path();
+        if let Some(parent) = config_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        fs::write(&config_path, content)
+    }
+
+    /// Settings a directory is shown with
+    pub fn settings_for(&self, path: &Path) -> ViewSettings {
+        if let Some(settings) = self.directories.get(path) {
+            return *settings;
+        }
+        self.inherited_for(path)
+    }
+
+    /// What a directory gets when it has no settings of its own
+    fn inherited_for(&self, path: &Path) -> ViewSettings {
+        if self.inherit_from_parent {
+            if let Some(settings) = path.ancestors().skip(1).find_map(|a| self.directories.get(a)) {
+                return *settings;
+            }
+        }
+        self.default
+    }
+
+    /// Remember settings for a directory. Settings that match what it would get anyway aren't stored.
+    pub fn set_for(&mut self, path: &Path, settings: ViewSettings) {
+        if settings == self.inherited_for(path) {
+            self.directories.remove(path);
+        } else {
+            self.directories.insert(path.to_path_buf(), settings);
+        }
+    }
+
+    /// Forget a directory's own settings
+    pub fn reset(&mut self, path: &Path) {
+        self.directories.remove(path);
+    }
+}
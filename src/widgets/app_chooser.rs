@@ -0,0 +1,117 @@
+use gtk4::prelude::*;
+use gtk4::{gio, glib, Label, ListBox, ScrolledWindow};
+use libadwaita as adw;
+use adw::prelude::*;
+use std::rc::Rc;
+
+use crate::core::Applications;
+
+/// Picks an application for a content type: the ones registered for it first, then every other one
+pub struct AppChooser;
+
+impl AppChooser {
+    pub fn present<F: Fn(gio::AppInfo) + 'static>(
+        parent: &impl IsA<gtk4::Widget>,
+        content_type: &str,
+        heading: &str,
+        on_chosen: F,
+    ) {
+        let recommended = Applications::for_content_types(&[content_type.to_string()]);
+        let recommended_ids: Vec<_> = recommended.iter().filter_map(|app| app.id()).collect();
+        let mut others: Vec<gio::AppInfo> = gio::AppInfo::all()
+            .into_iter()
+            .filter(|app| app.should_show() && !app.id().is_some_and(|id| recommended_ids.contains(&id)))
+            .collect();
+        others.sort_by_key(|app| app.display_name().to_lowercase());
+        let first_other = recommended.len();
+        let apps: Rc<Vec<gio::AppInfo>> = Rc::new(recommended.into_iter().chain(others).collect());
+
+        let dialog = adw::AlertDialog::builder()
+            .heading(heading)
+            .body(gio::content_type_get_description(content_type).as_str())
+            .build();
+
+        let list = ListBox::builder()
+            .selection_mode(gtk4::SelectionMode::Single)
+            .css_classes(["boxed-list"])
+            .build();
+        for app in apps.iter() {
+            let row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&app.display_name()).as_str())
+                .activatable(true)
+                .build();
+            let icon = gtk4::Image::builder().pixel_size(32).build();
+            match app.icon() {
+                Some(gicon) => icon.set_from_gicon(&gicon),
+                None => icon.set_icon_name(Some("application-x-executable")),
+            }
+            row.add_prefix(&icon);
+            list.append(&row);
+        }
+        list.set_header_func(move |row, _| {
+            let index = row.index() as usize;
+            if index == first_other && index > 0 {
+                let label = Label::builder()
+                    .label("Other Applications")
+                    .xalign(0.0)
+                    .margin_top(12)
+                    .margin_bottom(6)
+                    .margin_start(6)
+                    .css_classes(["heading"])
+                    .build();
+                row.set_header(Some(&label));
+            } else {
+                row.set_header(None::<&gtk4::Widget>);
+            }
+        });
+
+        let scrolled = ScrolledWindow::builder()
+            .child(&list)
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .propagate_natural_height(true)
+            .max_content_height(400)
+            .build();
+        dialog.set_extra_child(Some(&scrolled));
+
+        dialog.add_response("cancel", "Cancel");
+        dialog.add_response("select", "Select");
+        dialog.set_response_appearance("select", adw::ResponseAppearance::Suggested);
+        dialog.set_response_enabled("select", false);
+        dialog.set_default_response(Some("select"));
+        dialog.set_close_response("cancel");
+
+        list.connect_row_selected(glib::clone!(
+            #[weak] dialog,
+            move |_, row| dialog.set_response_enabled("select", row.is_some())
+        ));
+        if let Some(first) = list.row_at_index(0) {
+            list.select_row(Some(&first));
+        }
+
+        let on_chosen = Rc::new(on_chosen);
+        // Activating a row picks it straight away
+        list.connect_row_activated(glib::clone!(
+            #[weak] dialog,
+            #[strong] apps,
+            #[strong] on_chosen,
+            move |_, row| {
+                if let Some(app) = apps.get(row.index() as usize) {
+                    on_chosen(app.clone());
+                }
+                dialog.close();
+            }
+        ));
+
+        dialog.connect_response(None, move |_, response| {
+            if response != "select" {
+                return;
+            }
+            let chosen = list.selected_row().and_then(|row| apps.get(row.index() as usize).cloned());
+            if let Some(app) = chosen {
+                on_chosen(app);
+            }
+        });
+
+        dialog.present(Some(parent));
+    }
+}
@@ -0,0 +1,109 @@
+use gtk4::prelude::*;
+use libadwaita as adw;
+use adw::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::core::{ColumnLayout, ListColumn};
+
+/// Lets the user pick and order the list view columns
+pub struct ColumnChooser;
+
+impl ColumnChooser {
+    /// `on_changed` runs after every edit; the layout is already updated by then
+    pub fn present(parent: &impl IsA<gtk4::Widget>, layout: Rc<RefCell<ColumnLayout>>, on_changed: Rc<dyn Fn()>) {
+        let dialog = adw::PreferencesDialog::builder()
+            .title("Visible Columns")
+            .search_enabled(false)
+            .build();
+        let page = adw::PreferencesPage::new();
+        dialog.add(&page);
+
+        let group = adw::PreferencesGroup::builder()
+            .description("Column headers can also be dragged to reorder and resized in place")
+            .build();
+        page.add(&group);
+
+        let reset_button = gtk4::Button::builder()
+            .label("Reset")
+            .valign(gtk4::Align::Center)
+            .build();
+        group.set_header_suffix(Some(&reset_button));
+
+        let rows: Rc<RefCell<Vec<adw::SwitchRow>>> = Rc::new(RefCell::new(Vec::new()));
+        Self::fill(&group, &rows, &layout, &on_changed);
+
+        {
+            let group = group.clone();
+            reset_button.connect_clicked(move |_| {
+                *layout.borrow_mut() = ColumnLayout::default();
+                on_changed();
+                Self::fill(&group, &rows, &layout, &on_changed);
+            });
+        }
+
+        dialog.present(Some(parent));
+    }
+
+    /// (Re)create one row per column in the layout's order
+    fn fill(
+        group: &adw::PreferencesGroup,
+        rows: &Rc<RefCell<Vec<adw::SwitchRow>>>,
+        layout: &Rc<RefCell<ColumnLayout>>,
+        on_changed: &Rc<dyn Fn()>,
+    ) {
+        for row in rows.borrow_mut().drain(..) {
+            group.remove(&row);
+        }
+
+        let settings = layout.borrow().columns.clone();
+        let last = settings.len().saturating_sub(1);
+        for (index, setting) in settings.iter().enumerate() {
+            let column = setting.column;
+            let row = adw::SwitchRow::builder()
+                .title(column.title())
+                .active(setting.visible)
+                // The name column identifies the row, it can't be hidden
+                .sensitive(column != ListColumn::Name)
+                .build();
+
+            {
+                let layout = layout.clone();
+                let on_changed = on_changed.clone();
+                row.connect_active_notify(move |row| {
+                    if let Some(setting) = layout.borrow_mut().setting_mut(column) {
+                        setting.visible = row.is_active();
+                    }
+                    on_changed();
+                });
+            }
+
+            for (icon_name, tooltip, offset, enabled) in [
+                ("go-up-symbolic", "Move Up", -1, index > 0),
+                ("go-down-symbolic", "Move Down", 1, index < last),
+            ] {
+                let button = gtk4::Button::builder()
+                    .icon_name(icon_name)
+                    .tooltip_text(tooltip)
+                    .valign(gtk4::Align::Center)
+                    .sensitive(enabled)
+                    .css_classes(["flat"])
+                    .build();
+                row.add_prefix(&button);
+
+                let group = group.clone();
+                let rows = rows.clone();
+                let layout = layout.clone();
+                let on_changed = on_changed.clone();
+                button.connect_clicked(move |_| {
+                    layout.borrow_mut().move_column(column, offset);
+                    on_changed();
+                    Self::fill(&group, &rows, &layout, &on_changed);
+                });
+            }
+
+            group.add(&row);
+            rows.borrow_mut().push(row);
+        }
+    }
+}
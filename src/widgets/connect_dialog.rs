@@ -0,0 +1,81 @@
+use gtk4::prelude::*;
+use gtk4::{glib, Box as GtkBox, DropDown, Entry, Orientation};
+use libadwaita as adw;
+use adw::prelude::*;
+use std::path::PathBuf;
+
+use crate::core::Remote;
+
+/// Ask for a server address; a full URI may be typed instead of picking a protocol.
+/// `on_error` gets a message when the address can't be used.
+pub struct ConnectServerDialog;
+
+impl ConnectServerDialog {
+    pub fn present<F: Fn(PathBuf) + 'static, E: Fn(String) + 'static>(
+        parent: &impl IsA<gtk4::Widget>,
+        on_connect: F,
+        on_error: E,
+    ) {
+        let dialog = adw::AlertDialog::builder()
+            .heading("Connect to Server")
+            .body("Pick a protocol and enter the server address, or type a full address such as sftp://user@example.com/home")
+            .build();
+
+        let content = GtkBox::new(Orientation::Vertical, 6);
+        let names: Vec<&str> = Remote::SCHEMES.iter().map(|(_, name)| *name).collect();
+        let protocol = DropDown::from_strings(&names);
+        content.append(&protocol);
+
+        let entry = Entry::builder()
+            .placeholder_text("user@example.com/folder")
+            .activates_default(true)
+            .build();
+        entry.add_css_class("nautilus-entry");
+        content.append(&entry);
+        dialog.set_extra_child(Some(&content));
+
+        dialog.add_response("cancel", "Cancel");
+        dialog.add_response("connect", "Connect");
+        dialog.set_response_appearance("connect", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("connect"));
+        dialog.set_close_response("cancel");
+        dialog.set_response_enabled("connect", false);
+
+        {
+            let dialog = dialog.downgrade();
+            entry.connect_changed(move |entry| {
+                if let Some(dialog) = dialog.upgrade() {
+                    dialog.set_response_enabled("connect", !entry.text().trim().is_empty());
+                }
+            });
+        }
+
+        let entry_clone = entry.clone();
+        glib::idle_add_local_once(move || {
+            entry_clone.grab_focus();
+        });
+
+        dialog.connect_response(None, move |_, response| {
+            if response != "connect" {
+                return;
+            }
+            let text = entry.text().trim().to_string();
+            let address = if text.contains("://") {
+                text
+            } else {
+                let (scheme, _) = Remote::SCHEMES[protocol.selected() as usize];
+                format!("{}://{}", scheme, text)
+            };
+
+            match Remote::parse_location(&address).filter(|path| Remote::is_remote(path)) {
+                Some(path) => on_connect(path),
+                None => {
+                    log::warn!("Not a server address: {}", address);
+                    on_error(format!("Not a server address: {}", address));
+                }
+            }
+        });
+
+        dialog.present(Some(parent));
+    }
+}
@@ -0,0 +1,847 @@
+use gtk4::glib::{self, Object};
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+use gtk4::{
+    gio, Box as GtkBox, ColumnView, ColumnViewColumn, CustomSorter, DrawingArea, GestureClick, Image, Label,
+    LevelBar, ListItem, Orientation, PopoverMenu, ScrolledWindow, SignalListItemFactory, SingleSelection,
+    SortListModel, Stack, TreeExpander, TreeListModel, TreeListRow, TreeListRowSorter,
+};
+use libadwaita as adw;
+use adw::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::{Rc, Weak};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::core::{format_size, DirListing, DiskUsageScanner, DiskUsageTree, FileOperations};
+
+/// How often the view catches up with a running scan
+const UPDATE_INTERVAL: Duration = Duration::from_millis(250);
+/// Treemaps only draw this many of the largest items
+const TREEMAP_MAX_ITEMS: usize = 500;
+/// Fill colours of directories in the treemap
+const TREEMAP_COLORS: [(f64, f64, f64); 6] = [
+    (0.21, 0.52, 0.89),
+    (0.20, 0.65, 0.45),
+    (0.90, 0.60, 0.18),
+    (0.75, 0.33, 0.55),
+    (0.45, 0.40, 0.80),
+    (0.15, 0.60, 0.65),
+];
+
+mod imp {
+    use gtk4::glib;
+    use gtk4::glib::Object;
+    use gtk4::subclass::prelude::*;
+    use std::cell::Cell;
+
+    /// A node of the disk usage tree, as shown in the list
+    #[derive(Default)]
+    pub struct UsageObject {
+        pub node: Cell<usize>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for UsageObject {
+        const NAME: &'static str = "BlinkUsageObject";
+        type Type = super::UsageObject;
+        type ParentType = Object;
+    }
+
+    impl ObjectImpl for UsageObject {}
+}
+
+glib::wrapper! {
+    pub struct UsageObject(ObjectSubclass<imp::UsageObject>);
+}
+
+impl UsageObject {
+    fn new(node: usize) -> Self {
+        let obj: Self = Object::builder().build();
+        obj.imp().node.set(node);
+        obj
+    }
+
+    fn node(&self) -> usize {
+        self.imp().node.get()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum UsageColumn {
+    Name,
+    Size,
+    Share,
+    Files,
+}
+
+/// Scan results and the widgets showing them
+struct Analyzer {
+    tree: RefCell<DiskUsageTree>,
+    /// Directory the list and treemap start from
+    view_root: Cell<usize>,
+    /// Child lists handed to the tree model, with how many children each holds so far
+    stores: RefCell<HashMap<usize, (gio::ListStore, usize)>>,
+    /// Cells on screen, refreshed while sizes grow
+    bound: RefCell<Vec<(ListItem, UsageColumn)>>,
+    /// Treemap rectangles from the last draw: node and x, y, width, height
+    treemap_rects: RefCell<Vec<(usize, [f64; 4])>>,
+    scanning: Cell<bool>,
+    cancel: Arc<AtomicBool>,
+    root_store: gio::ListStore,
+    /// The list's sorter, poked while sizes change
+    sorter: Option<gtk4::Sorter>,
+    selection: SingleSelection,
+    title: adw::WindowTitle,
+    up_button: gtk4::Button,
+    treemap: DrawingArea,
+    status_label: Label,
+    spinner: adw::Spinner,
+    stop_button: gtk4::Button,
+    /// Failures show up as toasts over the window's content
+    toast_overlay: adw::ToastOverlay,
+    on_deleted: Box<dyn Fn()>,
+}
+
+/// ncdu-style breakdown of what takes up space below a directory
+pub struct DiskUsageWindow;
+
+impl DiskUsageWindow {
+    /// Scan `root` and show the results as they come in. `on_deleted` runs after items are
+    /// moved to the trash from the window.
+    pub fn present<F: Fn() + 'static>(parent: &impl IsA<gtk4::Window>, root: PathBuf, on_deleted: F) {
+        let window = adw::Window::builder()
+            .title("Disk Usage")
+            .default_width(860)
+            .default_height(600)
+            .transient_for(parent)
+            .build();
+
+        let title = adw::WindowTitle::new("Disk Usage", &root.to_string_lossy());
+        let header = adw::HeaderBar::builder().title_widget(&title).build();
+
+        let up_button = gtk4::Button::builder()
+            .icon_name("go-up-symbolic")
+            .tooltip_text("Parent Folder")
+            .sensitive(false)
+            .build();
+        header.pack_start(&up_button);
+
+        let treemap_button = gtk4::ToggleButton::builder()
+            .icon_name("view-grid-symbolic")
+            .tooltip_text("Treemap")
+            .build();
+        let trash_button = gtk4::Button::builder()
+            .icon_name("user-trash-symbolic")
+            .tooltip_text("Move to Trash")
+            .action_name("usage.trash")
+            .build();
+        let stop_button = gtk4::Button::builder()
+            .icon_name("process-stop-symbolic")
+            .tooltip_text("Stop Scanning")
+            .build();
+        let spinner = adw::Spinner::new();
+        header.pack_end(&treemap_button);
+        header.pack_end(&trash_button);
+        header.pack_end(&stop_button);
+        header.pack_end(&spinner);
+
+        let column_view = ColumnView::builder()
+            .show_row_separators(false)
+            .css_classes(["data-table"])
+            .build();
+        let scrolled = ScrolledWindow::builder()
+            .vexpand(true)
+            .hexpand(true)
+            .child(&column_view)
+            .build();
+
+        let treemap = DrawingArea::builder()
+            .vexpand(true)
+            .hexpand(true)
+            .has_tooltip(true)
+            .build();
+
+        let stack = Stack::new();
+        stack.set_transition_type(gtk4::StackTransitionType::Crossfade);
+        stack.add_named(&scrolled, Some("tree"));
+        stack.add_named(&treemap, Some("treemap"));
+
+        let status_label = Label::builder()
+            .halign(gtk4::Align::Start)
+            .label("Scanning…")
+            .css_classes(["dim-label", "caption"])
+            .build();
+        let status_bar = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .margin_start(12)
+            .margin_end(12)
+            .margin_top(4)
+            .margin_bottom(4)
+            .css_classes(["status-bar"])
+            .build();
+        status_bar.append(&status_label);
+
+        let toolbar_view = adw::ToolbarView::new();
+        toolbar_view.add_top_bar(&header);
+        toolbar_view.set_content(Some(&stack));
+        toolbar_view.add_bottom_bar(&status_bar);
+        let toast_overlay = adw::ToastOverlay::new();
+        toast_overlay.set_child(Some(&toolbar_view));
+        window.set_content(Some(&toast_overlay));
+
+        let root_store = gio::ListStore::new::<UsageObject>();
+        let analyzer = Rc::new_cyclic(|weak: &Weak<Analyzer>| {
+            let tree_model = TreeListModel::new(root_store.clone(), false, false, {
+                let weak = weak.clone();
+                move |item| weak.upgrade()?.child_model(item)
+            });
+
+            let name_column = Self::column(weak, "Name", UsageColumn::Name);
+            let size_column = Self::column(weak, "Size", UsageColumn::Size);
+            let share_column = Self::column(weak, "Share", UsageColumn::Share);
+            let files_column = Self::column(weak, "Files", UsageColumn::Files);
+            name_column.set_expand(true);
+            share_column.set_fixed_width(180);
+            for column in [&name_column, &size_column, &share_column, &files_column] {
+                column_view.append_column(column);
+            }
+            column_view.sort_by_column(Some(&size_column), gtk4::SortType::Descending);
+
+            let row_sorter = TreeListRowSorter::new(column_view.sorter());
+            let sort_model = SortListModel::new(Some(tree_model), Some(row_sorter));
+            let selection = SingleSelection::new(Some(sort_model));
+            column_view.set_model(Some(&selection));
+
+            Analyzer {
+                tree: RefCell::new(DiskUsageTree::new(&root)),
+                view_root: Cell::new(0),
+                stores: RefCell::new(HashMap::from([(0, (root_store.clone(), 0))])),
+                bound: RefCell::new(Vec::new()),
+                treemap_rects: RefCell::new(Vec::new()),
+                scanning: Cell::new(true),
+                cancel: Arc::new(AtomicBool::new(false)),
+                root_store: root_store.clone(),
+                sorter: column_view.sorter(),
+                selection,
+                title: title.clone(),
+                up_button: up_button.clone(),
+                treemap: treemap.clone(),
+                status_label: status_label.clone(),
+                spinner: spinner.clone(),
+                stop_button: stop_button.clone(),
+                toast_overlay: toast_overlay.clone(),
+                on_deleted: Box::new(on_deleted),
+            }
+        });
+
+        // Window-wide actions on the selected item, also offered in the context menu
+        let action_group = gio::SimpleActionGroup::new();
+        let trash_action = gio::SimpleAction::new("trash", None);
+        {
+            let open_action = gio::SimpleAction::new("open", None);
+            let weak = Rc::downgrade(&analyzer);
+            open_action.connect_activate(move |_, _| {
+                let Some(analyzer) = weak.upgrade() else { return };
+                if let Some(node) = analyzer.selected_node() {
+                    analyzer.drill(node);
+                }
+            });
+            action_group.add_action(&open_action);
+
+            let weak = Rc::downgrade(&analyzer);
+            let window_weak = window.downgrade();
+            trash_action.connect_activate(move |_, _| {
+                let (Some(analyzer), Some(window)) = (weak.upgrade(), window_weak.upgrade()) else { return };
+                analyzer.trash_selected(&window);
+            });
+            action_group.add_action(&trash_action);
+        }
+        window.insert_action_group("usage", Some(&action_group));
+
+        // Only an actual item can be trashed, never the scanned folder itself
+        trash_action.set_enabled(analyzer.selected_node().is_some());
+        {
+            let weak = Rc::downgrade(&analyzer);
+            analyzer.selection.connect_selected_item_notify(move |_| {
+                let Some(analyzer) = weak.upgrade() else { return };
+                trash_action.set_enabled(analyzer.selected_node().is_some());
+            });
+        }
+
+        // Double-click or Enter opens a directory
+        {
+            let weak = Rc::downgrade(&analyzer);
+            column_view.connect_activate(move |_, position| {
+                let Some(analyzer) = weak.upgrade() else { return };
+                analyzer.selection.set_selected(position);
+                if let Some(node) = analyzer.selected_node() {
+                    analyzer.drill(node);
+                }
+            });
+        }
+
+        // Delete moves the selected item to the trash
+        {
+            let key_controller = gtk4::EventControllerKey::new();
+            key_controller.connect_key_pressed(move |controller, key, _, _| {
+                if key != gtk4::gdk::Key::Delete {
+                    return glib::Propagation::Proceed;
+                }
+                if let Some(widget) = controller.widget() {
+                    let _ = widget.activate_action("usage.trash", None);
+                }
+                glib::Propagation::Stop
+            });
+            column_view.add_controller(key_controller);
+        }
+
+        {
+            let weak = Rc::downgrade(&analyzer);
+            up_button.connect_clicked(move |_| {
+                let Some(analyzer) = weak.upgrade() else { return };
+                let parent = analyzer.tree.borrow().node(analyzer.view_root.get()).parent;
+                if let Some(parent) = parent {
+                    analyzer.drill(parent);
+                }
+            });
+        }
+
+        // Keep what was found so far
+        {
+            let cancel = analyzer.cancel.clone();
+            stop_button.connect_clicked(move |button| {
+                cancel.store(true, Ordering::Relaxed);
+                button.set_sensitive(false);
+            });
+        }
+
+        {
+            let stack = stack.clone();
+            treemap_button.connect_toggled(move |button| {
+                stack.set_visible_child_name(if button.is_active() { "treemap" } else { "tree" });
+            });
+        }
+
+        Self::setup_treemap(&analyzer);
+
+        // Scan in the background and pour the listings into the tree as they arrive
+        let (tx, rx) = async_channel::unbounded::<DirListing>();
+        {
+            let cancel = analyzer.cancel.clone();
+            thread::spawn(move || {
+                DiskUsageScanner::walk(&root, true, &cancel, |listing| {
+                    let _ = tx.send_blocking(listing);
+                });
+            });
+        }
+        {
+            let analyzer = analyzer.clone();
+            glib::spawn_future_local(async move {
+                while let Ok(listing) = rx.recv().await {
+                    analyzer.tree.borrow_mut().insert(listing);
+                }
+                analyzer.scanning.set(false);
+                analyzer.update();
+            });
+        }
+        {
+            let weak = Rc::downgrade(&analyzer);
+            glib::timeout_add_local(UPDATE_INTERVAL, move || {
+                let Some(analyzer) = weak.upgrade() else {
+                    return glib::ControlFlow::Break;
+                };
+                if !analyzer.scanning.get() {
+                    return glib::ControlFlow::Break;
+                }
+                analyzer.update();
+                glib::ControlFlow::Continue
+            });
+        }
+
+        // Closing the window stops the scan; the analyzer goes once the scan has wound down
+        {
+            let cancel = analyzer.cancel.clone();
+            window.connect_destroy(move |_| cancel.store(true, Ordering::Relaxed));
+        }
+        unsafe {
+            window.set_data("analyzer", analyzer);
+        }
+
+        window.present();
+    }
+
+    fn column(analyzer: &Weak<Analyzer>, title: &str, column: UsageColumn) -> ColumnViewColumn {
+        let factory = SignalListItemFactory::new();
+
+        factory.connect_setup(move |_, item| {
+            let Some(list_item) = item.downcast_ref::<ListItem>() else { return };
+            let child: gtk4::Widget = match column {
+                UsageColumn::Name => {
+                    let content = GtkBox::new(Orientation::Horizontal, 8);
+                    content.append(&Image::new());
+                    content.append(&Label::builder().halign(gtk4::Align::Start).ellipsize(gtk4::pango::EllipsizeMode::Middle).build());
+                    let expander = TreeExpander::new();
+                    expander.set_child(Some(&content));
+                    expander.upcast()
+                }
+                UsageColumn::Share => {
+                    let content = GtkBox::new(Orientation::Horizontal, 8);
+                    let bar = LevelBar::builder()
+                        .min_value(0.0)
+                        .max_value(1.0)
+                        .hexpand(true)
+                        .valign(gtk4::Align::Center)
+                        .css_classes(["usage-share"])
+                        .build();
+                    for offset in [gtk4::LEVEL_BAR_OFFSET_LOW, gtk4::LEVEL_BAR_OFFSET_HIGH, gtk4::LEVEL_BAR_OFFSET_FULL] {
+                        bar.remove_offset_value(Some(offset));
+                    }
+                    content.append(&bar);
+                    content.append(&Label::builder().width_chars(6).xalign(1.0).build());
+                    content.upcast()
+                }
+                UsageColumn::Size | UsageColumn::Files => {
+                    Label::builder().xalign(1.0).css_classes(["numeric"]).build().upcast()
+                }
+            };
+            Self::add_context_menu(&child, list_item);
+            list_item.set_child(Some(&child));
+        });
+
+        {
+            let analyzer = analyzer.clone();
+            factory.connect_bind(move |_, item| {
+                let (Some(analyzer), Some(list_item)) = (analyzer.upgrade(), item.downcast_ref::<ListItem>()) else { return };
+                analyzer.bound.borrow_mut().push((list_item.clone(), column));
+                analyzer.update_cell(list_item, column);
+            });
+        }
+        {
+            let analyzer = analyzer.clone();
+            factory.connect_unbind(move |_, item| {
+                let (Some(analyzer), Some(list_item)) = (analyzer.upgrade(), item.downcast_ref::<ListItem>()) else { return };
+                analyzer.bound.borrow_mut().retain(|(bound, c)| !(bound == list_item && *c == column));
+            });
+        }
+
+        let sorter = {
+            let analyzer = analyzer.clone();
+            CustomSorter::new(move |a, b| {
+                let Some(analyzer) = analyzer.upgrade() else { return gtk4::Ordering::Equal };
+                let (Some(a), Some(b)) = (a.downcast_ref::<UsageObject>(), b.downcast_ref::<UsageObject>()) else {
+                    return gtk4::Ordering::Equal;
+                };
+                let tree = analyzer.tree.borrow();
+                let (a, b) = (tree.node(a.node()), tree.node(b.node()));
+                match column {
+                    UsageColumn::Name => a.name.to_string_lossy().to_lowercase().cmp(&b.name.to_string_lossy().to_lowercase()),
+                    UsageColumn::Size | UsageColumn::Share => a.size.cmp(&b.size),
+                    UsageColumn::Files => a.files.cmp(&b.files),
+                }
+                .into()
+            })
+        };
+
+        ColumnViewColumn::builder()
+            .title(title)
+            .factory(&factory)
+            .sorter(&sorter)
+            .resizable(true)
+            .build()
+    }
+
+    /// Right-click selects the row and offers to open or trash it
+    fn add_context_menu(widget: &gtk4::Widget, list_item: &ListItem) {
+        let gesture = GestureClick::builder().button(3).build();
+        let list_item = list_item.downgrade();
+        gesture.connect_pressed(move |gesture, _, x, y| {
+            let (Some(widget), Some(list_item)) = (gesture.widget(), list_item.upgrade()) else { return };
+            let Some(column_view) = widget.ancestor(ColumnView::static_type()).and_downcast::<ColumnView>() else { return };
+            if let Some(selection) = column_view.model().and_downcast::<SingleSelection>() {
+                selection.set_selected(list_item.position());
+            }
+
+            let menu = gio::Menu::new();
+            menu.append(Some("Open"), Some("usage.open"));
+            menu.append(Some("Move to Trash"), Some("usage.trash"));
+            let popover = PopoverMenu::from_model(Some(&menu));
+            popover.set_parent(&widget);
+            popover.set_has_arrow(false);
+            popover.set_pointing_to(Some(&gtk4::gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
+            popover.connect_closed(|popover| {
+                let popover = popover.clone();
+                glib::idle_add_local_once(move || popover.unparent());
+            });
+            popover.popup();
+        });
+        widget.add_controller(gesture);
+    }
+
+    fn setup_treemap(analyzer: &Rc<Analyzer>) {
+        let weak = Rc::downgrade(analyzer);
+        analyzer.treemap.set_draw_func(move |_, cr, width, height| {
+            let Some(analyzer) = weak.upgrade() else { return };
+            analyzer.draw_treemap(cr, width as f64, height as f64);
+        });
+
+        // Clicking a directory opens it
+        let click = GestureClick::new();
+        let weak = Rc::downgrade(analyzer);
+        click.connect_released(move |_, _, x, y| {
+            let Some(analyzer) = weak.upgrade() else { return };
+            let Some(node) = analyzer.treemap_node_at(x, y) else { return };
+            if analyzer.tree.borrow().node(node).is_dir {
+                analyzer.drill(node);
+            }
+        });
+        analyzer.treemap.add_controller(click);
+
+        let weak = Rc::downgrade(analyzer);
+        analyzer.treemap.connect_query_tooltip(move |_, x, y, _, tooltip| {
+            let Some(analyzer) = weak.upgrade() else { return false };
+            let Some(node) = analyzer.treemap_node_at(x as f64, y as f64) else { return false };
+            let tree = analyzer.tree.borrow();
+            let size = tree.node(node).size;
+            tooltip.set_text(Some(&format!("{}\n{}", tree.path(node).display(), format_size(size))));
+            true
+        });
+    }
+}
+
+impl Analyzer {
+    /// Children of a directory row for the tree model; files don't expand
+    fn child_model(&self, item: &Object) -> Option<gio::ListModel> {
+        let node = item.downcast_ref::<UsageObject>()?.node();
+        {
+            let tree = self.tree.borrow();
+            let usage_node = tree.node(node);
+            if !usage_node.is_dir || (usage_node.scanned && usage_node.children.is_empty()) {
+                return None;
+            }
+        }
+        let store = gio::ListStore::new::<UsageObject>();
+        self.stores.borrow_mut().insert(node, (store.clone(), 0));
+        self.sync_stores();
+        Some(store.upcast())
+    }
+
+    /// Add children that arrived since the lists were handed out
+    fn sync_stores(&self) {
+        let additions: Vec<(gio::ListStore, Vec<UsageObject>)> = {
+            let tree = self.tree.borrow();
+            let mut stores = self.stores.borrow_mut();
+            stores
+                .iter_mut()
+                .filter_map(|(node, (store, count))| {
+                    let children = &tree.node(*node).children;
+                    if children.len() <= *count {
+                        return None;
+                    }
+                    let new: Vec<UsageObject> = children[*count..].iter().map(|&child| UsageObject::new(child)).collect();
+                    *count = children.len();
+                    Some((store.clone(), new))
+                })
+                .collect()
+        };
+        for (store, objects) in additions {
+            store.extend_from_slice(&objects);
+        }
+    }
+
+    fn update(&self) {
+        self.sync_stores();
+        let bound = self.bound.borrow().clone();
+        for (list_item, column) in &bound {
+            self.update_cell(list_item, *column);
+        }
+        if let Some(sorter) = self.sorter.as_ref().filter(|_| self.scanning.get()) {
+            sorter.changed(gtk4::SorterChange::Different);
+        }
+        self.treemap.queue_draw();
+
+        let tree = self.tree.borrow();
+        let root = tree.node(tree.root());
+        if self.scanning.get() {
+            self.status_label.set_text(&format!(
+                "Scanning… {} folders, {}",
+                tree.scanned_dirs(),
+                format_size(root.size)
+            ));
+        } else {
+            self.spinner.set_visible(false);
+            self.stop_button.set_visible(false);
+            let status = if self.cancel.load(Ordering::Relaxed) { "Stopped" } else { "Done" };
+            self.status_label.set_text(&format!(
+                "{}: {} in {} files and {} folders",
+                status,
+                format_size(root.size),
+                root.files,
+                tree.scanned_dirs()
+            ));
+        }
+    }
+
+    fn update_cell(&self, list_item: &ListItem, column: UsageColumn) {
+        let Some(row) = list_item.item().and_downcast::<TreeListRow>() else { return };
+        let Some(obj) = row.item().and_downcast::<UsageObject>() else { return };
+        let Some(child) = list_item.child() else { return };
+        let tree = self.tree.borrow();
+        let node = tree.node(obj.node());
+
+        match column {
+            UsageColumn::Name => {
+                let Some(expander) = child.downcast_ref::<TreeExpander>() else { return };
+                expander.set_list_row(Some(&row));
+                let Some(content) = expander.child() else { return };
+                if let Some(icon) = content.first_child().and_downcast::<Image>() {
+                    let icon_name = match (&node.error, node.is_dir) {
+                        (Some(_), _) => "dialog-warning-symbolic",
+                        (None, true) => "folder-symbolic",
+                        (None, false) => "text-x-generic-symbolic",
+                    };
+                    icon.set_icon_name(Some(icon_name));
+                }
+                if let Some(label) = content.last_child().and_downcast::<Label>() {
+                    label.set_text(&node.name.to_string_lossy());
+                }
+                content.set_tooltip_text(node.error.as_ref().map(|e| format!("Could not read: {}", e)).as_deref());
+            }
+            UsageColumn::Size => {
+                if let Some(label) = child.downcast_ref::<Label>() {
+                    label.set_text(&format_size(node.size));
+                }
+            }
+            UsageColumn::Share => {
+                let parent_size = node.parent.map(|parent| tree.node(parent).size).unwrap_or(node.size);
+                let share = if parent_size > 0 { node.size as f64 / parent_size as f64 } else { 0.0 };
+                if let Some(bar) = child.first_child().and_downcast::<LevelBar>() {
+                    bar.set_value(share.min(1.0));
+                }
+                if let Some(label) = child.last_child().and_downcast::<Label>() {
+                    label.set_text(&format!("{:.1} %", share * 100.0));
+                }
+            }
+            UsageColumn::Files => {
+                if let Some(label) = child.downcast_ref::<Label>() {
+                    label.set_text(&if node.is_dir { node.files.to_string() } else { String::new() });
+                }
+            }
+        }
+    }
+
+    fn selected_node(&self) -> Option<usize> {
+        let row = self.selection.selected_item().and_downcast::<TreeListRow>()?;
+        Some(row.item().and_downcast::<UsageObject>()?.node())
+    }
+
+    /// Show a directory's contents at the top level
+    fn drill(&self, node: usize) {
+        {
+            let tree = self.tree.borrow();
+            let usage_node = tree.node(node);
+            if !usage_node.is_dir {
+                return;
+            }
+            self.title.set_subtitle(&tree.path(node).to_string_lossy());
+            self.up_button.set_sensitive(usage_node.parent.is_some());
+        }
+
+        self.view_root.set(node);
+        self.root_store.remove_all();
+        {
+            let mut stores = self.stores.borrow_mut();
+            stores.clear();
+            stores.insert(node, (self.root_store.clone(), 0));
+        }
+        self.sync_stores();
+        self.treemap.queue_draw();
+    }
+
+    fn trash_selected(self: &Rc<Self>, window: &adw::Window) {
+        let Some(node) = self.selected_node() else { return };
+        let (path, name, size) = {
+            let tree = self.tree.borrow();
+            let usage_node = tree.node(node);
+            (tree.path(node), usage_node.name.to_string_lossy().to_string(), usage_node.size)
+        };
+
+        let dialog = adw::AlertDialog::builder()
+            .heading("Move to Trash")
+            .body(format!("Move \"{}\" to trash? It takes up {}.", name, format_size(size)))
+            .build();
+        dialog.add_response("cancel", "Cancel");
+        dialog.add_response("trash", "Move to Trash");
+        dialog.set_response_appearance("trash", adw::ResponseAppearance::Destructive);
+        dialog.set_close_response("cancel");
+
+        let analyzer = Rc::downgrade(self);
+        dialog.connect_response(None, move |_, response| {
+            if response != "trash" {
+                return;
+            }
+            // Trashing a big folder can take a while on some filesystems
+            let (tx, rx) = async_channel::bounded(1);
+            let path = path.clone();
+            thread::spawn(move || {
+                let _ = tx.send_blocking(FileOperations::delete(&path).map_err(|e| (path, e)));
+            });
+
+            let analyzer = analyzer.clone();
+            let name = name.clone();
+            glib::spawn_future_local(async move {
+                let Ok(result) = rx.recv().await else { return };
+                let Some(analyzer) = analyzer.upgrade() else { return };
+                if let Err((path, e)) = result {
+                    log::warn!("Failed to move {:?} to trash: {}", path, e);
+                    let toast = adw::Toast::builder()
+                        .title(format!("Unable to move \"{}\" to trash: {}", name, e))
+                        .use_markup(false)
+                        .timeout(5)
+                        .build();
+                    analyzer.toast_overlay.add_toast(toast);
+                    return;
+                }
+                analyzer.remove_node(node);
+                (analyzer.on_deleted)();
+            });
+        });
+
+        dialog.present(Some(window));
+    }
+
+    fn remove_node(&self, node: usize) {
+        let parent = self.tree.borrow().node(node).parent;
+        self.tree.borrow_mut().remove(node);
+
+        let store = parent.and_then(|parent| {
+            let mut stores = self.stores.borrow_mut();
+            stores.remove(&node);
+            let (store, count) = stores.get_mut(&parent)?;
+            *count -= 1;
+            Some(store.clone())
+        });
+        if let Some(store) = store {
+            let position = (0..store.n_items())
+                .find(|&i| store.item(i).and_downcast::<UsageObject>().is_some_and(|obj| obj.node() == node));
+            if let Some(position) = position {
+                store.remove(position);
+            }
+        }
+        self.update();
+    }
+
+    fn treemap_node_at(&self, x: f64, y: f64) -> Option<usize> {
+        self.treemap_rects
+            .borrow()
+            .iter()
+            .find(|(_, [rx, ry, rw, rh])| x >= *rx && x < rx + rw && y >= *ry && y < ry + rh)
+            .map(|(node, _)| *node)
+    }
+
+    fn draw_treemap(&self, cr: &gtk4::cairo::Context, width: f64, height: f64) {
+        let tree = self.tree.borrow();
+        let mut items: Vec<(usize, f64)> = tree
+            .node(self.view_root.get())
+            .children
+            .iter()
+            .map(|&child| (child, tree.node(child).size as f64))
+            .filter(|(_, size)| *size > 0.0)
+            .collect();
+        items.sort_by(|a, b| b.1.total_cmp(&a.1));
+        items.truncate(TREEMAP_MAX_ITEMS);
+
+        let rects = squarify(&items, [0.0, 0.0, width, height]);
+        for (i, (node, [x, y, w, h])) in rects.iter().enumerate() {
+            let usage_node = tree.node(*node);
+            let (r, g, b) = if usage_node.is_dir {
+                TREEMAP_COLORS[i % TREEMAP_COLORS.len()]
+            } else {
+                (0.45, 0.45, 0.45)
+            };
+            cr.set_source_rgb(r, g, b);
+            cr.rectangle(x + 1.0, y + 1.0, (w - 2.0).max(0.0), (h - 2.0).max(0.0));
+            let _ = cr.fill();
+
+            if *w < 60.0 || *h < 18.0 {
+                continue;
+            }
+            cr.save().ok();
+            cr.rectangle(*x, *y, *w, *h);
+            cr.clip();
+            cr.set_source_rgb(1.0, 1.0, 1.0);
+            cr.set_font_size(12.0);
+            cr.move_to(x + 6.0, y + 15.0);
+            let _ = cr.show_text(&usage_node.name.to_string_lossy());
+            if *h >= 34.0 {
+                cr.set_font_size(11.0);
+                cr.move_to(x + 6.0, y + 30.0);
+                let _ = cr.show_text(&format_size(usage_node.size));
+            }
+            cr.restore().ok();
+        }
+        *self.treemap_rects.borrow_mut() = rects;
+    }
+}
+
+/// Squarified treemap layout: split `rect` into rectangles with areas proportional to the
+/// sizes, kept as close to square as possible. Items should be sorted largest first.
+fn squarify(items: &[(usize, f64)], rect: [f64; 4]) -> Vec<(usize, [f64; 4])> {
+    let [mut x, mut y, mut w, mut h] = rect;
+    let total: f64 = items.iter().map(|(_, size)| size).sum();
+    if total <= 0.0 || w <= 0.0 || h <= 0.0 {
+        return Vec::new();
+    }
+    let scale = w * h / total;
+    let areas: Vec<(usize, f64)> = items.iter().map(|&(node, size)| (node, size * scale)).collect();
+
+    let mut rects = Vec::with_capacity(areas.len());
+    let mut start = 0;
+    while start < areas.len() && w > 0.0 && h > 0.0 {
+        // Grow the row along the short side while that makes its rectangles squarer
+        let side = w.min(h);
+        let mut end = start + 1;
+        while end < areas.len() && worst_ratio(&areas[start..=end], side) <= worst_ratio(&areas[start..end], side) {
+            end += 1;
+        }
+
+        let row = &areas[start..end];
+        let row_area: f64 = row.iter().map(|(_, area)| area).sum();
+        if w >= h {
+            let row_width = row_area / h;
+            let mut row_y = y;
+            for &(node, area) in row {
+                let item_height = area / row_width;
+                rects.push((node, [x, row_y, row_width, item_height]));
+                row_y += item_height;
+            }
+            x += row_width;
+            w -= row_width;
+        } else {
+            let row_height = row_area / w;
+            let mut row_x = x;
+            for &(node, area) in row {
+                let item_width = area / row_height;
+                rects.push((node, [row_x, y, item_width, row_height]));
+                row_x += item_width;
+            }
+            y += row_height;
+            h -= row_height;
+        }
+        start = end;
+    }
+    rects
+}
+
+/// Largest aspect ratio in a treemap row laid along a side of the given length
+fn worst_ratio(row: &[(usize, f64)], side: f64) -> f64 {
+    let sum: f64 = row.iter().map(|(_, area)| area).sum();
+    let max = row.iter().map(|(_, area)| *area).fold(0.0, f64::max);
+    let min = row.iter().map(|(_, area)| *area).fold(f64::INFINITY, f64::min);
+    let (side2, sum2) = (side * side, sum * sum);
+    (side2 * max / sum2).max(sum2 / (side2 * min))
+}
@@ -0,0 +1,70 @@
+use gtk4::prelude::*;
+use gtk4::{glib, ListBox, ScrolledWindow};
+use libadwaita as adw;
+use adw::prelude::*;
+use std::path::PathBuf;
+
+use crate::core::Remote;
+
+/// End-of-job summary listing every item that failed and why, with the option to try them again
+pub struct ErrorReport;
+
+impl ErrorReport {
+    pub fn present<F: Fn(Vec<PathBuf>) + 'static>(
+        parent: &impl IsA<gtk4::Widget>,
+        heading: &str,
+        failures: Vec<(PathBuf, String)>,
+        on_retry: F,
+    ) {
+        let body = if failures.len() == 1 {
+            String::from("1 item failed.")
+        } else {
+            format!("{} items failed.", failures.len())
+        };
+        let dialog = adw::AlertDialog::builder()
+            .heading(heading)
+            .body(&body)
+            .build();
+
+        let list = ListBox::builder()
+            .selection_mode(gtk4::SelectionMode::None)
+            .css_classes(["boxed-list"])
+            .build();
+        for (path, error) in &failures {
+            let name = if Remote::is_remote(path) {
+                Remote::display_name(path)
+            } else {
+                path.file_name().unwrap_or(path.as_os_str()).to_string_lossy().to_string()
+            };
+            let row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&name).as_str())
+                .subtitle(glib::markup_escape_text(error).as_str())
+                .tooltip_text(path.to_string_lossy())
+                .build();
+            list.append(&row);
+        }
+        let scrolled = ScrolledWindow::builder()
+            .child(&list)
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .propagate_natural_height(true)
+            .max_content_height(300)
+            .build();
+        dialog.set_extra_child(Some(&scrolled));
+
+        dialog.add_response("close", "Close");
+        dialog.add_response("retry", "Retry");
+        dialog.set_response_appearance("retry", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("retry"));
+        dialog.set_close_response("close");
+
+        let paths: Vec<PathBuf> = failures.into_iter().map(|(path, _)| path).collect();
+        dialog.connect_response(None, move |_, response| {
+            if response == "retry" {
+                on_retry(paths.clone());
+            }
+        });
+
+        dialog.present(Some(parent));
+    }
+}
+
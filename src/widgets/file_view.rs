@@ -3,54 +3,77 @@ use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
 use gtk4::{
     gio, CustomFilter, DragSource, DropTarget, EventControllerKey, FilterListModel, GestureClick, GridView, Label, 
-    ListItem, ListView, MultiSelection, PopoverMenu, SignalListItemFactory, Stack,
+    ColumnView, ColumnViewColumn, ListItem, MultiSelection, PopoverMenu, SignalListItemFactory, Stack,
 };
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::fs::OpenOptions;
-use std::io::Write;
+use std::sync::Arc;
+use std::time::SystemTime;
 use async_channel;
+use libadwaita as adw;
+
+use super::app_chooser::AppChooser;
+use super::column_chooser::ColumnChooser;
+use super::error_report::ErrorReport;
+use crate::core::{
+    Applications, Archive, CachedListing, ColumnLayout, DirectoryCache, DriveScanner, FileEntry, ItemCount, ListColumn, Listing, Mime, RecentFiles, Remote,
+    Scanner, SortKey, ThumbnailPool, ThumbnailSize, Vfs, VfsBackend, VfsWatch, ViewMode, ViewSettings,
+    ViewSettingsStore,
+};
 
-use crate::core::{FileEntry, FileOperations, Scanner};
-
-// #region agent log
-fn debug_log(hypothesis_id: &str, location: &str, message: &str, data: serde_json::Value) {
-    let log_entry = serde_json::json!({
-        "sessionId": "debug-session",
-        "runId": "run1",
-        "hypothesisId": hypothesis_id,
-        "location": location,
-        "message": message,
-        "data": data,
-        "timestamp": std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis()
-    });
-    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open("/home/artwik/.config/alloy/.cursor/debug.log") {
-        let _ = writeln!(file, "{}", log_entry);
-    }
+/// Quiet period after a change in the watched folder before it is looked at
+const WATCH_SETTLE_MS: u64 = 200;
+/// Items added to the view at a time while a folder loads; the UI gets to draw in between
+const LOAD_BATCH: usize = 2000;
+/// A folder that loads faster than this never shows the loading spinner
+const LOADING_SPINNER_DELAY_MS: u64 = 300;
+/// Beyond this many changed items, a revalidated folder is replaced instead of patched
+const MAX_INCREMENTAL_CHANGES: usize = 1000;
+
+/// A handler set through one of the `connect_*` methods
+type Callback<T> = Rc<RefCell<Option<Box<dyn Fn(T)>>>>;
+type SimpleCallback = Rc<RefCell<Option<Box<dyn Fn()>>>>;
+
+/// Part of a folder listing on its way to the view
+enum ListingEvent {
+    /// Items, sorted among themselves
+    Batch(Vec<FileEntry>),
+    /// The listing is complete, with how many hidden items were left out
+    Done(std::io::Result<usize>),
 }
-// #endregion
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum ViewMode {
-    Grid,
-    List,
+/// Callbacks shared by the grid and list context menus
+#[derive(Clone)]
+struct ContextMenuCallbacks {
+    on_directory_activated: Callback<PathBuf>,
+    on_copy: Callback<Vec<PathBuf>>,
+    on_cut: Callback<Vec<PathBuf>>,
+    on_paste: SimpleCallback,
+    on_delete: Callback<Vec<PathBuf>>,
+    on_rename: Callback<PathBuf>,
+    on_extract_here: Callback<Vec<PathBuf>>,
+    on_extract_to: Callback<Vec<PathBuf>>,
+    on_compress: Callback<Vec<PathBuf>>,
+    on_properties: Callback<Vec<PathBuf>>,
+    on_error: Callback<String>,
+    pending_selection: Rc<RefCell<Vec<PathBuf>>>,
+    backend: Rc<RefCell<Arc<dyn VfsBackend>>>,
 }
 
 mod imp {
     use gtk4::glib;
     use gtk4::glib::Object;
     use gtk4::subclass::prelude::*;
-    use std::cell::RefCell;
+    use std::cell::{Cell, RefCell};
+
+    use crate::core::{FileEntry, ItemCount};
 
     #[derive(Default)]
     pub struct FileObject {
-        pub name: RefCell<String>,
-        pub path: RefCell<String>,
-        pub is_directory: RefCell<bool>,
-        pub size: RefCell<String>,
-        pub modified: RefCell<String>,
-        pub icon_name: RefCell<String>,
+        pub entry: RefCell<FileEntry>,
+        pub item_count: Cell<ItemCount>,
     }
 
     #[glib::object_subclass]
@@ -69,90 +92,80 @@ glib::wrapper! {
 
 impl FileObject {
     pub fn new(entry: &FileEntry) -> Self {
+        Self::from_entry(entry.clone())
+    }
+
+    pub fn from_entry(entry: FileEntry) -> Self {
         let obj: Self = Object::builder().build();
-        *obj.imp().name.borrow_mut() = entry.name.clone();
-        *obj.imp().path.borrow_mut() = entry.path.to_string_lossy().to_string();
-        *obj.imp().is_directory.borrow_mut() = entry.is_directory;
-        *obj.imp().size.borrow_mut() = entry.size_display();
-        *obj.imp().modified.borrow_mut() = entry.modified.clone();
-        *obj.imp().icon_name.borrow_mut() = Self::get_nautilus_icon(&entry.name, entry.is_directory);
+        *obj.imp().entry.borrow_mut() = entry;
         obj
     }
 
-    fn get_nautilus_icon(name: &str, is_directory: bool) -> String {
-        if is_directory {
-            // Use themed folder icons for special directories
-            let name_lower = name.to_lowercase();
-            match name_lower.as_str() {
-                "documents" => "folder-documents",
-                "downloads" => "folder-download",
-                "music" => "folder-music",
-                "pictures" => "folder-pictures",
-                "videos" => "folder-videos",
-                "desktop" => "user-desktop",
-                "templates" => "folder-templates",
-                "public" => "folder-publicshare",
-                _ => "folder",
-            }.to_string()
-        } else {
-            // File icons based on extension
-            let extension = name.rsplit('.').next().unwrap_or("").to_lowercase();
-            match extension.as_str() {
-                // Documents
-                "pdf" => "application-pdf",
-                "doc" | "docx" | "odt" => "x-office-document",
-                "xls" | "xlsx" | "ods" => "x-office-spreadsheet",
-                "ppt" | "pptx" | "odp" => "x-office-presentation",
-                "txt" | "md" | "rst" => "text-x-generic",
-                
-                // Images
-                "png" | "jpg" | "jpeg" | "gif" | "bmp" | "svg" | "webp" | "ico" => "image-x-generic",
-                
-                // Audio
-                "mp3" | "wav" | "flac" | "ogg" | "m4a" | "aac" => "audio-x-generic",
-                
-                // Video
-                "mp4" | "mkv" | "avi" | "mov" | "webm" | "wmv" => "video-x-generic",
-                
-                // Archives
-                "zip" | "tar" | "gz" | "bz2" | "xz" | "rar" | "7z" => "application-x-archive",
-                
-                // Code
-                "rs" | "py" | "js" | "ts" | "c" | "cpp" | "h" | "java" | "go" | "rb" | "php" => "text-x-script",
-                "html" | "css" | "xml" | "json" | "yaml" | "yml" | "toml" => "text-x-generic",
-                
-                // Executables
-                "sh" | "bash" => "application-x-executable",
-                "exe" | "msi" => "application-x-ms-dos-executable",
-                "deb" | "rpm" | "appimage" => "application-x-executable",
-                
-                _ => "text-x-generic",
-            }.to_string()
-        }
+    /// The underlying entry; formatting for display happens at bind time
+    pub fn entry(&self) -> std::cell::Ref<'_, FileEntry> {
+        self.imp().entry.borrow()
     }
 
     pub fn name(&self) -> String {
-        self.imp().name.borrow().clone()
+        self.entry().name.clone()
     }
 
     pub fn path(&self) -> PathBuf {
-        PathBuf::from(self.imp().path.borrow().clone())
+        self.entry().path.clone()
     }
 
     pub fn is_directory(&self) -> bool {
-        *self.imp().is_directory.borrow()
+        self.entry().is_directory
+    }
+
+    pub fn size(&self) -> u64 {
+        self.entry().size
+    }
+
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.entry().modified
+    }
+
+    pub fn is_symlink(&self) -> bool {
+        self.entry().is_symlink
+    }
+
+    pub fn item_count(&self) -> ItemCount {
+        self.imp().item_count.get()
+    }
+
+    pub fn set_item_count(&self, count: ItemCount) {
+        self.imp().item_count.set(count);
+    }
+
+    pub fn is_broken_link(&self) -> bool {
+        self.entry().is_broken_link
+    }
+
+    pub fn content_type(&self) -> String {
+        self.entry().content_type.clone()
     }
 
-    pub fn size(&self) -> String {
-        self.imp().size.borrow().clone()
+    pub fn type_description(&self) -> String {
+        self.entry().type_description.clone()
     }
 
-    pub fn modified(&self) -> String {
-        self.imp().modified.borrow().clone()
+    /// Themed icon for this file's content type
+    pub fn icon(&self) -> gio::Icon {
+        let entry = self.entry();
+        Mime::icon(&entry.content_type, &entry.icon_name, entry.is_directory)
     }
 
-    pub fn icon_name(&self) -> String {
-        self.imp().icon_name.borrow().clone()
+    /// Small emblem drawn over the icon: a link arrow, or a warning for broken links
+    pub fn emblem_icon_name(&self) -> Option<&'static str> {
+        let entry = self.entry();
+        if entry.is_broken_link {
+            Some("dialog-warning-symbolic")
+        } else if entry.is_symlink {
+            Some("emblem-symbolic-link")
+        } else {
+            None
+        }
     }
 }
 
@@ -160,30 +173,67 @@ impl FileObject {
 pub struct FileGridView {
     container: gtk4::Box,
     stack: Stack,
-    list_view: ListView,
+    column_view: ColumnView,
+    list_columns: Rc<Vec<(ListColumn, ColumnViewColumn)>>,
+    column_layout: Rc<RefCell<ColumnLayout>>,
     grid_view: GridView,
     store: gio::ListStore,
     filter: CustomFilter,
     selection: MultiSelection,
     current_path: Rc<RefCell<PathBuf>>,
-    all_entries: Rc<RefCell<Vec<FileEntry>>>,
-    show_hidden: Rc<RefCell<bool>>,
-    view_mode: Rc<RefCell<ViewMode>>,
-
-    on_directory_activated: Rc<RefCell<Option<Box<dyn Fn(PathBuf)>>>>,
-    on_copy: Rc<RefCell<Option<Box<dyn Fn(Vec<PathBuf>)>>>>,
-    on_cut: Rc<RefCell<Option<Box<dyn Fn(Vec<PathBuf>)>>>>,
-    on_paste: Rc<RefCell<Option<Box<dyn Fn()>>>>,
-    on_delete: Rc<RefCell<Option<Box<dyn Fn(Vec<PathBuf>)>>>>,
-    on_rename: Rc<RefCell<Option<Box<dyn Fn(PathBuf)>>>>,
-    on_pin: Rc<RefCell<Option<Box<dyn Fn(PathBuf)>>>>,
-    on_open_terminal: Rc<RefCell<Option<Box<dyn Fn(PathBuf)>>>>,
-    on_open_micro: Rc<RefCell<Option<Box<dyn Fn(PathBuf)>>>>,
+    /// Where the current directory lives; all reads and writes go through it
+    vfs: Vfs,
+    /// Backend of the current folder
+    backend: Rc<RefCell<Arc<dyn VfsBackend>>>,
+    watch: Rc<RefCell<Option<VfsWatch>>>,
+    /// Listings of folders the view left recently
+    listing_cache: Rc<RefCell<DirectoryCache<FileObject>>>,
+    /// How the current directory is shown
+    settings: Rc<RefCell<ViewSettings>>,
+    view_settings: Rc<RefCell<ViewSettingsStore>>,
+    read_only: Rc<RefCell<bool>>,
+    thumbnail_pool: Rc<ThumbnailPool>,
+    thumbnail_cache: Rc<RefCell<HashMap<PathBuf, gtk4::gdk::Texture>>>,
+    thumbnail_requested: Rc<RefCell<HashSet<PathBuf>>>,
+
+    on_directory_activated: Callback<PathBuf>,
+    on_copy: Callback<Vec<PathBuf>>,
+    on_cut: Callback<Vec<PathBuf>>,
+    on_paste: SimpleCallback,
+    on_delete: Callback<Vec<PathBuf>>,
+    on_rename: Callback<PathBuf>,
+    on_pin: Callback<PathBuf>,
+    on_open_terminal: Callback<PathBuf>,
+    on_open_micro: Callback<PathBuf>,
+    on_extract_here: Callback<Vec<PathBuf>>,
+    on_extract_to: Callback<Vec<PathBuf>>,
+    on_compress: Callback<Vec<PathBuf>>,
+    on_properties: Callback<Vec<PathBuf>>,
+    on_selection_changed: Callback<Vec<PathBuf>>,
+    on_quick_look: Callback<PathBuf>,
+    on_settings_changed: Callback<ViewSettings>,
+    on_directory_loaded: Callback<PathBuf>,
+    on_items_changed: SimpleCallback,
+    on_loading_changed: Callback<bool>,
+    on_error: Callback<String>,
     current_scan_id: Rc<RefCell<u64>>,
+    /// Whether the current directory is still being listed
+    loading: Rc<RefCell<bool>>,
+    /// Shown over an empty view when a folder takes a while to list
+    loading_spinner: adw::Spinner,
+    /// Covers the view when the current directory can't be listed
+    error_page: adw::StatusPage,
+    /// Hidden items left out of the current directory
+    hidden_count: Rc<RefCell<usize>>,
+    /// Items to select when the directory being loaded arrives
+    pending_selection: Rc<RefCell<Vec<PathBuf>>>,
+    /// Where to scroll to when the directory being loaded arrives
+    pending_scroll: Rc<RefCell<Option<f64>>>,
 }
 
 impl FileGridView {
-    pub fn new() -> Self {
+    /// A view browsing the locations `vfs` leads to
+    pub fn new(vfs: Vfs) -> Self {
         let container = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
             .css_classes(["nautilus-view"])
@@ -199,7 +249,33 @@ impl FileGridView {
         let selection = MultiSelection::new(Some(filter_model));
 
         // Prepare on_pin callback for use in context menus
-        let on_pin: Rc<RefCell<Option<Box<dyn Fn(PathBuf)>>>> = Rc::new(RefCell::new(None));
+        let on_pin: Callback<PathBuf> = Rc::new(RefCell::new(None));
+
+        // Thumbnails are generated in the background and applied to whichever tile shows the file
+        let thumbnail_pool = Rc::new(ThumbnailPool::new());
+        let thumbnail_cache: Rc<RefCell<HashMap<PathBuf, gtk4::gdk::Texture>>> = Rc::new(RefCell::new(HashMap::new()));
+        let thumbnail_requested: Rc<RefCell<HashSet<PathBuf>>> = Rc::new(RefCell::new(HashSet::new()));
+        let thumbnail_targets: Rc<RefCell<HashMap<PathBuf, glib::WeakRef<gtk4::Image>>>> = Rc::new(RefCell::new(HashMap::new()));
+
+        {
+            let results = thumbnail_pool.results();
+            let thumbnail_cache = thumbnail_cache.clone();
+            let thumbnail_targets = thumbnail_targets.clone();
+            glib::spawn_future_local(async move {
+                while let Ok((source, thumbnail)) = results.recv().await {
+                    let Ok(texture) = gtk4::gdk::Texture::from_filename(&thumbnail) else {
+                        continue;
+                    };
+                    if let Some(icon) = thumbnail_targets.borrow().get(&source).and_then(|w| w.upgrade()) {
+                        icon.set_paintable(Some(&texture));
+                    }
+                    thumbnail_cache.borrow_mut().insert(source, texture);
+                }
+            });
+        }
+
+        // Current directory's settings; the factories read the zoom level from it
+        let settings = Rc::new(RefCell::new(ViewSettings::default()));
 
         // ===== GRID VIEW (Nautilus-style) =====
         let grid_factory = SignalListItemFactory::new();
@@ -237,22 +313,68 @@ impl FileGridView {
                 .css_classes(["nautilus-label"])
                 .build();
 
-            tile.append(&icon);
+            tile.append(&with_emblem(&icon, 20));
             tile.append(&name_label);
             item.set_child(Some(&tile));
         });
 
-        grid_factory.connect_bind(|_, item| {
-            let item = item.downcast_ref::<ListItem>().unwrap();
-            let file_obj = item.item().and_downcast::<FileObject>().unwrap();
-
-            let tile = item.child().and_downcast::<gtk4::Box>().unwrap();
-            let icon = tile.first_child().and_downcast::<gtk4::Image>().unwrap();
-            let name_label = icon.next_sibling().and_downcast::<Label>().unwrap();
+        {
+            let thumbnail_pool = thumbnail_pool.clone();
+            let thumbnail_cache = thumbnail_cache.clone();
+            let thumbnail_requested = thumbnail_requested.clone();
+            let thumbnail_targets = thumbnail_targets.clone();
+            let settings = settings.clone();
+            grid_factory.connect_bind(move |_, item| {
+                let item = item.downcast_ref::<ListItem>().unwrap();
+                let file_obj = item.item().and_downcast::<FileObject>().unwrap();
+
+                let tile = item.child().and_downcast::<gtk4::Box>().unwrap();
+                let overlay = tile.first_child().and_downcast::<gtk4::Overlay>().unwrap();
+                let icon = overlay.child().and_downcast::<gtk4::Image>().unwrap();
+                let name_label = overlay.next_sibling().and_downcast::<Label>().unwrap();
+
+                // Tiles and labels grow with the zoom level
+                let icon_size = settings.borrow().icon_size(ViewMode::Grid);
+                icon.set_pixel_size(icon_size);
+                tile.set_width_request(icon_size + 32);
+                tile.set_height_request(icon_size + 32);
+                name_label.set_max_width_chars((icon_size / 5).clamp(8, 30));
+
+                icon.set_from_gicon(&file_obj.icon());
+                name_label.set_text(&file_obj.name());
+                bind_link_state(&tile, &overlay, &file_obj);
+
+                // Thumbnails load lazily, only for tiles that are actually bound.
+                // Remote files would have to be downloaded whole, so they keep their icons.
+                let path = file_obj.path();
+                if !file_obj.is_directory() && !Remote::is_remote(&path) {
+                    if let Some(texture) = thumbnail_cache.borrow().get(&path) {
+                        icon.set_paintable(Some(texture));
+                        return;
+                    }
+                    thumbnail_targets.borrow_mut().insert(path.clone(), icon.downgrade());
+                    if thumbnail_requested.borrow_mut().insert(path.clone()) {
+                        // Use the large cache when the tile is drawn bigger than a normal thumbnail
+                        let size = if icon.pixel_size() * icon.scale_factor() > ThumbnailSize::Normal.pixels() {
+                            ThumbnailSize::Large
+                        } else {
+                            ThumbnailSize::Normal
+                        };
+                        thumbnail_pool.request(path, size);
+                    }
+                }
+            });
+        }
 
-            icon.set_icon_name(Some(&file_obj.icon_name()));
-            name_label.set_text(&file_obj.name());
-        });
+        {
+            let thumbnail_targets = thumbnail_targets.clone();
+            grid_factory.connect_unbind(move |_, item| {
+                let item = item.downcast_ref::<ListItem>().unwrap();
+                if let Some(file_obj) = item.item().and_downcast::<FileObject>() {
+                    thumbnail_targets.borrow_mut().remove(&file_obj.path());
+                }
+            });
+        }
 
         let grid_view = GridView::builder()
             .model(&selection)
@@ -262,97 +384,158 @@ impl FileGridView {
             .css_classes(["nautilus-grid"])
             .build();
 
-        // ===== LIST VIEW =====
-        let list_factory = SignalListItemFactory::new();
-
-        list_factory.connect_setup(|_, item| {
-            let item = item.downcast_ref::<ListItem>().unwrap();
-
-            let hbox = gtk4::Box::builder()
-                .orientation(gtk4::Orientation::Horizontal)
-                .spacing(12)
-                .margin_start(12)
-                .margin_end(12)
-                .margin_top(6)
-                .margin_bottom(6)
-                .css_classes(["nautilus-list-row"])
-                .build();
-
-            let icon = gtk4::Image::builder()
-                .pixel_size(32)
-                .css_classes(["nautilus-list-icon"])
-                .build();
-
-            let name_label = Label::builder()
-                .halign(gtk4::Align::Start)
-                .hexpand(true)
-                .ellipsize(gtk4::pango::EllipsizeMode::End)
-                .css_classes(["nautilus-list-name"])
-                .build();
-
-            let size_label = Label::builder()
-                .halign(gtk4::Align::End)
-                .width_chars(10)
-                .css_classes(["dim-label", "nautilus-list-size"])
-                .build();
-
-            let date_label = Label::builder()
-                .halign(gtk4::Align::End)
-                .width_chars(16)
-                .css_classes(["dim-label", "nautilus-list-date"])
-                .build();
-
-            hbox.append(&icon);
-            hbox.append(&name_label);
-            hbox.append(&size_label);
-            hbox.append(&date_label);
+        // ===== LIST VIEW (configurable columns) =====
+        let column_layout = Rc::new(RefCell::new(ColumnLayout::load()));
+        let column_view = ColumnView::builder()
+            .model(&selection)
+            .reorderable(true)
+            .show_column_separators(false)
+            .css_classes(["nautilus-list"])
+            .build();
 
-            item.set_child(Some(&hbox));
-        });
+        let columns: Rc<Vec<(ListColumn, ColumnViewColumn)>> = Rc::new(
+            ListColumn::ALL
+                .iter()
+                .map(|column| {
+                    let view_column = ColumnViewColumn::builder()
+                        .title(column.title())
+                        .factory(&Self::column_factory(*column, settings.clone()))
+                        .resizable(true)
+                        .expand(*column == ListColumn::Name)
+                        .build();
+                    (*column, view_column)
+                })
+                .collect(),
+        );
+        let applying_columns = Rc::new(RefCell::new(false));
+        let pending_column_save: Rc<RefCell<Option<glib::SourceId>>> = Rc::new(RefCell::new(None));
+        Self::apply_column_layout(&column_view, &columns, &column_layout.borrow(), &applying_columns);
+
+        // Column widths and header drags are written back to the layout and saved shortly after
+        for (column, view_column) in columns.iter() {
+            let column = *column;
+            let column_layout = column_layout.clone();
+            let applying_columns = applying_columns.clone();
+            let pending_column_save = pending_column_save.clone();
+            view_column.connect_fixed_width_notify(move |view_column| {
+                if *applying_columns.borrow() || view_column.fixed_width() <= 0 {
+                    return;
+                }
+                if let Some(setting) = column_layout.borrow_mut().setting_mut(column) {
+                    setting.width = view_column.fixed_width();
+                }
+                Self::schedule_column_save(&column_layout, &pending_column_save);
+            });
+        }
 
-        list_factory.connect_bind(|_, item| {
-            let item = item.downcast_ref::<ListItem>().unwrap();
-            let file_obj = item.item().and_downcast::<FileObject>().unwrap();
+        {
+            let columns = columns.clone();
+            let column_layout = column_layout.clone();
+            let applying_columns = applying_columns.clone();
+            let pending_column_save = pending_column_save.clone();
+            column_view.columns().connect_items_changed(move |model, _, _, _| {
+                // Moving a header removes and re-inserts it; wait until it's back
+                if *applying_columns.borrow() || model.n_items() as usize != columns.len() {
+                    return;
+                }
 
-            let hbox = item.child().and_downcast::<gtk4::Box>().unwrap();
-            let icon = hbox.first_child().and_downcast::<gtk4::Image>().unwrap();
-            let name_label = icon.next_sibling().and_downcast::<Label>().unwrap();
-            let size_label = name_label.next_sibling().and_downcast::<Label>().unwrap();
-            let date_label = size_label.next_sibling().and_downcast::<Label>().unwrap();
+                let order: Vec<ListColumn> = (0..model.n_items())
+                    .filter_map(|i| model.item(i).and_downcast::<ColumnViewColumn>())
+                    .filter_map(|view_column| {
+                        columns.iter().find(|(_, c)| *c == view_column).map(|(column, _)| *column)
+                    })
+                    .collect();
+
+                let mut layout = column_layout.borrow_mut();
+                layout
+                    .columns
+                    .sort_by_key(|setting| order.iter().position(|c| *c == setting.column));
+                drop(layout);
+                Self::schedule_column_save(&column_layout, &pending_column_save);
+            });
+        }
 
-            icon.set_icon_name(Some(&file_obj.icon_name()));
-            name_label.set_text(&file_obj.name());
-            size_label.set_text(&file_obj.size());
-            date_label.set_text(&file_obj.modified());
-        });
+        // Right-clicking a column header offers the column chooser
+        {
+            let header_menu = gio::Menu::new();
+            header_menu.append(Some("Visible Columns…"), Some("list.choose-columns"));
+            for (_, view_column) in columns.iter() {
+                view_column.set_header_menu(Some(&header_menu));
+            }
 
-        let list_view = ListView::builder()
-            .model(&selection)
-            .factory(&list_factory)
-            .css_classes(["nautilus-list"])
-            .build();
+            let action_group = gio::SimpleActionGroup::new();
+            let choose_columns = gio::SimpleAction::new("choose-columns", None);
+            let column_view_clone = column_view.clone();
+            let columns = columns.clone();
+            let column_layout = column_layout.clone();
+            choose_columns.connect_activate(move |_, _| {
+                let on_changed: Rc<dyn Fn()> = {
+                    let column_view = column_view_clone.clone();
+                    let columns = columns.clone();
+                    let column_layout = column_layout.clone();
+                    let applying_columns = applying_columns.clone();
+                    let pending_column_save = pending_column_save.clone();
+                    Rc::new(move || {
+                        Self::apply_column_layout(&column_view, &columns, &column_layout.borrow(), &applying_columns);
+                        Self::schedule_column_save(&column_layout, &pending_column_save);
+                    })
+                };
+                ColumnChooser::present(&column_view_clone, column_layout.clone(), on_changed);
+            });
+            action_group.add_action(&choose_columns);
+            column_view.insert_action_group("list", Some(&action_group));
+        }
 
         // Add views to stack
         stack.add_named(&grid_view, Some("grid"));
-        stack.add_named(&list_view, Some("list"));
+        stack.add_named(&column_view, Some("list"));
         stack.set_visible_child_name("grid");
 
-        container.append(&stack);
+        let loading_spinner = adw::Spinner::builder()
+            .width_request(32)
+            .height_request(32)
+            .halign(gtk4::Align::Center)
+            .valign(gtk4::Align::Center)
+            .can_target(false)
+            .visible(false)
+            .build();
+        let overlay = gtk4::Overlay::new();
+        overlay.set_child(Some(&stack));
+        overlay.add_overlay(&loading_spinner);
+
+        let error_page = adw::StatusPage::builder()
+            .visible(false)
+            .css_classes(["compact"])
+            .build();
+        overlay.add_overlay(&error_page);
+        container.append(&overlay);
 
         let current_path = Rc::new(RefCell::new(PathBuf::new()));
-        let all_entries = Rc::new(RefCell::new(Vec::new()));
-        let show_hidden = Rc::new(RefCell::new(false));
-        let view_mode = Rc::new(RefCell::new(ViewMode::Grid));
-
-        let on_directory_activated: Rc<RefCell<Option<Box<dyn Fn(PathBuf)>>>> = Rc::new(RefCell::new(None));
-        let on_copy: Rc<RefCell<Option<Box<dyn Fn(Vec<PathBuf>)>>>> = Rc::new(RefCell::new(None));
-        let on_cut: Rc<RefCell<Option<Box<dyn Fn(Vec<PathBuf>)>>>> = Rc::new(RefCell::new(None));
-        let on_paste: Rc<RefCell<Option<Box<dyn Fn()>>>> = Rc::new(RefCell::new(None));
-        let on_delete: Rc<RefCell<Option<Box<dyn Fn(Vec<PathBuf>)>>>> = Rc::new(RefCell::new(None));
-        let on_rename: Rc<RefCell<Option<Box<dyn Fn(PathBuf)>>>> = Rc::new(RefCell::new(None));
+        let backend: Rc<RefCell<Arc<dyn VfsBackend>>> = Rc::new(RefCell::new(vfs.backend(Path::new("/"))));
+        let view_settings = Rc::new(RefCell::new(ViewSettingsStore::load()));
+        // Archives are browsed read-only: no paste, rename, cut or delete inside them
+        let read_only = Rc::new(RefCell::new(false));
+
+        let on_directory_activated: Callback<PathBuf> = Rc::new(RefCell::new(None));
+        let on_copy: Callback<Vec<PathBuf>> = Rc::new(RefCell::new(None));
+        let on_cut: Callback<Vec<PathBuf>> = Rc::new(RefCell::new(None));
+        let on_paste: SimpleCallback = Rc::new(RefCell::new(None));
+        let on_delete: Callback<Vec<PathBuf>> = Rc::new(RefCell::new(None));
+        let on_rename: Callback<PathBuf> = Rc::new(RefCell::new(None));
         // on_pin is already created above for use in factories
-        let on_open_terminal: Rc<RefCell<Option<Box<dyn Fn(PathBuf)>>>> = Rc::new(RefCell::new(None));
-        let on_open_micro: Rc<RefCell<Option<Box<dyn Fn(PathBuf)>>>> = Rc::new(RefCell::new(None));
+        let on_open_terminal: Callback<PathBuf> = Rc::new(RefCell::new(None));
+        let on_open_micro: Callback<PathBuf> = Rc::new(RefCell::new(None));
+        let on_extract_here: Callback<Vec<PathBuf>> = Rc::new(RefCell::new(None));
+        let on_extract_to: Callback<Vec<PathBuf>> = Rc::new(RefCell::new(None));
+        let on_compress: Callback<Vec<PathBuf>> = Rc::new(RefCell::new(None));
+        let on_properties: Callback<Vec<PathBuf>> = Rc::new(RefCell::new(None));
+        let on_selection_changed: Callback<Vec<PathBuf>> = Rc::new(RefCell::new(None));
+        let on_quick_look: Callback<PathBuf> = Rc::new(RefCell::new(None));
+        let on_settings_changed: Callback<ViewSettings> = Rc::new(RefCell::new(None));
+        let on_directory_loaded: Callback<PathBuf> = Rc::new(RefCell::new(None));
+        let on_items_changed: SimpleCallback = Rc::new(RefCell::new(None));
+        let on_loading_changed: Callback<bool> = Rc::new(RefCell::new(None));
+        let on_error: Callback<String> = Rc::new(RefCell::new(None));
         let current_scan_id = Rc::new(RefCell::new(0u64));
 
         // Keyboard shortcuts for Grid and List views
@@ -363,6 +546,8 @@ impl FileGridView {
             let on_cut_clone = on_cut.clone();
             let on_paste_clone = on_paste.clone();
             let on_rename_clone = on_rename.clone();
+            let on_properties_clone = on_properties.clone();
+            let read_only_clone = read_only.clone();
             let key_controller = EventControllerKey::new();
             
             key_controller.connect_key_pressed(move |_, key, _keycode, state| {
@@ -381,9 +566,10 @@ impl FileGridView {
                     }
                     selected_paths
                 };
+                let read_only = *read_only_clone.borrow();
                 
                 // Delete key
-                if key == gtk4::gdk::Key::Delete {
+                if key == gtk4::gdk::Key::Delete && !read_only {
                     let selected_paths = get_selected_paths();
                     if !selected_paths.is_empty() {
                         if let Some(ref callback) = *on_delete_clone.borrow() {
@@ -394,7 +580,7 @@ impl FileGridView {
                 }
                 
                 // F2 - Rename
-                if key == gtk4::gdk::Key::F2 {
+                if key == gtk4::gdk::Key::F2 && !read_only {
                     let selected_paths = get_selected_paths();
                     if selected_paths.len() == 1 {
                         if let Some(ref callback) = *on_rename_clone.borrow() {
@@ -404,6 +590,17 @@ impl FileGridView {
                     }
                 }
                 
+                // Alt+Enter - Properties
+                if key == gtk4::gdk::Key::Return && state.contains(gtk4::gdk::ModifierType::ALT_MASK) && !read_only {
+                    let selected_paths = get_selected_paths();
+                    if !selected_paths.is_empty() {
+                        if let Some(ref callback) = *on_properties_clone.borrow() {
+                            callback(selected_paths);
+                            return glib::Propagation::Stop;
+                        }
+                    }
+                }
+                
                 // Ctrl+C - Copy
                 if key == gtk4::gdk::Key::c && state.contains(gtk4::gdk::ModifierType::CONTROL_MASK) {
                     let selected_paths = get_selected_paths();
@@ -416,7 +613,7 @@ impl FileGridView {
                 }
                 
                 // Ctrl+X - Cut
-                if key == gtk4::gdk::Key::x && state.contains(gtk4::gdk::ModifierType::CONTROL_MASK) {
+                if key == gtk4::gdk::Key::x && state.contains(gtk4::gdk::ModifierType::CONTROL_MASK) && !read_only {
                     let selected_paths = get_selected_paths();
                     if !selected_paths.is_empty() {
                         if let Some(ref callback) = *on_cut_clone.borrow() {
@@ -427,7 +624,7 @@ impl FileGridView {
                 }
                 
                 // Ctrl+V - Paste
-                if key == gtk4::gdk::Key::v && state.contains(gtk4::gdk::ModifierType::CONTROL_MASK) {
+                if key == gtk4::gdk::Key::v && state.contains(gtk4::gdk::ModifierType::CONTROL_MASK) && !read_only {
                     if let Some(ref callback) = *on_paste_clone.borrow() {
                         callback();
                         return glib::Propagation::Stop;
@@ -438,27 +635,55 @@ impl FileGridView {
             });
             
             grid_view.add_controller(key_controller.clone());
-            list_view.add_controller(key_controller);
+            column_view.add_controller(key_controller);
+        }
+
+        // Space opens quick look. Captured before the list item uses it to toggle selection.
+        {
+            let selection_clone = selection.clone();
+            let on_quick_look_clone = on_quick_look.clone();
+            let key_controller = EventControllerKey::new();
+            key_controller.set_propagation_phase(gtk4::PropagationPhase::Capture);
+
+            key_controller.connect_key_pressed(move |_, key, _keycode, state| {
+                if key != gtk4::gdk::Key::space || !state.is_empty() {
+                    return glib::Propagation::Proceed;
+                }
+
+                if let Some(path) = Self::selected_paths_of(&selection_clone).into_iter().next() {
+                    if let Some(ref callback) = *on_quick_look_clone.borrow() {
+                        callback(path);
+                        return glib::Propagation::Stop;
+                    }
+                }
+                glib::Propagation::Proceed
+            });
+
+            grid_view.add_controller(key_controller.clone());
+            column_view.add_controller(key_controller);
+        }
+
+        // Notify selection changes (preview pane)
+        {
+            let on_selection_changed_clone = on_selection_changed.clone();
+            selection.connect_selection_changed(move |selection, _, _| {
+                if let Some(ref callback) = *on_selection_changed_clone.borrow() {
+                    callback(Self::selected_paths_of(selection));
+                }
+            });
         }
 
         // Double-click activation for GRID VIEW
         {
             let on_directory_activated_clone = on_directory_activated.clone();
+            let on_error_clone = on_error.clone();
+            let backend_clone = backend.clone();
             let selection_clone = selection.clone();
 
             grid_view.connect_activate(move |_, position| {
                 if let Some(item) = selection_clone.item(position) {
                     let file_obj = item.downcast::<FileObject>().unwrap();
-                    if file_obj.is_directory() {
-                        if let Some(ref callback) = *on_directory_activated_clone.borrow() {
-                            callback(file_obj.path());
-                        }
-                    } else {
-                        // Open file with default application
-                        if let Err(e) = open::that(&file_obj.path()) {
-                            eprintln!("Failed to open file: {}", e);
-                        }
-                    }
+                    Self::open_item(&file_obj, &backend_clone, &on_directory_activated_clone, &on_error_clone);
                 }
             });
         }
@@ -466,415 +691,90 @@ impl FileGridView {
         // Double-click activation for LIST VIEW
         {
             let on_directory_activated_clone = on_directory_activated.clone();
+            let on_error_clone = on_error.clone();
+            let backend_clone = backend.clone();
             let selection_clone = selection.clone();
 
-            list_view.connect_activate(move |_, position| {
+            column_view.connect_activate(move |_, position| {
                 if let Some(item) = selection_clone.item(position) {
                     let file_obj = item.downcast::<FileObject>().unwrap();
-                    if file_obj.is_directory() {
-                        if let Some(ref callback) = *on_directory_activated_clone.borrow() {
-                            callback(file_obj.path());
-                        }
-                    } else {
-                        if let Err(e) = open::that(&file_obj.path()) {
-                            eprintln!("Failed to open file: {}", e);
-                        }
-                    }
+                    Self::open_item(&file_obj, &backend_clone, &on_directory_activated_clone, &on_error_clone);
                 }
             });
         }
 
-        // Context menu for GRID VIEW - using GMenu with app.toggle-pin action
+        // Context menus for GRID and LIST views
+        let pending_selection: Rc<RefCell<Vec<PathBuf>>> = Rc::new(RefCell::new(Vec::new()));
         {
-            let on_copy_clone = on_copy.clone();
-            let on_cut_clone = on_cut.clone();
-            let on_paste_clone = on_paste.clone();
-            let on_delete_clone = on_delete.clone();
-            let on_rename_clone = on_rename.clone();
-            let selection_clone = selection.clone();
-            let grid_view_clone = grid_view.clone();
-            let current_popover: Rc<RefCell<Option<PopoverMenu>>> = Rc::new(RefCell::new(None));
-
-            let gesture = GestureClick::builder().button(3).build();
-
-            gesture.connect_pressed(move |_, _, x, y| {
-                // Close any existing popover first
-                if let Some(ref mut popover) = *current_popover.borrow_mut() {
-                    popover.popdown();
-                    popover.unparent();
-                }
-                current_popover.borrow_mut().take();
+            let callbacks = ContextMenuCallbacks {
+                on_directory_activated: on_directory_activated.clone(),
+                on_copy: on_copy.clone(),
+                on_cut: on_cut.clone(),
+                on_paste: on_paste.clone(),
+                on_delete: on_delete.clone(),
+                on_rename: on_rename.clone(),
+                on_extract_here: on_extract_here.clone(),
+                on_extract_to: on_extract_to.clone(),
+                on_compress: on_compress.clone(),
+                on_properties: on_properties.clone(),
+                on_error: on_error.clone(),
+                pending_selection: pending_selection.clone(),
+                backend: backend.clone(),
+            };
+            Self::attach_context_menu(&grid_view, &selection, callbacks.clone(), read_only.clone(), current_path.clone());
+            Self::attach_context_menu(&column_view, &selection, callbacks, read_only.clone(), current_path.clone());
+        }
 
-                let mut selected_paths = Vec::new();
+        // Drag source for GRID VIEW
+        {
+            let drag_source = DragSource::new();
+            drag_source.set_actions(gtk4::gdk::DragAction::COPY | gtk4::gdk::DragAction::MOVE);
+            
+            let selection_clone = selection.clone();
+            drag_source.connect_prepare(move |_, _, _| {
+                let mut selected_items = Vec::new();
                 let n_items = selection_clone.n_items();
                 for i in 0..n_items {
                     if selection_clone.is_selected(i) {
                         if let Some(item) = selection_clone.item(i) {
                             if let Ok(file_obj) = item.downcast::<FileObject>() {
-                                selected_paths.push(file_obj.path());
+                                selected_items.push(file_obj);
                             }
                         }
                     }
                 }
-
-                // If no items selected, select item at click position
-                if selected_paths.is_empty() && n_items > 0 {
-                    if let Some(item) = selection_clone.item(0) {
-                        if let Ok(file_obj) = item.downcast::<FileObject>() {
-                            selected_paths.push(file_obj.path());
-                        }
-                    }
-                }
-
-                // Build menu using gio::Menu
-                let menu = gio::Menu::new();
                 
-                if !selected_paths.is_empty() {
-                    // File section
-                    let file_section = gio::Menu::new();
-                    file_section.append(Some("Open"), Some("file.open"));
-                    if selected_paths.len() == 1 {
-                        file_section.append(Some("Rename…"), Some("file.rename"));
-                    }
-                    menu.append_section(None, &file_section);
-                    
-                    // Edit section
-                    let edit_section = gio::Menu::new();
-                    edit_section.append(Some("Copy"), Some("file.copy"));
-                    edit_section.append(Some("Cut"), Some("file.cut"));
-                    menu.append_section(None, &edit_section);
+                if !selected_items.is_empty() {
+                    let gfiles: Vec<gio::File> = selected_items
+                        .iter()
+                        .map(|obj| gio::File::for_path(&obj.path()))
+                        .collect();
                     
-                    // Delete section
-                    let delete_section = gio::Menu::new();
-                    delete_section.append(Some("Move to Trash"), Some("file.delete"));
-                    menu.append_section(None, &delete_section);
+                    let file_list = gtk4::gdk::FileList::from_array(&gfiles);
+                    let content = gtk4::gdk::ContentProvider::for_value(&file_list.to_value());
+                    Some(content)
                 } else {
-                    let paste_section = gio::Menu::new();
-                    paste_section.append(Some("Paste"), Some("file.paste"));
-                    menu.append_section(None, &paste_section);
-                }
-
-                // Create action group for file-specific actions
-                let action_group = gio::SimpleActionGroup::new();
-
-                // Paste action
-                {
-                    let on_paste = on_paste_clone.clone();
-                    let action = gio::SimpleAction::new("paste", None);
-                    action.connect_activate(move |_, _| {
-                        if let Some(ref callback) = *on_paste.borrow() {
-                            callback();
-                        }
-                    });
-                    action_group.add_action(&action);
-                }
-
-                // Open action
-                {
-                    let paths = selected_paths.clone();
-                    let action = gio::SimpleAction::new("open", None);
-                    action.connect_activate(move |_, _| {
-                        for path in &paths {
-                            if let Err(e) = open::that(path) {
-                                eprintln!("Failed to open: {}", e);
-                            }
-                        }
-                    });
-                    action_group.add_action(&action);
-                }
-
-                // Copy action
-                {
-                    let paths = selected_paths.clone();
-                    let on_copy = on_copy_clone.clone();
-                    let action = gio::SimpleAction::new("copy", None);
-                    action.connect_activate(move |_, _| {
-                        if let Some(ref callback) = *on_copy.borrow() {
-                            callback(paths.clone());
-                        }
-                    });
-                    action_group.add_action(&action);
-                }
-
-                // Cut action
-                {
-                    let paths = selected_paths.clone();
-                    let on_cut = on_cut_clone.clone();
-                    let action = gio::SimpleAction::new("cut", None);
-                    action.connect_activate(move |_, _| {
-                        if let Some(ref callback) = *on_cut.borrow() {
-                            callback(paths.clone());
-                        }
-                    });
-                    action_group.add_action(&action);
-                }
-
-                // Delete action
-                {
-                    let paths = selected_paths.clone();
-                    let on_delete = on_delete_clone.clone();
-                    let action = gio::SimpleAction::new("delete", None);
-                    action.connect_activate(move |_, _| {
-                        if let Some(ref callback) = *on_delete.borrow() {
-                            callback(paths.clone());
-                        }
-                    });
-                    action_group.add_action(&action);
-                }
-
-                // Rename action
-                {
-                    let paths = selected_paths.clone();
-                    let on_rename = on_rename_clone.clone();
-                    let action = gio::SimpleAction::new("rename", None);
-                    action.connect_activate(move |_, _| {
-                        if let Some(path) = paths.first() {
-                            if let Some(ref callback) = *on_rename.borrow() {
-                                callback(path.clone());
-                            }
-                        }
-                    });
-                    action_group.add_action(&action);
-                }
-
-                // Create popover and attach action group
-                let popover = PopoverMenu::from_model(Some(&menu));
-                
-                if grid_view_clone.parent().is_some() {
-                    popover.set_parent(&grid_view_clone);
+                    None
                 }
-                popover.set_pointing_to(Some(&gtk4::gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
-                
-                popover.insert_action_group("file", Some(&action_group));
-                
-                let popover_clone = popover.clone();
-                let current_popover_clone = current_popover.clone();
-                popover.connect_closed(move |p: &PopoverMenu| {
-                    p.unparent();
-                    current_popover_clone.borrow_mut().take();
-                });
-                
-                *current_popover.borrow_mut() = Some(popover_clone.clone());
-                popover_clone.popup();
             });
-
-            grid_view.add_controller(gesture);
+            
+            grid_view.add_controller(drag_source);
         }
 
-        // Context menu for LIST VIEW - using GMenu with app.toggle-pin action
+        // Drag source for LIST VIEW
         {
-            let on_copy_clone = on_copy.clone();
-            let on_cut_clone = on_cut.clone();
-            let on_paste_clone = on_paste.clone();
-            let on_delete_clone = on_delete.clone();
-            let on_rename_clone = on_rename.clone();
+            let drag_source = DragSource::new();
+            drag_source.set_actions(gtk4::gdk::DragAction::COPY | gtk4::gdk::DragAction::MOVE);
+            
             let selection_clone = selection.clone();
-            let list_view_clone = list_view.clone();
-            let current_popover: Rc<RefCell<Option<PopoverMenu>>> = Rc::new(RefCell::new(None));
-
-            let gesture = GestureClick::builder().button(3).build();
-
-            gesture.connect_pressed(move |_, _, x, y| {
-                // Close any existing popover first
-                if let Some(ref mut popover) = *current_popover.borrow_mut() {
-                    popover.popdown();
-                    popover.unparent();
-                }
-                current_popover.borrow_mut().take();
-
-                let mut selected_paths = Vec::new();
+            drag_source.connect_prepare(move |_, _, _| {
+                let mut selected_items = Vec::new();
                 let n_items = selection_clone.n_items();
                 for i in 0..n_items {
                     if selection_clone.is_selected(i) {
                         if let Some(item) = selection_clone.item(i) {
                             if let Ok(file_obj) = item.downcast::<FileObject>() {
-                                selected_paths.push(file_obj.path());
-                            }
-                        }
-                    }
-                }
-
-                if selected_paths.is_empty() && n_items > 0 {
-                    if let Some(item) = selection_clone.item(0) {
-                        if let Ok(file_obj) = item.downcast::<FileObject>() {
-                            selected_paths.push(file_obj.path());
-                        }
-                    }
-                }
-
-                // Build menu using gio::Menu
-                let menu = gio::Menu::new();
-                
-                if !selected_paths.is_empty() {
-                    let file_section = gio::Menu::new();
-                    file_section.append(Some("Open"), Some("file.open"));
-                    if selected_paths.len() == 1 {
-                        file_section.append(Some("Rename…"), Some("file.rename"));
-                    }
-                    menu.append_section(None, &file_section);
-                    
-                    let edit_section = gio::Menu::new();
-                    edit_section.append(Some("Copy"), Some("file.copy"));
-                    edit_section.append(Some("Cut"), Some("file.cut"));
-                    menu.append_section(None, &edit_section);
-                    
-                    let delete_section = gio::Menu::new();
-                    delete_section.append(Some("Move to Trash"), Some("file.delete"));
-                    menu.append_section(None, &delete_section);
-                } else {
-                    let paste_section = gio::Menu::new();
-                    paste_section.append(Some("Paste"), Some("file.paste"));
-                    menu.append_section(None, &paste_section);
-                }
-
-                // Create action group
-                let action_group = gio::SimpleActionGroup::new();
-
-                {
-                    let on_paste = on_paste_clone.clone();
-                    let action = gio::SimpleAction::new("paste", None);
-                    action.connect_activate(move |_, _| {
-                        if let Some(ref callback) = *on_paste.borrow() {
-                            callback();
-                        }
-                    });
-                    action_group.add_action(&action);
-                }
-
-                {
-                    let paths = selected_paths.clone();
-                    let action = gio::SimpleAction::new("open", None);
-                    action.connect_activate(move |_, _| {
-                        for path in &paths {
-                            let _ = open::that(path);
-                        }
-                    });
-                    action_group.add_action(&action);
-                }
-
-                {
-                    let paths = selected_paths.clone();
-                    let on_copy = on_copy_clone.clone();
-                    let action = gio::SimpleAction::new("copy", None);
-                    action.connect_activate(move |_, _| {
-                        if let Some(ref callback) = *on_copy.borrow() {
-                            callback(paths.clone());
-                        }
-                    });
-                    action_group.add_action(&action);
-                }
-
-                {
-                    let paths = selected_paths.clone();
-                    let on_cut = on_cut_clone.clone();
-                    let action = gio::SimpleAction::new("cut", None);
-                    action.connect_activate(move |_, _| {
-                        if let Some(ref callback) = *on_cut.borrow() {
-                            callback(paths.clone());
-                        }
-                    });
-                    action_group.add_action(&action);
-                }
-
-                {
-                    let paths = selected_paths.clone();
-                    let on_delete = on_delete_clone.clone();
-                    let action = gio::SimpleAction::new("delete", None);
-                    action.connect_activate(move |_, _| {
-                        if let Some(ref callback) = *on_delete.borrow() {
-                            callback(paths.clone());
-                        }
-                    });
-                    action_group.add_action(&action);
-                }
-
-                {
-                    let paths = selected_paths.clone();
-                    let on_rename = on_rename_clone.clone();
-                    let action = gio::SimpleAction::new("rename", None);
-                    action.connect_activate(move |_, _| {
-                        if let Some(path) = paths.first() {
-                            if let Some(ref callback) = *on_rename.borrow() {
-                                callback(path.clone());
-                            }
-                        }
-                    });
-                    action_group.add_action(&action);
-                }
-
-                let popover = PopoverMenu::from_model(Some(&menu));
-                
-                if list_view_clone.parent().is_some() {
-                    popover.set_parent(&list_view_clone);
-                }
-                popover.set_pointing_to(Some(&gtk4::gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
-                
-                popover.insert_action_group("file", Some(&action_group));
-                
-                let popover_clone = popover.clone();
-                let current_popover_clone = current_popover.clone();
-                popover.connect_closed(move |p: &PopoverMenu| {
-                    p.unparent();
-                    current_popover_clone.borrow_mut().take();
-                });
-                
-                *current_popover.borrow_mut() = Some(popover_clone.clone());
-                popover_clone.popup();
-            });
-
-            list_view.add_controller(gesture);
-        }
-
-        // Drag source for GRID VIEW
-        {
-            let drag_source = DragSource::new();
-            drag_source.set_actions(gtk4::gdk::DragAction::COPY | gtk4::gdk::DragAction::MOVE);
-            
-            let selection_clone = selection.clone();
-            drag_source.connect_prepare(move |_, _, _| {
-                let mut selected_items = Vec::new();
-                let n_items = selection_clone.n_items();
-                for i in 0..n_items {
-                    if selection_clone.is_selected(i) {
-                        if let Some(item) = selection_clone.item(i) {
-                            if let Ok(file_obj) = item.downcast::<FileObject>() {
-                                selected_items.push(file_obj);
-                            }
-                        }
-                    }
-                }
-                
-                if !selected_items.is_empty() {
-                    let gfiles: Vec<gio::File> = selected_items
-                        .iter()
-                        .map(|obj| gio::File::for_path(&obj.path()))
-                        .collect();
-                    
-                    let file_list = gtk4::gdk::FileList::from_array(&gfiles);
-                    let content = gtk4::gdk::ContentProvider::for_value(&file_list.to_value());
-                    Some(content)
-                } else {
-                    None
-                }
-            });
-            
-            grid_view.add_controller(drag_source);
-        }
-
-        // Drag source for LIST VIEW
-        {
-            let drag_source = DragSource::new();
-            drag_source.set_actions(gtk4::gdk::DragAction::COPY | gtk4::gdk::DragAction::MOVE);
-            
-            let selection_clone = selection.clone();
-            drag_source.connect_prepare(move |_, _, _| {
-                let mut selected_items = Vec::new();
-                let n_items = selection_clone.n_items();
-                for i in 0..n_items {
-                    if selection_clone.is_selected(i) {
-                        if let Some(item) = selection_clone.item(i) {
-                            if let Ok(file_obj) = item.downcast::<FileObject>() {
-                                selected_items.push(file_obj);
+                                selected_items.push(file_obj);
                             }
                         }
                     }
@@ -894,195 +794,55 @@ impl FileGridView {
                 }
             });
             
-            list_view.add_controller(drag_source);
+            column_view.add_controller(drag_source);
         }
 
-        // Drop target for GRID VIEW
-        {
+        // Dropped files are copied into the current folder, or moved when the drag asks for it
+        for view in [grid_view.clone().upcast::<gtk4::Widget>(), column_view.clone().upcast()] {
             let current_path_clone = current_path.clone();
             let store_clone = store.clone();
-            let show_hidden_clone = show_hidden.clone();
+            let vfs_clone = vfs.clone();
+            let settings_clone = settings.clone();
+            let read_only_clone = read_only.clone();
+            let view_clone = view.clone();
             
             let drop_target = DropTarget::new(
                 gtk4::gdk::FileList::static_type(), 
                 gtk4::gdk::DragAction::COPY | gtk4::gdk::DragAction::MOVE
             );
             
-            drop_target.connect_drop(move |_, value, _, _| {
-                if let Ok(file_list) = value.get::<gtk4::gdk::FileList>() {
-                    let files: Vec<PathBuf> = file_list.files()
-                        .iter()
-                        .filter_map(|f| f.path())
-                        .collect();
-                    
-                    if files.is_empty() {
-                        return false;
-                    }
-                    
-                    let dest_dir = current_path_clone.borrow().clone();
-                    let show_hidden_val = *show_hidden_clone.borrow();
-                    
-                    // Perform copy operation in background thread to avoid blocking UI
-                    let files_clone = files.clone();
-                    let store_clone_final = store_clone.clone();
-                    let dest_dir_clone = dest_dir.clone();
-                    
-                    // Create channel for communication
-                    let (tx, rx) = async_channel::unbounded::<()>();
-                    
-                    // Clone dest_dir for async closure
-                    let dest_dir_for_async = dest_dir_clone.clone();
-                    
-                    // Listen for completion on UI thread
-                    glib::spawn_future_local(async move {
-                        if rx.recv().await.is_ok() {
-                            // Defer rescan to let filesystem settle
-                            glib::timeout_add_local(std::time::Duration::from_millis(500), move || {
-                                if let Ok(entries) = Scanner::scan_with_hidden(&dest_dir_for_async, show_hidden_val) {
-                                    store_clone_final.remove_all();
-                                    for entry in &entries {
-                                        store_clone_final.append(&FileObject::new(entry));
-                                    }
-                                }
-                                glib::ControlFlow::Break
-                            });
-                        }
-                    });
-                    
-                    std::thread::spawn(move || {
-                        for source_file in &files_clone {
-                            if let Some(file_name) = source_file.file_name() {
-                                let mut dest_path = dest_dir_clone.join(file_name);
-                                
-                                if source_file == &dest_path {
-                                    continue;
-                                }
-                                
-                                let mut counter = 1;
-                                while dest_path.exists() {
-                                    let stem = source_file.file_stem()
-                                        .map(|s| s.to_string_lossy().to_string())
-                                        .unwrap_or_default();
-                                    let extension = source_file.extension()
-                                        .map(|e| format!(".{}", e.to_string_lossy()))
-                                        .unwrap_or_default();
-                                    
-                                    let new_name = format!("{} ({}){}", stem, counter, extension);
-                                    dest_path = dest_dir_clone.join(new_name);
-                                    counter += 1;
-                                }
-                                
-                                if let Err(e) = FileOperations::copy_file(source_file, &dest_path) {
-                                    eprintln!("Failed to copy: {}", e);
-                                }
-                            }
-                        }
-                        
-                        // Signal completion
-                        let _ = tx.send_blocking(());
-                    });
-                    
-                    true
-                } else {
-                    false
+            drop_target.connect_drop(move |target, value, _, _| {
+                if *read_only_clone.borrow() {
+                    return false;
                 }
-            });
-            
-            grid_view.add_controller(drop_target);
-        }
 
-        // Drop target for LIST VIEW
-        {
-            let current_path_clone = current_path.clone();
-            let store_clone = store.clone();
-            let show_hidden_clone = show_hidden.clone();
-            
-            let drop_target = DropTarget::new(
-                gtk4::gdk::FileList::static_type(), 
-                gtk4::gdk::DragAction::COPY | gtk4::gdk::DragAction::MOVE
-            );
-            
-            drop_target.connect_drop(move |_, value, _, _| {
-                if let Ok(file_list) = value.get::<gtk4::gdk::FileList>() {
-                    let files: Vec<PathBuf> = file_list.files()
-                        .iter()
-                        .filter_map(|f| f.path())
-                        .collect();
-                    
-                    if files.is_empty() {
-                        return false;
-                    }
-                    
-                    let dest_dir = current_path_clone.borrow().clone();
-                    let show_hidden_val = *show_hidden_clone.borrow();
-                    
-                    // Perform copy operation in background thread to avoid blocking UI
-                    let files_clone = files.clone();
-                    let store_clone_final = store_clone.clone();
-                    let dest_dir_clone = dest_dir.clone();
-                    
-                    // Create channel for communication
-                    let (tx, rx) = async_channel::unbounded::<()>();
-                    
-                    // Clone dest_dir for async closure
-                    let dest_dir_for_async = dest_dir_clone.clone();
-                    
-                    // Listen for completion on UI thread
-                    glib::spawn_future_local(async move {
-                        if rx.recv().await.is_ok() {
-                            // Defer rescan to let filesystem settle
-                            glib::timeout_add_local(std::time::Duration::from_millis(500), move || {
-                                if let Ok(entries) = Scanner::scan_with_hidden(&dest_dir_for_async, show_hidden_val) {
-                                    store_clone_final.remove_all();
-                                    for entry in &entries {
-                                        store_clone_final.append(&FileObject::new(entry));
-                                    }
-                                }
-                                glib::ControlFlow::Break
-                            });
-                        }
-                    });
-                    
-                    std::thread::spawn(move || {
-                        for source_file in &files_clone {
-                            if let Some(file_name) = source_file.file_name() {
-                                let mut dest_path = dest_dir_clone.join(file_name);
-                                
-                                if source_file == &dest_path {
-                                    continue;
-                                }
-                                
-                                let mut counter = 1;
-                                while dest_path.exists() {
-                                    let stem = source_file.file_stem()
-                                        .map(|s| s.to_string_lossy().to_string())
-                                        .unwrap_or_default();
-                                    let extension = source_file.extension()
-                                        .map(|e| format!(".{}", e.to_string_lossy()))
-                                        .unwrap_or_default();
-                                    
-                                    let new_name = format!("{} ({}){}", stem, counter, extension);
-                                    dest_path = dest_dir_clone.join(new_name);
-                                    counter += 1;
-                                }
-                                
-                                if let Err(e) = FileOperations::copy_file(source_file, &dest_path) {
-                                    eprintln!("Failed to copy: {}", e);
-                                }
-                            }
-                        }
-                        
-                        // Signal completion
-                        let _ = tx.send_blocking(());
-                    });
-                    
-                    true
-                } else {
-                    false
+                let Ok(file_list) = value.get::<gtk4::gdk::FileList>() else {
+                    return false;
+                };
+                let files: Vec<PathBuf> = file_list.files()
+                    .iter()
+                    .filter_map(|f| f.path())
+                    .collect();
+                if files.is_empty() {
+                    return false;
                 }
+
+                let is_move = target
+                    .current_drop()
+                    .is_some_and(|drop| drop.actions() == gtk4::gdk::DragAction::MOVE);
+                Self::transfer_dropped_files(
+                    files,
+                    is_move,
+                    current_path_clone.borrow().clone(),
+                    vfs_clone.clone(),
+                    *settings_clone.borrow(),
+                    store_clone.clone(),
+                    view_clone.clone(),
+                );
+                true
             });
             
-            list_view.add_controller(drop_target);
+            view.add_controller(drop_target);
         }
 
         // Add keyboard shortcuts for 'f' (terminal) and 'm' (micro)
@@ -1095,13 +855,13 @@ impl FileGridView {
             let key_controller = gtk4::EventControllerKey::new();
             key_controller.connect_key_pressed(move |_, keyval, _, _| {
                 // Get selected items
-                let mut selected_paths = Vec::new();
+                let mut selected_items = Vec::new();
                 let n_items = selection_clone.n_items();
                 for i in 0..n_items {
                     if selection_clone.is_selected(i) {
                         if let Some(item) = selection_clone.item(i) {
                             if let Ok(file_obj) = item.downcast::<FileObject>() {
-                                selected_paths.push(file_obj.path());
+                                selected_items.push((file_obj.path(), file_obj.is_directory()));
                             }
                         }
                     }
@@ -1109,8 +869,8 @@ impl FileGridView {
 
                 // Handle 'f' key - open terminal in directory
                 if keyval == gtk4::gdk::Key::f {
-                    let target_path = if let Some(first_path) = selected_paths.first() {
-                        if first_path.is_dir() {
+                    let target_path = if let Some((first_path, is_dir)) = selected_items.first() {
+                        if *is_dir {
                             first_path.clone()
                         } else {
                             first_path.parent().unwrap_or_else(|| std::path::Path::new("/")).to_path_buf()
@@ -1127,8 +887,8 @@ impl FileGridView {
 
                 // Handle 'm' key - open file in micro
                 if keyval == gtk4::gdk::Key::m {
-                    if let Some(first_path) = selected_paths.first() {
-                        if !first_path.is_dir() {
+                    if let Some((first_path, is_dir)) = selected_items.first() {
+                        if !is_dir {
                             if let Some(ref callback) = *on_open_micro_clone.borrow() {
                                 callback(first_path.clone());
                             }
@@ -1151,21 +911,30 @@ impl FileGridView {
 
             // Add controller to both views
             grid_view.add_controller(key_controller.clone());
-            list_view.add_controller(key_controller);
+            column_view.add_controller(key_controller);
         }
 
-        Self {
+        let file_view = Self {
             container,
             stack,
-            list_view,
+            column_view,
+            list_columns: columns,
+            column_layout,
             grid_view,
             store,
             filter,
             selection,
             current_path,
-            all_entries,
-            show_hidden,
-            view_mode,
+            vfs,
+            backend,
+            watch: Rc::new(RefCell::new(None)),
+            listing_cache: Rc::new(RefCell::new(DirectoryCache::default())),
+            settings,
+            view_settings,
+            read_only,
+            thumbnail_pool,
+            thumbnail_cache,
+            thumbnail_requested,
             on_directory_activated,
             on_copy,
             on_cut,
@@ -1175,132 +944,1451 @@ impl FileGridView {
             on_pin,
             on_open_terminal,
             on_open_micro,
+            on_extract_here,
+            on_extract_to,
+            on_compress,
+            on_properties,
+            on_selection_changed,
+            on_quick_look,
+            on_settings_changed,
+            on_directory_loaded,
+            on_items_changed,
+            on_loading_changed,
+            on_error,
             current_scan_id,
+            loading: Rc::new(RefCell::new(false)),
+            loading_spinner,
+            error_page,
+            hidden_count: Rc::new(RefCell::new(0)),
+            pending_selection,
+            pending_scroll: Rc::new(RefCell::new(None)),
+        };
+
+        // Ctrl+wheel zooms whichever view is shown
+        for view in [file_view.grid_view.clone().upcast::<gtk4::Widget>(), file_view.column_view.clone().upcast()] {
+            let scroll = gtk4::EventControllerScroll::new(
+                gtk4::EventControllerScrollFlags::VERTICAL | gtk4::EventControllerScrollFlags::DISCRETE,
+            );
+            let file_view_clone = file_view.clone();
+            scroll.connect_scroll(move |controller, _, dy| {
+                if !controller.current_event_state().contains(gtk4::gdk::ModifierType::CONTROL_MASK) {
+                    return glib::Propagation::Proceed;
+                }
+                if dy < 0.0 {
+                    file_view_clone.zoom_in();
+                } else if dy > 0.0 {
+                    file_view_clone.zoom_out();
+                }
+                glib::Propagation::Stop
+            });
+            view.add_controller(scroll);
         }
-    }
 
-    pub fn container(&self) -> &gtk4::Box {
-        &self.container
+        file_view
     }
 
-    pub fn load_directory(&self, path: &Path) {
-        // #region agent log
-        debug_log("E", "file_view.rs:load_directory", "Function entry", serde_json::json!({
-            "path": path.to_string_lossy(),
-            "exists": path.exists(),
-            "is_dir": path.is_dir()
-        }));
-        // #endregion
-        
-        self.selection.unselect_all();
-        self.current_path.replace(path.to_path_buf());
-        self.store.remove_all();
+    /// Cell factory for one list column
+    fn column_factory(column: ListColumn, settings: Rc<RefCell<ViewSettings>>) -> SignalListItemFactory {
+        let factory = SignalListItemFactory::new();
+
+        // Folders are counted one at a time on a worker, as rows for them are first shown
+        let (count_tx, count_rx) = async_channel::unbounded::<(PathBuf, async_channel::Sender<Option<usize>>)>();
+        if column == ListColumn::ItemCount {
+            std::thread::spawn(move || {
+                while let Ok((path, reply)) = count_rx.recv_blocking() {
+                    if !reply.is_closed() {
+                        let _ = reply.send_blocking(ListColumn::count_items(&path));
+                    }
+                }
+            });
+        }
 
-        // Increment scan ID to ignore previous pending scans
-        let mut scan_id_guard = self.current_scan_id.borrow_mut();
-        *scan_id_guard += 1;
-        let scan_id = *scan_id_guard;
-        drop(scan_id_guard);
+        factory.connect_setup(move |_, item| {
+            let item = item.downcast_ref::<ListItem>().unwrap();
 
-        let path = path.to_path_buf();
-        let show_hidden = *self.show_hidden.borrow();
-        
-        let (tx, rx) = async_channel::unbounded::<Result<Vec<FileEntry>, std::io::Error>>();
-        
-        let store = self.store.clone();
-        let all_entries = self.all_entries.clone();
-        let current_scan_id = self.current_scan_id.clone();
-        
-        // Spawn background thread for scanning
-        std::thread::spawn(move || {
-            let result = Scanner::scan_with_hidden(&path, show_hidden);
-            let _ = tx.send_blocking(result);
+            if column == ListColumn::Name {
+                let hbox = gtk4::Box::builder()
+                    .orientation(gtk4::Orientation::Horizontal)
+                    .spacing(12)
+                    .margin_start(6)
+                    .css_classes(["nautilus-list-row"])
+                    .build();
+
+                let icon = gtk4::Image::builder()
+                    .pixel_size(32)
+                    .css_classes(["nautilus-list-icon"])
+                    .build();
+
+                let name_label = Label::builder()
+                    .halign(gtk4::Align::Start)
+                    .hexpand(true)
+                    .ellipsize(gtk4::pango::EllipsizeMode::End)
+                    .css_classes(["nautilus-list-name"])
+                    .build();
+
+                hbox.append(&with_emblem(&icon, 14));
+                hbox.append(&name_label);
+                item.set_child(Some(&hbox));
+            } else {
+                let label = Label::builder()
+                    .xalign(if column.is_numeric() { 1.0 } else { 0.0 })
+                    .ellipsize(gtk4::pango::EllipsizeMode::End)
+                    .css_classes(["dim-label", "nautilus-list-cell"])
+                    .build();
+                item.set_child(Some(&label));
+            }
         });
-        
-        // Receive result on UI thread
-        glib::spawn_future_local(async move {
-            if let Ok(result) = rx.recv().await {
-                // Only process if this is still the latest scan
-                if *current_scan_id.borrow() == scan_id {
-                    match result {
-                        Ok(entries) => {
-                            // #region agent log
-                            debug_log("E", "file_view.rs:load_directory", "Scan successful (async)", serde_json::json!({
-                                "entry_count": entries.len()
-                            }));
-                            // #endregion
-                            
-                            all_entries.replace(entries.clone());
-                            for entry in &entries {
-                                store.append(&FileObject::new(entry));
+
+        factory.connect_bind(move |_, item| {
+            let item = item.downcast_ref::<ListItem>().unwrap();
+            let file_obj = item.item().and_downcast::<FileObject>().unwrap();
+
+            if column == ListColumn::Name {
+                let hbox = item.child().and_downcast::<gtk4::Box>().unwrap();
+                let overlay = hbox.first_child().and_downcast::<gtk4::Overlay>().unwrap();
+                let icon = overlay.child().and_downcast::<gtk4::Image>().unwrap();
+                let name_label = overlay.next_sibling().and_downcast::<Label>().unwrap();
+
+                icon.set_pixel_size(settings.borrow().icon_size(ViewMode::List));
+                icon.set_from_gicon(&file_obj.icon());
+                name_label.set_text(&file_obj.name());
+                bind_link_state(&hbox, &overlay, &file_obj);
+            } else if column == ListColumn::ItemCount && file_obj.is_directory() {
+                let label = item.child().and_downcast::<Label>().unwrap();
+                match file_obj.item_count() {
+                    ItemCount::Counted(count) => label.set_text(&ListColumn::item_count_text(count)),
+                    ItemCount::Counting => label.set_text(""),
+                    ItemCount::Unknown => {
+                        label.set_text("");
+                        file_obj.set_item_count(ItemCount::Counting);
+                        let (reply_tx, reply_rx) = async_channel::bounded(1);
+                        let _ = count_tx.send_blocking((file_obj.path(), reply_tx));
+
+                        let item = item.downgrade();
+                        glib::spawn_future_local(async move {
+                            let Ok(count) = reply_rx.recv().await else { return };
+                            file_obj.set_item_count(ItemCount::Counted(count));
+                            // The row may show another item by now
+                            let Some(item) = item.upgrade() else { return };
+                            if item.item().as_ref() == Some(file_obj.upcast_ref()) {
+                                label.set_text(&ListColumn::item_count_text(count));
                             }
-                        }
-                        Err(e) => {
-                            // #region agent log
-                            debug_log("E", "file_view.rs:load_directory", "Scan failed (async)", serde_json::json!({
-                                "error": e.to_string()
-                            }));
-                            // #endregion
-                            eprintln!("Failed to scan directory: {}", e);
-                        }
+                        });
                     }
                 }
+            } else {
+                let label = item.child().and_downcast::<Label>().unwrap();
+                label.set_text(&column.text(&file_obj.entry()));
             }
         });
-    }
 
-    pub fn refresh(&self) {
-        let current = self.current_path.borrow().clone();
-        self.load_directory(&current);
+        factory
     }
 
-    pub fn filter(&self, query: &str) {
-        let query = query.to_lowercase();
-        self.filter.set_filter_func(move |obj| {
-            if query.is_empty() {
-                return true;
+    /// Put the list columns in the layout's order, visibility and widths
+    fn apply_column_layout(
+        column_view: &ColumnView,
+        columns: &[(ListColumn, ColumnViewColumn)],
+        layout: &ColumnLayout,
+        applying: &Rc<RefCell<bool>>,
+    ) {
+        applying.replace(true);
+        for (position, setting) in layout.columns.iter().enumerate() {
+            let Some((_, view_column)) = columns.iter().find(|(column, _)| *column == setting.column) else {
+                continue;
+            };
+            let model = column_view.columns();
+            let current = (0..model.n_items()).find(|i| model.item(*i).as_ref() == Some(view_column.upcast_ref()));
+            if current != Some(position as u32) {
+                column_view.insert_column(position as u32, view_column);
             }
-            let file_obj = obj.downcast_ref::<FileObject>().unwrap();
-            file_obj.name().to_lowercase().contains(&query)
-        });
+            view_column.set_visible(setting.visible);
+            view_column.set_fixed_width(setting.width);
+        }
+        applying.replace(false);
     }
 
-    pub fn toggle_view_mode(&self) {
-        let current = *self.view_mode.borrow();
-        let new_mode = match current {
-            ViewMode::Grid => ViewMode::List,
-            ViewMode::List => ViewMode::Grid,
-        };
-        self.view_mode.replace(new_mode);
-        match new_mode {
-            ViewMode::Grid => self.stack.set_visible_child_name("grid"),
-            ViewMode::List => self.stack.set_visible_child_name("list"),
+    /// Save the column layout once the user stops dragging
+    fn schedule_column_save(layout: &Rc<RefCell<ColumnLayout>>, pending: &Rc<RefCell<Option<glib::SourceId>>>) {
+        if let Some(source) = pending.borrow_mut().take() {
+            source.remove();
         }
-    }
 
-    pub fn is_grid_mode(&self) -> bool {
-        *self.view_mode.borrow() == ViewMode::Grid
+        let layout = layout.clone();
+        let pending_clone = pending.clone();
+        let source = glib::timeout_add_local_once(std::time::Duration::from_millis(500), move || {
+            pending_clone.borrow_mut().take();
+            if let Err(e) = layout.borrow().save() {
+                log::warn!("Failed to save column layout: {}", e);
+            }
+        });
+        pending.replace(Some(source));
     }
 
-    pub fn set_show_hidden(&self, show_hidden: bool) {
-        *self.show_hidden.borrow_mut() = show_hidden;
+    /// Open an item the way double-click does: folders and archives are browsed,
+    /// archive members are extracted to a temporary folder and opened from there,
+    /// other files go to the default application for their type.
+    fn open_item(
+        file_obj: &FileObject,
+        backend: &Rc<RefCell<Arc<dyn VfsBackend>>>,
+        on_directory_activated: &Callback<PathBuf>,
+        on_error: &Callback<String>,
+    ) {
+        let path = file_obj.path();
+        if file_obj.is_directory() || Archive::is_archive(&path) {
+            if let Some(ref callback) = *on_directory_activated.borrow() {
+                callback(path);
+            }
+            return;
+        }
+        Self::open_files(backend, vec![(path, file_obj.content_type())], on_error);
     }
 
-    pub fn connect_directory_activated<F: Fn(PathBuf) + 'static>(&self, callback: F) {
-        *self.on_directory_activated.borrow_mut() = Some(Box::new(callback));
+    /// Open files with their default applications, asking the backend for local copies on a worker
+    fn open_files(
+        backend: &Rc<RefCell<Arc<dyn VfsBackend>>>,
+        files: Vec<(PathBuf, String)>,
+        on_error: &Callback<String>,
+    ) {
+        if files.is_empty() {
+            return;
+        }
+        let backend = backend.borrow().clone();
+        let (tx, rx) = async_channel::bounded(1);
+        std::thread::spawn(move || {
+            let local: Vec<_> = files
+                .into_iter()
+                .map(|(path, content_type)| match backend.local_path(&path) {
+                    Ok(local) => Ok((local, content_type)),
+                    Err(e) => Err(format!("Failed to open {}: {}", path.display(), e)),
+                })
+                .collect();
+            let _ = tx.send_blocking(local);
+        });
+        let on_error = on_error.clone();
+        glib::spawn_future_local(async move {
+            let Ok(local) = rx.recv().await else { return };
+            let mut files = Vec::new();
+            for result in local {
+                match result {
+                    Ok(file) => files.push(file),
+                    Err(message) => Self::report_to(&on_error, message),
+                }
+            }
+            for failure in Applications::open_with_defaults(&files) {
+                Self::report_to(&on_error, failure);
+            }
+        });
     }
 
-    pub fn connect_copy<F: Fn(Vec<PathBuf>) + 'static>(&self, callback: F) {
-        *self.on_copy.borrow_mut() = Some(Box::new(callback));
+    /// Tell the user about a failure, e.g. with a toast
+    fn report_to(on_error: &Callback<String>, message: String) {
+        log::warn!("{}", message);
+        if let Some(ref callback) = *on_error.borrow() {
+            callback(message);
+        }
     }
 
-    pub fn connect_cut<F: Fn(Vec<PathBuf>) + 'static>(&self, callback: F) {
-        *self.on_cut.borrow_mut() = Some(Box::new(callback));
-    }
+    /// Attach the right-click menu to a grid or list view
+    fn attach_context_menu(
+        view: &impl IsA<gtk4::Widget>,
+        selection: &MultiSelection,
+        callbacks: ContextMenuCallbacks,
+        read_only: Rc<RefCell<bool>>,
+        current_path: Rc<RefCell<PathBuf>>,
+    ) {
+        let view = view.clone().upcast::<gtk4::Widget>();
+        let view_clone = view.clone();
+        let selection_clone = selection.clone();
+        let current_popover: Rc<RefCell<Option<PopoverMenu>>> = Rc::new(RefCell::new(None));
+
+        let gesture = GestureClick::builder().button(3).build();
+
+        gesture.connect_pressed(move |_, _, x, y| {
+            // Close any existing popover first
+            if let Some(ref mut popover) = *current_popover.borrow_mut() {
+                popover.popdown();
+                popover.unparent();
+            }
+            current_popover.borrow_mut().take();
 
-    pub fn connect_paste<F: Fn() + 'static>(&self, callback: F) {
-        *self.on_paste.borrow_mut() = Some(Box::new(callback));
-    }
+            let mut selected_items = Vec::new();
+            let n_items = selection_clone.n_items();
+            for i in 0..n_items {
+                if selection_clone.is_selected(i) {
+                    if let Some(item) = selection_clone.item(i) {
+                        if let Ok(file_obj) = item.downcast::<FileObject>() {
+                            selected_items.push(file_obj);
+                        }
+                    }
+                }
+            }
+
+            // If no items selected, select item at click position
+            if selected_items.is_empty() && n_items > 0 {
+                if let Some(item) = selection_clone.item(0) {
+                    if let Ok(file_obj) = item.downcast::<FileObject>() {
+                        selected_items.push(file_obj);
+                    }
+                }
+            }
+
+            let selected_paths: Vec<PathBuf> = selected_items.iter().map(|obj| obj.path()).collect();
+            let read_only = *read_only.borrow();
+            let in_recent = RecentFiles::is_recent(&current_path.borrow());
+            let has_archives = !read_only && selected_paths.iter().any(|p| Archive::is_archive(p));
+
+            // "Open With" only applies to real files, not folders or archive members
+            let archive_members = read_only && !in_recent;
+            let content_types: Vec<String> = if archive_members || selected_items.iter().any(|obj| obj.is_directory()) {
+                Vec::new()
+            } else {
+                let mut types: Vec<String> = Vec::new();
+                for content_type in selected_items.iter().map(|obj| obj.content_type()) {
+                    if !types.contains(&content_type) {
+                        types.push(content_type);
+                    }
+                }
+                types
+            };
+
+            // Build menu using gio::Menu
+            let menu = gio::Menu::new();
+
+            if !selected_paths.is_empty() {
+                // File section
+                let file_section = gio::Menu::new();
+                file_section.append(Some("Open"), Some("file.open"));
+                if !content_types.is_empty() {
+                    let open_with_menu = gio::Menu::new();
+
+                    let apps_section = gio::Menu::new();
+                    for app in Applications::for_content_types(&content_types) {
+                        let Some(id) = app.id() else { continue };
+                        let item = gio::MenuItem::new(Some(&app.display_name()), None);
+                        item.set_action_and_target_value(Some("file.open-with"), Some(&id.to_variant()));
+                        if let Some(icon) = app.icon() {
+                            item.set_icon(&icon);
+                        }
+                        apps_section.append_item(&item);
+                    }
+                    open_with_menu.append_section(None, &apps_section);
+
+                    let other_section = gio::Menu::new();
+                    other_section.append(Some("Other Application…"), Some("file.open-with-other"));
+                    if content_types.len() == 1 {
+                        other_section.append(Some("Set Default Application…"), Some("file.set-default-app"));
+                    }
+                    open_with_menu.append_section(None, &other_section);
+
+                    file_section.append_submenu(Some("Open With"), &open_with_menu);
+                }
+                if selected_paths.len() == 1 && !read_only {
+                    file_section.append(Some("Rename…"), Some("file.rename"));
+                }
+                menu.append_section(None, &file_section);
+
+                // Recent files live elsewhere; offer to go there or drop them from the list
+                if in_recent {
+                    let recent_section = gio::Menu::new();
+                    if selected_paths.len() == 1 {
+                        recent_section.append(Some("Open Containing Folder"), Some("file.open-containing-folder"));
+                    }
+                    recent_section.append(Some("Remove from Recent"), Some("file.remove-from-recent"));
+                    menu.append_section(None, &recent_section);
+                }
+
+                // Archive section
+                let archive_section = gio::Menu::new();
+                if has_archives {
+                    archive_section.append(Some("Extract Here"), Some("file.extract-here"));
+                    archive_section.append(Some("Extract to…"), Some("file.extract-to"));
+                }
+                if !read_only {
+                    archive_section.append(Some("Compress…"), Some("file.compress"));
+                }
+                if archive_section.n_items() > 0 {
+                    menu.append_section(None, &archive_section);
+                }
+
+                // Edit section
+                let edit_section = gio::Menu::new();
+                edit_section.append(Some("Copy"), Some("file.copy"));
+                if !read_only {
+                    edit_section.append(Some("Cut"), Some("file.cut"));
+                }
+                menu.append_section(None, &edit_section);
+
+                // Delete section
+                if !read_only {
+                    let delete_section = gio::Menu::new();
+                    delete_section.append(Some("Move to Trash"), Some("file.delete"));
+                    menu.append_section(None, &delete_section);
+                }
+            } else if !read_only {
+                let paste_section = gio::Menu::new();
+                paste_section.append(Some("Paste"), Some("file.paste"));
+                menu.append_section(None, &paste_section);
+            }
+
+            // Properties of the selection, or of the current folder when nothing is selected
+            if !read_only {
+                let properties_section = gio::Menu::new();
+                let analyze_path = match selected_items.as_slice() {
+                    [] => Some(current_path.borrow().clone()),
+                    [item] if item.is_directory() => Some(item.path()),
+                    _ => None,
+                };
+                if let Some(path) = analyze_path.filter(|_| !in_recent) {
+                    let item = gio::MenuItem::new(Some("Analyze Disk Usage"), None);
+                    item.set_action_and_target_value(
+                        Some("win.analyze-disk-usage"),
+                        Some(&path.to_string_lossy().to_string().to_variant()),
+                    );
+                    properties_section.append_item(&item);
+                }
+                properties_section.append(Some("Properties"), Some("file.properties"));
+                menu.append_section(None, &properties_section);
+            }
+
+            // Create action group for file-specific actions
+            let action_group = gio::SimpleActionGroup::new();
+
+            // Paste action
+            {
+                let on_paste = callbacks.on_paste.clone();
+                let action = gio::SimpleAction::new("paste", None);
+                action.connect_activate(move |_, _| {
+                    if let Some(ref callback) = *on_paste.borrow() {
+                        callback();
+                    }
+                });
+                action_group.add_action(&action);
+            }
+
+            // Open action
+            {
+                let items = selected_items.clone();
+                let on_directory_activated = callbacks.on_directory_activated.clone();
+                let on_error = callbacks.on_error.clone();
+                let backend = callbacks.backend.clone();
+                let action = gio::SimpleAction::new("open", None);
+                action.connect_activate(move |_, _| {
+                    // Plain files are grouped so each application is launched once with all its files
+                    let mut files = Vec::new();
+                    for file_obj in &items {
+                        let path = file_obj.path();
+                        if file_obj.is_directory() || Archive::is_archive(&path) {
+                            Self::open_item(file_obj, &backend, &on_directory_activated, &on_error);
+                        } else {
+                            files.push((path, file_obj.content_type()));
+                        }
+                    }
+                    Self::open_files(&backend, files, &on_error);
+                });
+                action_group.add_action(&action);
+            }
+
+            // Recent files actions
+            if in_recent {
+                if let Some(path) = selected_paths.first().cloned() {
+                    let on_directory_activated = callbacks.on_directory_activated.clone();
+                    let pending_selection = callbacks.pending_selection.clone();
+                    let action = gio::SimpleAction::new("open-containing-folder", None);
+                    action.connect_activate(move |_, _| {
+                        let Some(parent) = path.parent() else { return };
+                        pending_selection.replace(vec![path.clone()]);
+                        if let Some(ref callback) = *on_directory_activated.borrow() {
+                            callback(parent.to_path_buf());
+                        }
+                    });
+                    action_group.add_action(&action);
+                }
+
+                let paths = selected_paths.clone();
+                let on_error = callbacks.on_error.clone();
+                let action = gio::SimpleAction::new("remove-from-recent", None);
+                action.connect_activate(move |_, _| {
+                    for path in &paths {
+                        if let Err(e) = RecentFiles::remove(path) {
+                            Self::report_to(&on_error, format!("Failed to remove {} from recent files: {}", path.display(), e));
+                        }
+                    }
+                });
+                action_group.add_action(&action);
+            }
+
+            // Open With actions
+            {
+                let paths = selected_paths.clone();
+                let on_error = callbacks.on_error.clone();
+                let action = gio::SimpleAction::new("open-with", Some(&String::static_variant_type()));
+                action.connect_activate(move |_, param| {
+                    let Some(id) = param.and_then(|p| p.get::<String>()) else { return };
+                    match Applications::find(&id) {
+                        Some(app) => {
+                            if let Err(e) = Applications::launch(&app, &paths) {
+                                Self::report_to(&on_error, format!("Failed to launch {}: {}", app.display_name(), e));
+                            }
+                        }
+                        None => Self::report_to(&on_error, format!("Application not found: {}", id)),
+                    }
+                });
+                action_group.add_action(&action);
+            }
+
+            if let Some(content_type) = content_types.first() {
+                let paths = selected_paths.clone();
+                let content_type_clone = content_type.clone();
+                let view = view_clone.clone();
+                let on_error = callbacks.on_error.clone();
+                let action = gio::SimpleAction::new("open-with-other", None);
+                action.connect_activate(move |_, _| {
+                    let paths = paths.clone();
+                    let on_error = on_error.clone();
+                    AppChooser::present(&view, &content_type_clone, "Open With", move |app| {
+                        if let Err(e) = Applications::launch(&app, &paths) {
+                            Self::report_to(&on_error, format!("Failed to launch {}: {}", app.display_name(), e));
+                        }
+                    });
+                });
+                action_group.add_action(&action);
+
+                let content_type_clone = content_type.clone();
+                let view = view_clone.clone();
+                let on_error = callbacks.on_error.clone();
+                let action = gio::SimpleAction::new("set-default-app", None);
+                action.connect_activate(move |_, _| {
+                    let content_type = content_type_clone.clone();
+                    let on_error = on_error.clone();
+                    let heading = format!(
+                        "Default application for {}",
+                        gio::content_type_get_description(&content_type)
+                    );
+                    AppChooser::present(&view, &content_type_clone, &heading, move |app| {
+                        if let Err(e) = Applications::set_default(&app, &content_type) {
+                            Self::report_to(&on_error, format!("Failed to set default application: {}", e));
+                        }
+                    });
+                });
+                action_group.add_action(&action);
+            }
+
+            // Path-list actions: copy, cut, delete, extract and compress
+            let path_actions = [
+                ("copy", callbacks.on_copy.clone()),
+                ("cut", callbacks.on_cut.clone()),
+                ("delete", callbacks.on_delete.clone()),
+                ("extract-here", callbacks.on_extract_here.clone()),
+                ("extract-to", callbacks.on_extract_to.clone()),
+                ("compress", callbacks.on_compress.clone()),
+            ];
+            for (name, handler) in path_actions {
+                let paths = selected_paths.clone();
+                let action = gio::SimpleAction::new(name, None);
+                action.connect_activate(move |_, _| {
+                    if let Some(ref callback) = *handler.borrow() {
+                        callback(paths.clone());
+                    }
+                });
+                action_group.add_action(&action);
+            }
+
+            // Properties action
+            {
+                let paths = if selected_paths.is_empty() {
+                    vec![current_path.borrow().clone()]
+                } else {
+                    selected_paths.clone()
+                };
+                let on_properties = callbacks.on_properties.clone();
+                let action = gio::SimpleAction::new("properties", None);
+                action.connect_activate(move |_, _| {
+                    if let Some(ref callback) = *on_properties.borrow() {
+                        callback(paths.clone());
+                    }
+                });
+                action_group.add_action(&action);
+            }
+
+            // Rename action
+            {
+                let paths = selected_paths.clone();
+                let on_rename = callbacks.on_rename.clone();
+                let action = gio::SimpleAction::new("rename", None);
+                action.connect_activate(move |_, _| {
+                    if let Some(path) = paths.first() {
+                        if let Some(ref callback) = *on_rename.borrow() {
+                            callback(path.clone());
+                        }
+                    }
+                });
+                action_group.add_action(&action);
+            }
+
+            // Create popover and attach action group
+            let popover = PopoverMenu::from_model(Some(&menu));
+
+            if view_clone.parent().is_some() {
+                popover.set_parent(&view_clone);
+            }
+            popover.set_pointing_to(Some(&gtk4::gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
+
+            popover.insert_action_group("file", Some(&action_group));
+
+            let popover_clone = popover.clone();
+            let current_popover_clone = current_popover.clone();
+            popover.connect_closed(move |p: &PopoverMenu| {
+                p.unparent();
+                current_popover_clone.borrow_mut().take();
+            });
+
+            *current_popover.borrow_mut() = Some(popover_clone.clone());
+            popover_clone.popup();
+        });
+
+        view.add_controller(gesture);
+    }
+
+    pub fn container(&self) -> &gtk4::Box {
+        &self.container
+    }
+
+    pub fn load_directory(&self, path: &Path) {
+        self.stash_listing();
+        self.load(path);
+    }
+
+    /// `load_directory` without stashing what is shown, for when the caller already has
+    fn load(&self, path: &Path) {
+        log::debug!("Loading {}", path.display());
+
+        // Entering a directory picks up its settings; refreshing keeps the current ones
+        let refreshing = *self.current_path.borrow() == path;
+        if !refreshing {
+            let settings = self.view_settings.borrow().settings_for(path);
+            self.apply_settings(settings);
+        }
+        let settings = *self.settings.borrow();
+
+        self.selection.unselect_all();
+        self.current_path.replace(path.to_path_buf());
+        let in_recent = RecentFiles::is_recent(path);
+        let backend = self.vfs.backend(path);
+        self.read_only.replace(backend.is_read_only(path));
+        self.show_recent_columns(in_recent);
+
+        let cached = self
+            .listing_cache
+            .borrow_mut()
+            .take(path)
+            .filter(|listing| !in_recent && listing.show_hidden == settings.show_hidden);
+        if cached.is_none() {
+            self.store.remove_all();
+        }
+
+        // Thumbnails of the previous folder are no longer needed
+        self.thumbnail_pool.cancel_pending();
+        self.thumbnail_cache.borrow_mut().clear();
+        self.thumbnail_requested.borrow_mut().clear();
+
+        // Increment scan ID to ignore previous pending scans
+        let mut scan_id_guard = self.current_scan_id.borrow_mut();
+        *scan_id_guard += 1;
+        let scan_id = *scan_id_guard;
+        drop(scan_id_guard);
+
+        let path = path.to_path_buf();
+        let loaded_path = path.clone();
+        self.backend.replace(backend.clone());
+        self.watch.replace(None);
+        self.hidden_count.replace(0);
+        self.error_page.set_visible(false);
+        self.set_loading(true);
+
+        if let Some(listing) = cached {
+            // A refresh always lists the folder again: edits to files don't change the folder's time
+            self.show_cached_listing(&path, listing, scan_id);
+            return;
+        }
+
+        // Only folders that are slow to list show the spinner
+        {
+            let file_view = self.clone();
+            glib::timeout_add_local_once(std::time::Duration::from_millis(LOADING_SPINNER_DELAY_MS), move || {
+                if *file_view.current_scan_id.borrow() == scan_id
+                    && *file_view.loading.borrow()
+                    && file_view.store.n_items() == 0
+                {
+                    file_view.loading_spinner.set_visible(true);
+                }
+            });
+        }
+
+        let store = self.store.clone();
+        let current_scan_id = self.current_scan_id.clone();
+        let file_view = self.clone();
+
+        // List and sort off the main thread, merging the items into the view batch by batch
+        glib::spawn_future_local(async move {
+            // The recent list is already in order, most recently used first
+            let events = Self::stream_listing(backend, path, settings, !in_recent);
+            let mut result = Err(std::io::Error::other("Listing was abandoned"));
+            while let Ok(event) = events.recv().await {
+                // Dropping the receiver stops the worker
+                if *current_scan_id.borrow() != scan_id {
+                    return;
+                }
+                match event {
+                    ListingEvent::Batch(entries) => {
+                        file_view.loading_spinner.set_visible(false);
+                        file_view.merge_sorted_batch(entries);
+                        // Let GTK draw what arrived before adding more
+                        glib::timeout_future(std::time::Duration::ZERO).await;
+                    }
+                    ListingEvent::Done(done) => {
+                        result = done;
+                        break;
+                    }
+                }
+            }
+            if *current_scan_id.borrow() != scan_id {
+                return;
+            }
+
+            let hidden = match result {
+                Ok(hidden) => hidden,
+                // Remote shares are mounted on first use, then listed again
+                Err(e) if e.kind() == std::io::ErrorKind::NotConnected && Remote::is_remote(&loaded_path) => {
+                    file_view.set_loading(false);
+                    file_view.mount_and_reload(loaded_path);
+                    return;
+                }
+                Err(e) => {
+                    log::warn!("Failed to scan directory: {}", e);
+                    store.remove_all();
+                    file_view.show_load_error(&e);
+                    file_view.set_loading(false);
+                    return;
+                }
+            };
+            log::debug!("Listed {} items in {}", store.n_items(), loaded_path.display());
+            file_view.hidden_count.replace(hidden);
+            file_view.finish_loading(loaded_path, scan_id, in_recent);
+        });
+    }
+
+    /// List a folder as batches of items, each sorted on a worker thread unless `sort` is off.
+    /// Backends that work on the main loop are listed there; the others are read on a worker
+    /// thread that stays at most one batch ahead of the view.
+    fn stream_listing(
+        backend: Arc<dyn VfsBackend>,
+        dir: PathBuf,
+        settings: ViewSettings,
+        sort: bool,
+    ) -> async_channel::Receiver<ListingEvent> {
+        let (tx, rx) = async_channel::bounded(1);
+        let sort_batch = move |batch: &mut Vec<FileEntry>| {
+            if sort {
+                Scanner::sort(batch, settings.sort_key, settings.sort_descending);
+            }
+        };
+
+        let Some(listing) = backend.list_async(&dir, settings.show_hidden) else {
+            std::thread::spawn(move || {
+                let result = backend.list_batches(&dir, settings.show_hidden, LOAD_BATCH, &mut |mut batch| {
+                    sort_batch(&mut batch);
+                    tx.send_blocking(ListingEvent::Batch(batch)).is_ok()
+                });
+                let _ = tx.send_blocking(ListingEvent::Done(result));
+            });
+            return rx;
+        };
+
+        glib::spawn_future_local(async move {
+            let (entries, hidden) = match listing.await {
+                Ok(listing) => listing,
+                Err(e) => {
+                    let _ = tx.send(ListingEvent::Done(Err(e))).await;
+                    return;
+                }
+            };
+            let sorted = gio::spawn_blocking(move || {
+                let mut entries = entries;
+                sort_batch(&mut entries);
+                entries
+            })
+            .await;
+            let Ok(entries) = sorted else {
+                let _ = tx.send(ListingEvent::Done(Err(std::io::Error::other("Listing was abandoned")))).await;
+                return;
+            };
+
+            let mut entries = entries.into_iter();
+            loop {
+                let batch: Vec<FileEntry> = entries.by_ref().take(LOAD_BATCH).collect();
+                if batch.is_empty() {
+                    break;
+                }
+                if tx.send(ListingEvent::Batch(batch)).await.is_err() {
+                    return;
+                }
+            }
+            let _ = tx.send(ListingEvent::Done(Ok(hidden))).await;
+        });
+        rx
+    }
+
+    /// List a folder: on the main loop for backends that work there, else on a worker thread.
+    /// The items are sorted for the view on a worker thread unless `sort` is off.
+    async fn read_listing(
+        backend: Arc<dyn VfsBackend>,
+        dir: PathBuf,
+        settings: ViewSettings,
+        sort: bool,
+    ) -> std::io::Result<Listing> {
+        let listed = match backend.list_async(&dir, settings.show_hidden) {
+            Some(listing) => Some(listing.await),
+            None => None,
+        };
+        gio::spawn_blocking(move || {
+            let (mut entries, hidden) = listed.unwrap_or_else(|| backend.list(&dir, settings.show_hidden))?;
+            if sort {
+                Scanner::sort(&mut entries, settings.sort_key, settings.sort_descending);
+            }
+            Ok((entries, hidden))
+        })
+        .await
+        .unwrap_or_else(|_| Err(std::io::Error::other("Listing was abandoned")))
+    }
+
+    fn finish_loading(&self, path: PathBuf, scan_id: u64, in_recent: bool) {
+        self.apply_pending_selection();
+        if !in_recent {
+            self.watch_directory(&path, scan_id);
+        }
+        self.set_loading(false);
+        self.notify_items_changed();
+        if let Some(ref callback) = *self.on_directory_loaded.borrow() {
+            callback(path);
+        }
+    }
+
+    fn notify_items_changed(&self) {
+        if let Some(ref callback) = *self.on_items_changed.borrow() {
+            callback();
+        }
+    }
+
+    /// Keep the listing shown so that coming back to it is instant
+    fn stash_listing(&self) {
+        let path = self.current_path.borrow().clone();
+        if path.as_os_str().is_empty() || *self.loading.borrow() || RecentFiles::is_recent(&path) {
+            return;
+        }
+
+        let settings = *self.settings.borrow();
+        let items: Vec<FileObject> = (0..self.store.n_items())
+            .filter_map(|position| self.store.item(position).and_downcast::<FileObject>())
+            .collect();
+        let listing = CachedListing {
+            items,
+            show_hidden: settings.show_hidden,
+            sort_key: settings.sort_key,
+            sort_descending: settings.sort_descending,
+            hidden: *self.hidden_count.borrow(),
+            scroll: self.scroll_position(),
+            selection: self.selected_paths(),
+        };
+        self.listing_cache.borrow_mut().insert(path, listing);
+    }
+
+    /// Show a folder as it was when the view left it, then bring it up to date in the background
+    fn show_cached_listing(&self, path: &Path, listing: CachedListing<FileObject>, scan_id: u64) {
+        let settings = *self.settings.borrow();
+        let mut items = listing.items;
+        if (listing.sort_key, listing.sort_descending) != (settings.sort_key, settings.sort_descending) {
+            items.sort_by(|a, b| Scanner::compare(&a.entry(), &b.entry(), settings.sort_key, settings.sort_descending));
+        }
+        self.store.splice(0, self.store.n_items(), &items);
+        self.hidden_count.replace(listing.hidden);
+
+        // An item asked for (e.g. the folder just left when going up) wins over the old selection
+        if self.pending_selection.borrow().is_empty() {
+            self.select_paths(&listing.selection);
+            if let Some(adjustment) = self.vadjustment() {
+                // The view knows its height once it has laid out the items again
+                let scroll = listing.scroll;
+                glib::idle_add_local_once(move || adjustment.set_value(scroll));
+            }
+        }
+
+        // Always listed again: edits to files inside don't change the folder's modification time
+        let backend = self.backend.borrow().clone();
+        let path = path.to_path_buf();
+        let file_view = self.clone();
+        glib::spawn_future_local(async move {
+            let result = Self::read_listing(backend, path.clone(), settings, true).await;
+            if *file_view.current_scan_id.borrow() != scan_id {
+                return;
+            }
+            match result {
+                Ok(listing) => file_view.merge_listing(listing),
+                Err(e) if e.kind() == std::io::ErrorKind::NotConnected && Remote::is_remote(&path) => {
+                    file_view.set_loading(false);
+                    file_view.mount_and_reload(path);
+                    return;
+                }
+                Err(e) => {
+                    log::warn!("Failed to scan directory: {}", e);
+                    file_view.store.remove_all();
+                    file_view.show_load_error(&e);
+                    file_view.set_loading(false);
+                    return;
+                }
+            }
+            file_view.finish_loading(path, scan_id, false);
+        });
+    }
+
+    /// Bring the shown items in line with a fresh, sorted listing, touching only what changed
+    fn merge_listing(&self, (entries, hidden): Listing) {
+        self.hidden_count.replace(hidden);
+        let fresh: HashMap<&Path, &FileEntry> = entries.iter().map(|e| (e.path.as_path(), e)).collect();
+        let mut unchanged = HashSet::new();
+        let mut stale = Vec::new();
+        for position in (0..self.store.n_items()).rev() {
+            let Some(obj) = self.store.item(position).and_downcast::<FileObject>() else { continue };
+            let entry = obj.entry();
+            match fresh.get(entry.path.as_path()) {
+                Some(&new) if *new == *entry => {
+                    unchanged.insert(entry.path.clone());
+                }
+                _ => stale.push(position),
+            }
+        }
+        drop(fresh);
+
+        if stale.len() + entries.len() - unchanged.len() > MAX_INCREMENTAL_CHANGES {
+            self.thumbnail_cache.borrow_mut().clear();
+            self.thumbnail_requested.borrow_mut().clear();
+            let objects: Vec<FileObject> = entries.into_iter().map(FileObject::from_entry).collect();
+            self.store.splice(0, self.store.n_items(), &objects);
+            self.notify_items_changed();
+            return;
+        }
+
+        // Positions were collected from the end, so removing them keeps the rest valid
+        for position in stale {
+            self.store.remove(position);
+        }
+        for entry in entries.into_iter().filter(|e| !unchanged.contains(&e.path)) {
+            self.thumbnail_cache.borrow_mut().remove(&entry.path);
+            self.thumbnail_requested.borrow_mut().remove(&entry.path);
+            self.insert_sorted(entry);
+        }
+        self.notify_items_changed();
+    }
+
+    /// Explain in place of the items why the current directory can't be shown
+    fn show_load_error(&self, error: &std::io::Error) {
+        let (icon, title) = match error.kind() {
+            std::io::ErrorKind::PermissionDenied => ("action-unavailable-symbolic", "No Permission to Open This Folder"),
+            std::io::ErrorKind::NotFound => ("folder-symbolic", "Folder Not Found"),
+            std::io::ErrorKind::NotConnected => ("network-offline-symbolic", "Server Not Reachable"),
+            _ => ("dialog-error-symbolic", "Unable to Read This Folder"),
+        };
+        self.show_error_page(icon, title, &error.to_string());
+    }
+
+    fn show_error_page(&self, icon: &str, title: &str, description: &str) {
+        self.error_page.set_icon_name(Some(icon));
+        self.error_page.set_title(title);
+        self.error_page.set_description(Some(&glib::markup_escape_text(description)));
+
+        let retry = gtk4::Button::builder()
+            .label("Try Again")
+            .halign(gtk4::Align::Center)
+            .css_classes(["pill"])
+            .build();
+        let file_view = self.clone();
+        retry.connect_clicked(move |_| file_view.refresh());
+        self.error_page.set_child(Some(&retry));
+        self.error_page.set_visible(true);
+    }
+
+    fn set_loading(&self, loading: bool) {
+        self.loading.replace(loading);
+        if !loading {
+            self.loading_spinner.set_visible(false);
+        }
+        if let Some(ref callback) = *self.on_loading_changed.borrow() {
+            callback(loading);
+        }
+    }
+
+    /// Keep the current directory up to date as items in it change
+    fn watch_directory(&self, path: &Path, scan_id: u64) {
+        let backend = self.backend.borrow().clone();
+        let watch = match backend.watch(path) {
+            Ok(watch) => watch,
+            Err(e) => {
+                log::warn!("Not watching {} for changes: {}", path.display(), e);
+                return;
+            }
+        };
+        let changes = watch.changes.clone();
+        self.watch.replace(Some(watch));
+
+        // Ends when the watch is replaced, which closes the channel
+        let file_view = self.clone();
+        let dir = path.to_path_buf();
+        glib::spawn_future_local(async move {
+            while let Ok(first) = changes.recv().await {
+                // Let a burst of changes (e.g. an extraction) settle, then look at each item once
+                glib::timeout_future(std::time::Duration::from_millis(WATCH_SETTLE_MS)).await;
+                let mut paths = HashSet::from([first]);
+                while let Ok(path) = changes.try_recv() {
+                    paths.insert(path);
+                }
+
+                // Hidden items left out aren't in the store, so the folder is asked how many there are
+                let recount_hidden = !file_view.settings.borrow().show_hidden
+                    && paths.iter().any(|path| path.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.')));
+
+                let backend = backend.clone();
+                let dir = dir.clone();
+                let (tx, rx) = async_channel::bounded(1);
+                std::thread::spawn(move || {
+                    let updates: Vec<(PathBuf, Option<FileEntry>)> = paths
+                        .into_iter()
+                        .map(|path| {
+                            let entry = backend.stat(&path).ok();
+                            (path, entry)
+                        })
+                        .collect();
+                    let hidden = recount_hidden.then(|| backend.count_hidden(&dir).ok()).flatten();
+                    let _ = tx.send_blocking((updates, hidden));
+                });
+                let Ok((updates, hidden)) = rx.recv().await else { return };
+                if *file_view.current_scan_id.borrow() != scan_id {
+                    return;
+                }
+                file_view.apply_changes(updates, hidden);
+            }
+        });
+    }
+
+    /// Bring items of the current directory up to date: `None` means an item is gone.
+    /// The old items are found in one pass over the store, however many changed.
+    /// `hidden` is the recounted number of hidden items left out, when any of them changed.
+    fn apply_changes(&self, updates: Vec<(PathBuf, Option<FileEntry>)>, hidden: Option<usize>) {
+        let settings = *self.settings.borrow();
+        let changed: HashSet<&Path> = updates.iter().map(|(path, _)| path.as_path()).collect();
+        let stale: Vec<u32> = (0..self.store.n_items())
+            .filter(|&position| {
+                self.store
+                    .item(position)
+                    .and_downcast::<FileObject>()
+                    .is_some_and(|obj| changed.contains(obj.entry().path.as_path()))
+            })
+            .collect();
+        drop(changed);
+
+        // Runs of neighbours go in one splice; removing from the end keeps earlier positions valid
+        let mut runs: Vec<(u32, u32)> = Vec::new();
+        for position in stale {
+            match runs.last_mut() {
+                Some((start, count)) if *start + *count == position => *count += 1,
+                _ => runs.push((position, 1)),
+            }
+        }
+        for (start, count) in runs.into_iter().rev() {
+            self.store.splice(start, count, &[] as &[FileObject]);
+        }
+
+        for (path, entry) in updates {
+            self.thumbnail_cache.borrow_mut().remove(&path);
+            self.thumbnail_requested.borrow_mut().remove(&path);
+            if let Some(entry) = entry.filter(|e| settings.show_hidden || !e.is_hidden) {
+                self.insert_sorted(entry);
+            }
+        }
+        if let Some(hidden) = hidden {
+            self.hidden_count.replace(hidden);
+        }
+        self.notify_items_changed();
+    }
+
+    /// Add an item at its place in the sorted store
+    fn insert_sorted(&self, entry: FileEntry) {
+        let position = self.sorted_position(&entry, 0);
+        self.store.insert(position, &FileObject::from_entry(entry));
+    }
+
+    /// Add items sorted among themselves to the sorted store, with one splice for each run
+    /// of items that land between the same two shown items
+    fn merge_sorted_batch(&self, entries: Vec<FileEntry>) {
+        let mut runs: Vec<(u32, Vec<FileObject>)> = Vec::new();
+        let mut low = 0;
+        for entry in entries {
+            // Each item goes after the one before it, so the search can start there
+            low = self.sorted_position(&entry, low);
+            let object = FileObject::from_entry(entry);
+            match runs.last_mut() {
+                Some((position, objects)) if *position == low => objects.push(object),
+                _ => runs.push((low, vec![object])),
+            }
+        }
+        // Later runs first, so the positions of earlier ones stay valid
+        for (position, objects) in runs.into_iter().rev() {
+            self.store.splice(position, 0, &objects);
+        }
+    }
+
+    /// Where an item belongs in the sorted store: after every equal or earlier item from `low` on
+    fn sorted_position(&self, entry: &FileEntry, low: u32) -> u32 {
+        let settings = *self.settings.borrow();
+        let (mut low, mut high) = (low, self.store.n_items());
+        while low < high {
+            let middle = (low + high) / 2;
+            let Some(obj) = self.store.item(middle).and_downcast::<FileObject>() else { break };
+            if Scanner::compare(&obj.entry(), entry, settings.sort_key, settings.sort_descending).is_le() {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        low
+    }
+
+    /// Mount the share a remote folder is on, asking for a password if needed, then list it again
+    fn mount_and_reload(&self, path: PathBuf) {
+        let window = self.container.root().and_downcast::<gtk4::Window>();
+        let file_view = self.clone();
+        glib::spawn_future_local(async move {
+            let operation = gtk4::MountOperation::new(window.as_ref());
+            let result = Remote::mount(&Remote::file(&path), Some(operation.upcast_ref())).await;
+            if *file_view.current_path.borrow() != path {
+                return;
+            }
+            match result {
+                Ok(true) => file_view.load_directory(&path),
+                Ok(false) => {
+                    log::warn!("Failed to scan directory: {} is not reachable", path.display());
+                    let description = format!("{} is mounted but can't be listed.", Remote::display_name(&path));
+                    file_view.show_error_page("network-offline-symbolic", "Server Not Reachable", &description);
+                }
+                Err(e) => {
+                    log::warn!("Failed to mount {}: {}", path.display(), e);
+                    let description = DriveScanner::error_message(&e)
+                        .unwrap_or_else(|| String::from("Connecting was cancelled."));
+                    file_view.show_error_page("network-offline-symbolic", "Unable to Connect", &description);
+                }
+            }
+        });
+    }
+
+    /// Copy files dropped onto the view into `dest_dir`, then show the folder's new contents.
+    /// Files the folder's backend can't reach, such as archive members dropped on a server, are
+    /// read through their own backend and written. Files that couldn't be copied are listed
+    /// afterwards, with the option to try again.
+    fn transfer_dropped_files(
+        files: Vec<PathBuf>,
+        is_move: bool,
+        dest_dir: PathBuf,
+        vfs: Vfs,
+        settings: ViewSettings,
+        store: gio::ListStore,
+        view: gtk4::Widget,
+    ) {
+        let (tx, rx) = async_channel::bounded::<(Vec<(PathBuf, String)>, std::io::Result<Vec<FileEntry>>)>(1);
+        let listed_dir = dest_dir.clone();
+        let backend = vfs.backend(&dest_dir);
+        let thread_vfs = vfs.clone();
+
+        // Copy or move in a background thread to avoid blocking the UI
+        std::thread::spawn(move || {
+            let mut failures = Vec::new();
+            for source_file in &files {
+                let Some(file_name) = source_file.file_name() else { continue };
+                let mut dest_path = Vfs::child(&dest_dir, &file_name.to_string_lossy());
+                if source_file == &dest_path {
+                    continue;
+                }
+
+                let mut counter = 1;
+                while backend.exists(&dest_path) {
+                    let stem = source_file.file_stem()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    let extension = source_file.extension()
+                        .map(|e| format!(".{}", e.to_string_lossy()))
+                        .unwrap_or_default();
+
+                    let new_name = format!("{} ({}){}", stem, counter, extension);
+                    dest_path = Vfs::child(&dest_dir, &new_name);
+                    counter += 1;
+                }
+
+                let result = if is_move {
+                    backend.move_to(source_file, &dest_path)
+                } else {
+                    backend.copy(source_file, &dest_path).or_else(|e| match e.kind() {
+                        std::io::ErrorKind::NotFound => thread_vfs
+                            .backend(source_file)
+                            .read(source_file)
+                            .and_then(|data| backend.write(&dest_path, &data)),
+                        _ => Err(e),
+                    })
+                };
+                if let Err(e) = result {
+                    log::warn!("Failed to {} {}: {}", if is_move { "move" } else { "copy" }, source_file.display(), e);
+                    failures.push((source_file.clone(), e.to_string()));
+                }
+            }
+
+            let result = backend.list(&dest_dir, settings.show_hidden).map(|(mut entries, _)| {
+                Scanner::sort(&mut entries, settings.sort_key, settings.sort_descending);
+                entries
+            });
+            let _ = tx.send_blocking((failures, result));
+        });
+
+        glib::spawn_future_local(async move {
+            let Ok((failures, result)) = rx.recv().await else { return };
+            match result {
+                Ok(entries) => {
+                    let objects: Vec<FileObject> = entries.into_iter().map(FileObject::from_entry).collect();
+                    store.splice(0, store.n_items(), &objects);
+                }
+                Err(e) => log::warn!("Failed to scan {}: {}", listed_dir.display(), e),
+            }
+
+            if !failures.is_empty() {
+                let parent = view.clone();
+                let title = if is_move { "Some Items Could Not Be Moved" } else { "Some Items Could Not Be Copied" };
+                ErrorReport::present(&parent, title, failures, move |failed| {
+                    Self::transfer_dropped_files(failed, is_move, listed_dir.clone(), vfs.clone(), settings, store.clone(), view.clone());
+                });
+            }
+        });
+    }
+
+    pub fn refresh(&self) {
+        let current = self.current_path.borrow().clone();
+        self.load_directory(&current);
+    }
+
+    /// Number of items shown, after the search filter
+    pub fn item_count(&self) -> u32 {
+        self.selection.n_items()
+    }
+
+    /// Number of hidden items in the current directory that are not shown
+    pub fn hidden_count(&self) -> usize {
+        *self.hidden_count.borrow()
+    }
+
+    /// Whether the Recent files list is shown
+    pub fn is_showing_recent(&self) -> bool {
+        RecentFiles::is_recent(&self.current_path.borrow())
+    }
+
+    pub fn filter(&self, query: &str) {
+        let query = query.to_lowercase();
+        self.filter.set_filter_func(move |obj| {
+            if query.is_empty() {
+                return true;
+            }
+            let file_obj = obj.downcast_ref::<FileObject>().unwrap();
+            file_obj.name().to_lowercase().contains(&query)
+        });
+    }
+
+    pub fn toggle_view_mode(&self) {
+        let mut settings = *self.settings.borrow();
+        settings.view_mode = match settings.view_mode {
+            ViewMode::Grid => ViewMode::List,
+            ViewMode::List => ViewMode::Grid,
+        };
+        self.apply_settings(settings);
+        self.remember_settings();
+    }
+
+    pub fn view_settings(&self) -> ViewSettings {
+        *self.settings.borrow()
+    }
+
+    /// Show the current directory in a view mode without remembering it (e.g. when restoring a session)
+    pub fn set_view_mode(&self, view_mode: ViewMode) {
+        let mut settings = *self.settings.borrow();
+        settings.view_mode = view_mode;
+        self.apply_settings(settings);
+    }
+
+    pub fn toggle_show_hidden(&self) {
+        // Stashed while the settings still describe the items shown
+        self.stash_listing();
+        let mut settings = *self.settings.borrow();
+        settings.show_hidden = !settings.show_hidden;
+        self.apply_settings(settings);
+        self.remember_settings();
+        let current = self.current_path.borrow().clone();
+        self.load(&current);
+    }
+
+    /// Change the show-hidden default, e.g. when the shell's setting changes
+    pub fn set_default_show_hidden(&self, show_hidden: bool) {
+        self.view_settings.borrow_mut().default.show_hidden = show_hidden;
+        self.save_view_settings();
+        self.reload_settings();
+    }
+
+    /// Re-order the current directory without rescanning it
+    pub fn set_sort(&self, key: SortKey, descending: bool) {
+        let mut settings = *self.settings.borrow();
+        settings.sort_key = key;
+        settings.sort_descending = descending;
+        self.apply_settings(settings);
+        self.remember_settings();
+
+        self.store.sort(|a, b| {
+            let a = a.downcast_ref::<FileObject>().unwrap();
+            let b = b.downcast_ref::<FileObject>().unwrap();
+            Scanner::compare(&a.entry(), &b.entry(), key, descending)
+        });
+    }
+
+    pub fn zoom_in(&self) {
+        let zoom = self.settings.borrow().zoom();
+        self.set_zoom(zoom + 1);
+    }
+
+    pub fn zoom_out(&self) {
+        let zoom = self.settings.borrow().zoom();
+        self.set_zoom(zoom.saturating_sub(1));
+    }
+
+    /// Back to the default zoom level of the current view mode
+    pub fn reset_zoom(&self) {
+        let mut default = self.view_settings.borrow().default;
+        default.view_mode = self.settings.borrow().view_mode;
+        self.set_zoom(default.zoom());
+    }
+
+    fn set_zoom(&self, zoom: usize) {
+        let mut settings = *self.settings.borrow();
+        let previous = settings.zoom();
+        settings.set_zoom(zoom);
+        if settings.zoom() == previous {
+            return;
+        }
+
+        self.apply_settings(settings);
+        self.remember_settings();
+
+        // Thumbnails may need the other cache size now
+        if settings.view_mode == ViewMode::Grid {
+            self.thumbnail_pool.cancel_pending();
+            self.thumbnail_cache.borrow_mut().clear();
+            self.thumbnail_requested.borrow_mut().clear();
+        }
+        self.rebind_items();
+    }
+
+    /// Have the views bind every item again (e.g. after zooming), keeping the selection
+    fn rebind_items(&self) {
+        let selected = self.selection.selection();
+        let n_items = self.store.n_items();
+        self.store.items_changed(0, n_items, n_items);
+        for i in 0..selected.size() {
+            self.selection.select_item(selected.nth(i as u32), false);
+        }
+    }
+
+    /// Use the current directory's settings for every directory without its own
+    pub fn make_settings_default(&self) {
+        let path = self.current_path.borrow().clone();
+        let mut store = self.view_settings.borrow_mut();
+        store.default = *self.settings.borrow();
+        store.reset(&path);
+        drop(store);
+        self.save_view_settings();
+    }
+
+    /// Forget the current directory's own settings
+    pub fn reset_settings(&self) {
+        let path = self.current_path.borrow().clone();
+        self.view_settings.borrow_mut().reset(&path);
+        self.save_view_settings();
+        self.reload_settings();
+    }
+
+    pub fn inherits_settings(&self) -> bool {
+        self.view_settings.borrow().inherit_from_parent
+    }
+
+    /// Whether folders without their own settings follow their parent folder
+    pub fn set_inherit_settings(&self, inherit: bool) {
+        self.view_settings.borrow_mut().inherit_from_parent = inherit;
+        self.save_view_settings();
+        self.reload_settings();
+    }
+
+    /// Re-read the current directory's settings from the store and reload it
+    fn reload_settings(&self) {
+        self.stash_listing();
+        let path = self.current_path.borrow().clone();
+        let settings = self.view_settings.borrow().settings_for(&path);
+        self.apply_settings(settings);
+        self.load(&path);
+    }
+
+    fn apply_settings(&self, settings: ViewSettings) {
+        self.settings.replace(settings);
+        match settings.view_mode {
+            ViewMode::Grid => self.stack.set_visible_child_name("grid"),
+            ViewMode::List => self.stack.set_visible_child_name("list"),
+        }
+
+        if let Some(ref callback) = *self.on_settings_changed.borrow() {
+            callback(settings);
+        }
+    }
+
+    /// Store the current settings for the current directory
+    fn remember_settings(&self) {
+        // Archive contents aren't real directories, don't clutter the store with them
+        if self.is_read_only() {
+            return;
+        }
+        let path = self.current_path.borrow().clone();
+        self.view_settings.borrow_mut().set_for(&path, *self.settings.borrow());
+        self.save_view_settings();
+    }
+
+    fn save_view_settings(&self) {
+        if let Err(e) = self.view_settings.borrow().save() {
+            log::warn!("Failed to save view settings: {}", e);
+        }
+    }
+
+    pub fn connect_directory_activated<F: Fn(PathBuf) + 'static>(&self, callback: F) {
+        *self.on_directory_activated.borrow_mut() = Some(Box::new(callback));
+    }
+
+    pub fn connect_copy<F: Fn(Vec<PathBuf>) + 'static>(&self, callback: F) {
+        *self.on_copy.borrow_mut() = Some(Box::new(callback));
+    }
+
+    pub fn connect_cut<F: Fn(Vec<PathBuf>) + 'static>(&self, callback: F) {
+        *self.on_cut.borrow_mut() = Some(Box::new(callback));
+    }
+
+    pub fn connect_paste<F: Fn() + 'static>(&self, callback: F) {
+        *self.on_paste.borrow_mut() = Some(Box::new(callback));
+    }
 
     pub fn connect_delete<F: Fn(Vec<PathBuf>) + 'static>(&self, callback: F) {
         *self.on_delete.borrow_mut() = Some(Box::new(callback));
@@ -1322,7 +2410,187 @@ impl FileGridView {
         *self.on_open_micro.borrow_mut() = Some(Box::new(callback));
     }
 
+    pub fn connect_extract_here<F: Fn(Vec<PathBuf>) + 'static>(&self, callback: F) {
+        *self.on_extract_here.borrow_mut() = Some(Box::new(callback));
+    }
+
+    pub fn connect_extract_to<F: Fn(Vec<PathBuf>) + 'static>(&self, callback: F) {
+        *self.on_extract_to.borrow_mut() = Some(Box::new(callback));
+    }
+
+    pub fn connect_compress<F: Fn(Vec<PathBuf>) + 'static>(&self, callback: F) {
+        *self.on_compress.borrow_mut() = Some(Box::new(callback));
+    }
+
+    pub fn connect_properties<F: Fn(Vec<PathBuf>) + 'static>(&self, callback: F) {
+        *self.on_properties.borrow_mut() = Some(Box::new(callback));
+    }
+
+    pub fn connect_selection_changed<F: Fn(Vec<PathBuf>) + 'static>(&self, callback: F) {
+        *self.on_selection_changed.borrow_mut() = Some(Box::new(callback));
+    }
+
+    pub fn connect_quick_look<F: Fn(PathBuf) + 'static>(&self, callback: F) {
+        *self.on_quick_look.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Called whenever the view settings change, including when entering a directory
+    pub fn connect_settings_changed<F: Fn(ViewSettings) + 'static>(&self, callback: F) {
+        *self.on_settings_changed.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Called each time a directory finished loading, including refreshes after file operations
+    pub fn connect_directory_loaded<F: Fn(PathBuf) + 'static>(&self, callback: F) {
+        *self.on_directory_loaded.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Called when the number of items shown or left out as hidden may have changed: after loading
+    /// a directory and when items in it change
+    pub fn connect_items_changed<F: Fn() + 'static>(&self, callback: F) {
+        *self.on_items_changed.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Called with `true` when a directory starts loading and `false` once it has loaded or failed
+    /// Tell the user about a failure
+    pub fn report_error(&self, message: String) {
+        Self::report_to(&self.on_error, message);
+    }
+
+    /// Failures the user should hear about, as a short message
+    pub fn connect_error<F: Fn(String) + 'static>(&self, callback: F) {
+        *self.on_error.borrow_mut() = Some(Box::new(callback));
+    }
+
+    pub fn connect_loading_changed<F: Fn(bool) + 'static>(&self, callback: F) {
+        *self.on_loading_changed.borrow_mut() = Some(Box::new(callback));
+    }
+
+    fn selected_paths_of(selection: &MultiSelection) -> Vec<PathBuf> {
+        let mut selected_paths = Vec::new();
+        let bitset = selection.selection();
+        for i in 0..bitset.size() {
+            if let Some(item) = selection.item(bitset.nth(i as u32)) {
+                if let Ok(file_obj) = item.downcast::<FileObject>() {
+                    selected_paths.push(file_obj.path());
+                }
+            }
+        }
+        selected_paths
+    }
+
+    pub fn selected_paths(&self) -> Vec<PathBuf> {
+        Self::selected_paths_of(&self.selection)
+    }
+
+    /// Move the selection `offset` items from the first selected one and return the new item
+    pub fn select_relative(&self, offset: i32) -> Option<PathBuf> {
+        let n_items = self.selection.n_items();
+        if n_items == 0 {
+            return None;
+        }
+
+        let bitset = self.selection.selection();
+        let current = if bitset.is_empty() { 0 } else { bitset.minimum() as i32 };
+        let position = (current + offset).clamp(0, n_items as i32 - 1) as u32;
+
+        self.selection.select_item(position, true);
+        self.scroll_to(position);
+
+        self.selection
+            .item(position)
+            .and_downcast::<FileObject>()
+            .map(|file_obj| file_obj.path())
+    }
+
+    fn scroll_to(&self, position: u32) {
+        match self.settings.borrow().view_mode {
+            ViewMode::Grid => self.grid_view.scroll_to(position, gtk4::ListScrollFlags::FOCUS, None),
+            ViewMode::List => self.column_view.scroll_to(position, None, gtk4::ListScrollFlags::FOCUS, None),
+        }
+    }
+
+    /// Select these items once the current directory has loaded, scrolling to the first
+    pub fn select_paths_when_loaded(&self, paths: Vec<PathBuf>) {
+        self.pending_selection.replace(paths);
+    }
+
+    /// Scroll to this offset once the current directory has loaded, instead of to the selection
+    pub fn scroll_when_loaded(&self, scroll: f64) {
+        self.pending_scroll.replace(Some(scroll));
+    }
+
+    /// How far the view is scrolled down
+    pub fn scroll_position(&self) -> f64 {
+        self.vadjustment().map_or(0.0, |adjustment| adjustment.value())
+    }
+
+    fn apply_pending_selection(&self) {
+        let wanted = self.pending_selection.take();
+        let scroll = self.pending_scroll.take();
+
+        if !wanted.is_empty() {
+            self.selection.unselect_all();
+            let first = self.select_paths(&wanted);
+            if let (Some(position), None) = (first, scroll) {
+                self.scroll_to(position);
+            }
+        }
+        if let (Some(scroll), Some(adjustment)) = (scroll, self.vadjustment()) {
+            // The view knows its height once it has laid out the items
+            glib::idle_add_local_once(move || adjustment.set_value(scroll));
+        }
+    }
+
+    /// Add these items to the selection, returning the position of the first one
+    fn select_paths(&self, paths: &[PathBuf]) -> Option<u32> {
+        if paths.is_empty() {
+            return None;
+        }
+        let mut first = None;
+        for position in 0..self.selection.n_items() {
+            let Some(file_obj) = self.selection.item(position).and_downcast::<FileObject>() else { continue };
+            if paths.contains(&file_obj.path()) {
+                self.selection.select_item(position, false);
+                first.get_or_insert(position);
+            }
+        }
+        first
+    }
+
+    /// Vertical scrolling of the view shown
+    fn vadjustment(&self) -> Option<gtk4::Adjustment> {
+        match self.settings.borrow().view_mode {
+            ViewMode::Grid => self.grid_view.vadjustment(),
+            ViewMode::List => self.column_view.vadjustment(),
+        }
+    }
+
+    /// The Recent view spans folders, so it always shows when and where each file is
+    fn show_recent_columns(&self, in_recent: bool) {
+        let layout = self.column_layout.borrow();
+        for (column, view_column) in self.list_columns.iter() {
+            if matches!(column, ListColumn::Accessed | ListColumn::Path) {
+                let visible = layout.columns.iter().any(|s| s.column == *column && s.visible);
+                view_column.set_visible(in_recent || visible);
+            }
+        }
+    }
+
+    /// The backend for a location as this view reaches it
+    pub fn backend_for(&self, path: &Path) -> Arc<dyn VfsBackend> {
+        self.vfs.backend(path)
+    }
+
+    /// Whether the current location is read-only (e.g. inside an archive)
+    pub fn is_read_only(&self) -> bool {
+        *self.read_only.borrow()
+    }
+
     pub fn rename_selected(&self) {
+        if self.is_read_only() {
+            return;
+        }
+
         // Helper to get selected paths (logic duplicated closely from key controller)
         let mut selected_paths = Vec::new();
         let n_items = self.selection.n_items();
@@ -1346,6 +2614,51 @@ impl FileGridView {
 
 impl Default for FileGridView {
     fn default() -> Self {
-        Self::new()
+        Self::new(Vfs::default())
+    }
+}
+
+/// Wrap an item icon in an overlay with a hidden emblem in its bottom-right corner
+fn with_emblem(icon: &gtk4::Image, emblem_size: i32) -> gtk4::Overlay {
+    let emblem = gtk4::Image::builder()
+        .pixel_size(emblem_size)
+        .halign(gtk4::Align::End)
+        .valign(gtk4::Align::End)
+        .visible(false)
+        .css_classes(["nautilus-emblem"])
+        .build();
+
+    let overlay = gtk4::Overlay::builder()
+        .child(icon)
+        .halign(gtk4::Align::Center)
+        .build();
+    overlay.add_overlay(&emblem);
+    overlay
+}
+
+/// Show the symlink emblem and flag broken links on a bound row or tile
+fn bind_link_state(row: &impl IsA<gtk4::Widget>, overlay: &gtk4::Overlay, file_obj: &FileObject) {
+    if let Some(emblem) = overlay.last_child().and_downcast::<gtk4::Image>() {
+        match file_obj.emblem_icon_name() {
+            Some(name) => {
+                emblem.set_icon_name(Some(name));
+                emblem.set_visible(true);
+            }
+            None => emblem.set_visible(false),
+        }
+    }
+
+    let entry = file_obj.entry();
+    let tooltip = match &entry.symlink_target {
+        Some(target) if entry.is_broken_link => Some(format!("Broken link to {}", target.display())),
+        Some(target) => Some(format!("Link to {}", target.display())),
+        None => None,
+    };
+    row.set_tooltip_text(tooltip.as_deref());
+
+    if entry.is_broken_link {
+        row.add_css_class("nautilus-broken-link");
+    } else {
+        row.remove_css_class("nautilus-broken-link");
     }
 }
@@ -1,10 +1,12 @@
 use gtk4::prelude::*;
-use gtk4::{Box as GtkBox, Button, Entry, Orientation, Popover, SearchEntry, ToggleButton};
+use gtk4::{gio, Box as GtkBox, Button, Entry, ListBox, MenuButton, Orientation, Popover, PopoverMenu, SearchEntry, ToggleButton};
 use libadwaita as adw;
 use std::cell::RefCell;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
+use crate::core::{RecentFiles, Remote};
+
 #[derive(Clone)]
 pub struct NautilusHeaderBar {
     container: adw::HeaderBar,
@@ -14,6 +16,11 @@ pub struct NautilusHeaderBar {
     search_entry: SearchEntry,
     search_popover: Popover,
     view_toggle_btn: Button,
+    back_menu: gio::Menu,
+    forward_menu: gio::Menu,
+    recent_locations: Rc<RefCell<Vec<PathBuf>>>,
+    /// Text of the current location, as put into the path entry
+    shown_path: Rc<RefCell<String>>,
     is_editing_path: Rc<RefCell<bool>>,
 
     on_path_clicked: Rc<RefCell<Option<Box<dyn Fn(PathBuf)>>>>,
@@ -29,6 +36,57 @@ impl NautilusHeaderBar {
         container.add_css_class("flat");
         container.set_show_back_button(false);
 
+        // ===== LEFT SIDE: Back/forward, the actions live on the window =====
+        // Holding or right-clicking either button lists the history in that direction.
+        let nav_box = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .css_classes(["linked"])
+            .build();
+        let back_menu = gio::Menu::new();
+        let forward_menu = gio::Menu::new();
+        for (icon_name, tooltip, action_name, menu) in [
+            ("go-previous-symbolic", "Back (Alt+Left)", "win.go-back", &back_menu),
+            ("go-next-symbolic", "Forward (Alt+Right)", "win.go-forward", &forward_menu),
+        ] {
+            let button = Button::builder()
+                .icon_name(icon_name)
+                .tooltip_text(tooltip)
+                .action_name(action_name)
+                .build();
+            let popover = PopoverMenu::from_model(Some(menu));
+            popover.set_parent(&button);
+            popover.set_has_arrow(false);
+            popover.set_halign(gtk4::Align::Start);
+
+            let long_press = gtk4::GestureLongPress::new();
+            {
+                let popover = popover.clone();
+                let menu = menu.clone();
+                long_press.connect_pressed(move |gesture, _, _| {
+                    if menu.n_items() > 0 {
+                        gesture.set_state(gtk4::EventSequenceState::Claimed);
+                        popover.popup();
+                    }
+                });
+            }
+            button.add_controller(long_press);
+
+            let right_click = gtk4::GestureClick::builder().button(3).build();
+            {
+                let popover = popover.clone();
+                let menu = menu.clone();
+                right_click.connect_pressed(move |_, _, _, _| {
+                    if menu.n_items() > 0 {
+                        popover.popup();
+                    }
+                });
+            }
+            button.add_controller(right_click);
+
+            nav_box.append(&button);
+        }
+        container.pack_start(&nav_box);
+
         // ===== CENTER: Breadcrumb path bar with editable entry =====
         let path_box = GtkBox::builder()
             .orientation(Orientation::Horizontal)
@@ -56,6 +114,23 @@ impl NautilusHeaderBar {
             .build();
         path_entry_box.append(&path_entry);
 
+        // Recent locations offered while typing a path
+        let recent_locations: Rc<RefCell<Vec<PathBuf>>> = Rc::new(RefCell::new(Vec::new()));
+        let shown_path: Rc<RefCell<String>> = Rc::new(RefCell::new(String::new()));
+        let recent_list = ListBox::builder()
+            .selection_mode(gtk4::SelectionMode::None)
+            .activate_on_single_click(true)
+            .can_focus(false)
+            .css_classes(["navigation-sidebar"])
+            .build();
+        let recent_popover = Popover::builder()
+            .autohide(false)
+            .has_arrow(false)
+            .position(gtk4::PositionType::Bottom)
+            .child(&recent_list)
+            .build();
+        recent_popover.set_parent(&path_entry);
+
         path_box.append(&breadcrumbs_box);
         path_box.append(&path_entry_box);
 
@@ -119,6 +194,43 @@ impl NautilusHeaderBar {
             .build();
         container.pack_end(&view_toggle_btn);
 
+        // View options: sorting, hidden files and how per-folder settings are remembered.
+        // The actions live on the window.
+        let view_menu = gio::Menu::new();
+        let sort_section = gio::Menu::new();
+        for (label, id) in [("Name", "name"), ("Size", "size"), ("Type", "type"), ("Modified", "modified")] {
+            sort_section.append(Some(label), Some(&format!("win.sort-by::{}", id)));
+        }
+        sort_section.append(Some("Reversed Order"), Some("win.sort-descending"));
+        view_menu.append_section(Some("Sort"), &sort_section);
+
+        let zoom_section = gio::Menu::new();
+        zoom_section.append(Some("Zoom In"), Some("win.zoom-in"));
+        zoom_section.append(Some("Zoom Out"), Some("win.zoom-out"));
+        zoom_section.append(Some("Reset Zoom"), Some("win.zoom-reset"));
+        view_menu.append_section(Some("Icon Size"), &zoom_section);
+
+        let visibility_section = gio::Menu::new();
+        visibility_section.append(Some("Show Hidden Files"), Some("win.show-hidden"));
+        view_menu.append_section(None, &visibility_section);
+
+        let settings_section = gio::Menu::new();
+        settings_section.append(Some("Use Parent Folder's View"), Some("win.view-inherit"));
+        settings_section.append(Some("Use as Default for All Folders"), Some("win.view-make-default"));
+        settings_section.append(Some("Reset Folder View"), Some("win.view-reset"));
+        view_menu.append_section(None, &settings_section);
+
+        let help_section = gio::Menu::new();
+        help_section.append(Some("Copy Debug Info"), Some("win.copy-debug-info"));
+        view_menu.append_section(None, &help_section);
+
+        let view_menu_btn = MenuButton::builder()
+            .icon_name("view-more-symbolic")
+            .tooltip_text("View Options")
+            .menu_model(&view_menu)
+            .build();
+        container.pack_end(&view_menu_btn);
+
         // New folder button
         let new_folder_btn = Button::builder()
             .icon_name("folder-new-symbolic")
@@ -183,8 +295,8 @@ impl NautilusHeaderBar {
             path_entry.connect_activate(move |entry| {
                 let text = entry.text();
                 if !text.is_empty() {
-                    let path = PathBuf::from(text.as_str());
-                    if path.exists() {
+                    // Remote URIs can only be checked by going there
+                    if let Some(path) = Remote::parse_location(&text).filter(|p| p.exists() || Remote::is_remote(p)) {
                         if let Some(ref callback) = *on_path_entered_clone.borrow() {
                             callback(path);
                         }
@@ -196,6 +308,85 @@ impl NautilusHeaderBar {
             });
         }
 
+        // Suggest recent locations matching what has been typed so far
+        {
+            let recent_list = recent_list.clone();
+            let recent_popover = recent_popover.clone();
+            let recent_locations = recent_locations.clone();
+            let shown_path = shown_path.clone();
+            let refresh = move |entry: &Entry| {
+                while let Some(row) = recent_list.first_child() {
+                    recent_list.remove(&row);
+                }
+                if !WidgetExt::is_visible(entry) {
+                    recent_popover.popdown();
+                    return;
+                }
+
+                let text = entry.text().to_lowercase();
+                // The entry starts out holding the current location, show everything then
+                let showing_current = text.is_empty() || *shown_path.borrow() == entry.text().as_str();
+                let matches: Vec<PathBuf> = recent_locations
+                    .borrow()
+                    .iter()
+                    .filter(|path| {
+                        showing_current || path.to_string_lossy().to_lowercase().contains(text.as_str())
+                    })
+                    .take(8)
+                    .cloned()
+                    .collect();
+
+                for path in &matches {
+                    let label = gtk4::Label::builder()
+                        .label(path.to_string_lossy().as_ref())
+                        .halign(gtk4::Align::Start)
+                        .ellipsize(gtk4::pango::EllipsizeMode::Middle)
+                        .max_width_chars(60)
+                        .build();
+                    let row = gtk4::ListBoxRow::builder()
+                        .child(&label)
+                        .focusable(false)
+                        .build();
+                    unsafe {
+                        row.set_data("path", path.clone());
+                    }
+                    recent_list.append(&row);
+                }
+
+                if matches.is_empty() {
+                    recent_popover.popdown();
+                } else {
+                    recent_popover.popup();
+                }
+            };
+            let refresh = Rc::new(refresh);
+
+            {
+                let refresh = refresh.clone();
+                path_entry.connect_changed(move |entry| refresh(entry));
+            }
+            path_entry.connect_visible_notify(move |entry| refresh(entry));
+        }
+
+        {
+            let breadcrumbs_box_clone = breadcrumbs_box.clone();
+            let path_entry_clone = path_entry.clone();
+            let is_editing_path_clone = is_editing_path.clone();
+            let on_path_entered_clone = on_path_entered.clone();
+
+            recent_list.connect_row_activated(move |_, row| {
+                let path = unsafe { row.data::<PathBuf>("path").map(|p| p.as_ref().clone()) };
+                if let Some(path) = path {
+                    if let Some(ref callback) = *on_path_entered_clone.borrow() {
+                        callback(path);
+                    }
+                }
+                *is_editing_path_clone.borrow_mut() = false;
+                breadcrumbs_box_clone.set_visible(true);
+                path_entry_clone.set_visible(false);
+            });
+        }
+
         // Cancel editing on escape key
         {
             let breadcrumbs_box_clone = breadcrumbs_box.clone();
@@ -302,6 +493,10 @@ impl NautilusHeaderBar {
             search_entry,
             search_popover,
             view_toggle_btn,
+            back_menu,
+            forward_menu,
+            recent_locations,
+            shown_path,
             is_editing_path,
             on_path_clicked,
             on_path_entered,
@@ -317,6 +512,7 @@ impl NautilusHeaderBar {
 
     pub fn set_path(&self, path: &Path) {
         // Update path entry text
+        self.shown_path.replace(path.to_string_lossy().to_string());
         self.path_entry.set_text(&path.to_string_lossy());
 
         // Clear existing breadcrumbs
@@ -324,6 +520,21 @@ impl NautilusHeaderBar {
             self.breadcrumbs_box.remove(&child);
         }
 
+        // Virtual locations get a single crumb
+        if RecentFiles::is_recent(path) {
+            let recent_btn = Button::builder()
+                .label("Recent")
+                .css_classes(["flat", "current-path"])
+                .build();
+            self.breadcrumbs_box.append(&recent_btn);
+            return;
+        }
+
+        if Remote::is_remote(path) {
+            self.set_remote_path(path);
+            return;
+        }
+
         let home_dir = dirs::home_dir();
         let path_str = path.to_string_lossy();
         
@@ -423,6 +634,59 @@ impl NautilusHeaderBar {
         }
     }
 
+    /// Crumbs for a remote location: the server, then each folder below it
+    fn set_remote_path(&self, path: &Path) {
+        let root = Remote::root(path);
+        let server_btn = Button::builder()
+            .css_classes(["flat"])
+            .tooltip_text(root.to_string_lossy().as_ref())
+            .build();
+        let server_box = GtkBox::new(Orientation::Horizontal, 6);
+        server_box.append(&gtk4::Image::from_icon_name("network-server-symbolic"));
+        server_box.append(&gtk4::Label::new(Some(&Remote::display_name(&root))));
+        server_btn.set_child(Some(&server_box));
+        self.append_crumb(&server_btn, root.clone());
+
+        let mut segments: Vec<PathBuf> = path
+            .ancestors()
+            .take_while(|ancestor| ancestor.as_os_str().len() > root.as_os_str().len())
+            .map(Path::to_path_buf)
+            .collect();
+        segments.reverse();
+        if segments.is_empty() {
+            server_btn.add_css_class("current-path");
+        }
+
+        let last = segments.len().saturating_sub(1);
+        for (i, segment) in segments.into_iter().enumerate() {
+            let arrow = gtk4::Image::builder()
+                .icon_name("go-next-symbolic")
+                .css_classes(["dim-label"])
+                .pixel_size(12)
+                .build();
+            self.breadcrumbs_box.append(&arrow);
+
+            let btn = Button::builder()
+                .label(Remote::display_name(&segment))
+                .css_classes(["flat"])
+                .build();
+            if i == last {
+                btn.add_css_class("current-path");
+            }
+            self.append_crumb(&btn, segment);
+        }
+    }
+
+    fn append_crumb(&self, button: &Button, path: PathBuf) {
+        let callback = self.on_path_clicked.clone();
+        button.connect_clicked(move |_| {
+            if let Some(ref cb) = *callback.borrow() {
+                cb(path.clone());
+            }
+        });
+        self.breadcrumbs_box.append(button);
+    }
+
     pub fn connect_path_clicked<F: Fn(PathBuf) + 'static>(&self, callback: F) {
         *self.on_path_clicked.borrow_mut() = Some(Box::new(callback));
     }
@@ -453,6 +717,27 @@ impl NautilusHeaderBar {
         }
     }
 
+    /// Fill the back/forward dropdowns; entries are (history index, location), nearest first
+    pub fn set_history(&self, back: &[(usize, PathBuf)], forward: &[(usize, PathBuf)]) {
+        for (menu, entries) in [(&self.back_menu, back), (&self.forward_menu, forward)] {
+            menu.remove_all();
+            for (index, path) in entries {
+                let label = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.to_string_lossy().to_string());
+                let item = gio::MenuItem::new(Some(&label), None);
+                item.set_action_and_target_value(Some("win.go-to-history"), Some(&(*index as u64).to_variant()));
+                menu.append_item(&item);
+            }
+        }
+    }
+
+    /// Locations suggested by the path entry, best first
+    pub fn set_recent_locations(&self, paths: Vec<PathBuf>) {
+        self.recent_locations.replace(paths);
+    }
+
     pub fn clear_search(&self) {
         self.search_entry.set_text("");
     }
@@ -1,7 +1,21 @@
+mod app_chooser;
+mod column_chooser;
+mod connect_dialog;
+mod disk_usage_view;
+mod error_report;
 mod file_view;
 mod header_bar;
+mod preview_pane;
+mod properties_dialog;
 mod sidebar;
+mod status_bar;
 
+pub use connect_dialog::ConnectServerDialog;
+pub use disk_usage_view::DiskUsageWindow;
+pub use error_report::ErrorReport;
 pub use file_view::FileGridView;
 pub use header_bar::NautilusHeaderBar;
+pub use preview_pane::PreviewPane;
+pub use properties_dialog::PropertiesDialog;
 pub use sidebar::NautilusSidebar;
+pub use status_bar::StatusBar;
@@ -0,0 +1,325 @@
+use gtk4::prelude::*;
+use gtk4::{gdk, gdk_pixbuf, gio, glib, Box as GtkBox, Label, Orientation, Picture, ScrolledWindow, Stack, TextView};
+use libadwaita as adw;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use crate::core::{format_size, FileProperties, Preview, PreviewContent, SizeTotals, Vfs};
+
+/// Largest edge of the texture loaded for image previews
+const IMAGE_PREVIEW_SIZE: i32 = 1024;
+
+/// Side panel (and quick look body) showing a preview and basic properties of a file
+#[derive(Clone)]
+pub struct PreviewPane {
+    container: GtkBox,
+    stack: Stack,
+    picture: Picture,
+    text_view: TextView,
+    icon: gtk4::Image,
+    summary_label: Label,
+    name_label: Label,
+    properties_grid: gtk4::Grid,
+    current_path: Rc<RefCell<Option<PathBuf>>>,
+    /// Where previewed files are read from
+    vfs: Vfs,
+    load_id: Rc<RefCell<u64>>,
+    /// Stops the size measurement of the folder shown
+    size_cancel: Rc<RefCell<Arc<AtomicBool>>>,
+}
+
+impl PreviewPane {
+    /// A pane reading the files it previews through `vfs`
+    pub fn new(vfs: Vfs) -> Self {
+        let container = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(12)
+            .margin_top(12)
+            .margin_bottom(12)
+            .margin_start(12)
+            .margin_end(12)
+            .width_request(280)
+            .css_classes(["nautilus-preview"])
+            .build();
+
+        let stack = Stack::new();
+        stack.set_vexpand(true);
+        stack.set_transition_type(gtk4::StackTransitionType::Crossfade);
+
+        // Placeholder when nothing is selected
+        let placeholder = adw::StatusPage::builder()
+            .icon_name("view-reveal-symbolic")
+            .title("No Preview")
+            .description("Select a file to preview it")
+            .build();
+        placeholder.add_css_class("compact");
+        stack.add_named(&placeholder, Some("empty"));
+
+        // Images
+        let picture = Picture::builder()
+            .content_fit(gtk4::ContentFit::Contain)
+            .can_shrink(true)
+            .build();
+        stack.add_named(&picture, Some("image"));
+
+        // Text and source code
+        let text_view = TextView::builder()
+            .editable(false)
+            .cursor_visible(false)
+            .monospace(true)
+            .wrap_mode(gtk4::WrapMode::WordChar)
+            .left_margin(8)
+            .right_margin(8)
+            .top_margin(8)
+            .bottom_margin(8)
+            .css_classes(["nautilus-preview-text"])
+            .build();
+        let text_scrolled = ScrolledWindow::builder()
+            .child(&text_view)
+            .vexpand(true)
+            .build();
+        stack.add_named(&text_scrolled, Some("text"));
+
+        // Everything else: a large icon with a one-line summary
+        let icon_box = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(12)
+            .valign(gtk4::Align::Center)
+            .build();
+        let icon = gtk4::Image::builder()
+            .pixel_size(128)
+            .css_classes(["nautilus-icon"])
+            .build();
+        let summary_label = Label::builder()
+            .css_classes(["dim-label"])
+            .wrap(true)
+            .justify(gtk4::Justification::Center)
+            .build();
+        icon_box.append(&icon);
+        icon_box.append(&summary_label);
+        stack.add_named(&icon_box, Some("icon"));
+
+        stack.set_visible_child_name("empty");
+        container.append(&stack);
+
+        let name_label = Label::builder()
+            .wrap(true)
+            .wrap_mode(gtk4::pango::WrapMode::WordChar)
+            .justify(gtk4::Justification::Center)
+            .css_classes(["title-4"])
+            .build();
+        container.append(&name_label);
+
+        let properties_grid = gtk4::Grid::builder()
+            .row_spacing(4)
+            .column_spacing(12)
+            .build();
+        container.append(&properties_grid);
+
+        Self {
+            container,
+            stack,
+            picture,
+            text_view,
+            icon,
+            summary_label,
+            name_label,
+            properties_grid,
+            current_path: Rc::new(RefCell::new(None)),
+            vfs,
+            load_id: Rc::new(RefCell::new(0)),
+            size_cancel: Rc::new(RefCell::new(Arc::new(AtomicBool::new(false)))),
+        }
+    }
+
+    pub fn container(&self) -> &GtkBox {
+        &self.container
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        self.container.set_visible(visible);
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.container.is_visible()
+    }
+
+    /// Show the preview for a path (or the placeholder for None), loading it in the background
+    pub fn show_path(&self, path: Option<PathBuf>) {
+        if *self.current_path.borrow() == path {
+            return;
+        }
+        self.current_path.replace(path.clone());
+        self.size_cancel.borrow().store(true, Ordering::Relaxed);
+
+        // Newer requests supersede pending ones
+        let load_id = {
+            let mut id = self.load_id.borrow_mut();
+            *id += 1;
+            *id
+        };
+
+        let Some(path) = path else {
+            self.clear_properties();
+            self.name_label.set_text("");
+            self.stack.set_visible_child_name("empty");
+            return;
+        };
+
+        let dark_theme = adw::StyleManager::default().is_dark();
+        let backend = self.vfs.backend(&path);
+        let (tx, rx) = async_channel::bounded::<Result<(Preview, Option<gdk::Texture>), String>>(1);
+
+        thread::spawn(move || {
+            let result = Preview::load(backend.as_ref(), &path, dark_theme)
+                .map(|preview| {
+                    let texture = match preview.content {
+                        PreviewContent::Image { .. } => gdk_pixbuf::Pixbuf::from_file_at_scale(
+                            &path,
+                            IMAGE_PREVIEW_SIZE,
+                            IMAGE_PREVIEW_SIZE,
+                            true,
+                        )
+                        .ok()
+                        .map(|pixbuf| gdk::Texture::for_pixbuf(&pixbuf)),
+                        _ => None,
+                    };
+                    (preview, texture)
+                })
+                .map_err(|e| e.to_string());
+            let _ = tx.send_blocking(result);
+        });
+
+        let pane = self.clone();
+        glib::spawn_future_local(async move {
+            let Ok(result) = rx.recv().await else { return };
+            if *pane.load_id.borrow() != load_id {
+                return;
+            }
+
+            match result {
+                Ok((preview, texture)) => pane.display(preview, texture),
+                Err(e) => {
+                    pane.clear_properties();
+                    pane.name_label.set_text("");
+                    pane.icon.set_icon_name(Some("dialog-warning-symbolic"));
+                    pane.summary_label.set_text(&format!("Cannot preview: {}", e));
+                    pane.stack.set_visible_child_name("icon");
+                }
+            }
+        });
+    }
+
+    fn display(&self, preview: Preview, texture: Option<gdk::Texture>) {
+        self.name_label.set_text(&preview.name);
+        self.clear_properties();
+        for (row, (label, value)) in preview.properties.iter().enumerate() {
+            let key = Label::builder()
+                .label(label)
+                .halign(gtk4::Align::End)
+                .valign(gtk4::Align::Start)
+                .css_classes(["dim-label"])
+                .build();
+            let value = Label::builder()
+                .label(value)
+                .halign(gtk4::Align::Start)
+                .wrap(true)
+                .wrap_mode(gtk4::pango::WrapMode::WordChar)
+                .selectable(true)
+                .build();
+            self.properties_grid.attach(&key, 0, row as i32, 1, 1);
+            self.properties_grid.attach(&value, 1, row as i32, 1, 1);
+        }
+
+        let icon = gio::content_type_get_icon(&preview.content_type);
+        self.icon.set_from_gicon(&icon);
+
+        match preview.content {
+            PreviewContent::Image { width, height } => match texture {
+                Some(texture) => {
+                    self.picture.set_paintable(Some(&texture));
+                    self.stack.set_visible_child_name("image");
+                }
+                None => {
+                    self.summary_label.set_text(&format!("{} × {}", width, height));
+                    self.stack.set_visible_child_name("icon");
+                }
+            },
+            PreviewContent::Text { markup, truncated } => {
+                let buffer = self.text_view.buffer();
+                buffer.set_text("");
+                let mut end = buffer.end_iter();
+                buffer.insert_markup(&mut end, &markup);
+                if truncated {
+                    buffer.insert(&mut end, "\n…");
+                }
+                self.stack.set_visible_child_name("text");
+            }
+            PreviewContent::Directory { items } => {
+                self.icon.set_icon_name(Some("folder"));
+                self.summary_label.set_text(&format!("{} items", items));
+                self.stack.set_visible_child_name("icon");
+                self.measure_directory(items, preview.properties.len() as i32);
+            }
+            PreviewContent::Metadata | PreviewContent::None => {
+                self.summary_label.set_text("");
+                self.stack.set_visible_child_name("icon");
+            }
+        }
+    }
+
+    /// Add the folder's total size under its properties once it has been measured in the background.
+    /// Showing another path stops the measurement.
+    fn measure_directory(&self, items: usize, row: i32) {
+        let Some(path) = self.current_path.borrow().clone() else { return };
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.size_cancel.replace(cancel.clone());
+
+        let key = Label::builder()
+            .label("Size")
+            .halign(gtk4::Align::End)
+            .css_classes(["dim-label"])
+            .build();
+        let value = Label::builder()
+            .label("Calculating…")
+            .halign(gtk4::Align::Start)
+            .selectable(true)
+            .build();
+        self.properties_grid.attach(&key, 0, row, 1, 1);
+        self.properties_grid.attach(&value, 1, row, 1, 1);
+
+        let (tx, rx) = async_channel::bounded::<(SizeTotals, bool)>(1);
+        thread::spawn(move || {
+            let totals = FileProperties::measure(&[path], &cancel, |totals| {
+                // Skip updates while the UI is still showing the last one
+                let _ = tx.try_send((totals, false));
+            });
+            if !cancel.load(Ordering::Relaxed) {
+                let _ = tx.send_blocking((totals, true));
+            }
+        });
+
+        let summary_label = self.summary_label.clone();
+        glib::spawn_future_local(async move {
+            while let Ok((totals, done)) = rx.recv().await {
+                let size = format_size(totals.size);
+                if done {
+                    value.set_text(&size);
+                    summary_label.set_text(&format!("{} items, {}", items, size));
+                } else {
+                    value.set_text(&format!("{}…", size));
+                }
+            }
+        });
+    }
+
+    fn clear_properties(&self) {
+        while let Some(child) = self.properties_grid.first_child() {
+            self.properties_grid.remove(&child);
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use gtk4::prelude::*;
+use gtk4::{glib, CheckButton, Label};
+use libadwaita as adw;
+use adw::prelude::*;
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use chrono::{DateTime, Local};
+
+use crate::core::{format_size, ChecksumKind, FileProperties, SizeTotals};
+
+/// Properties of one or more files: basics, size, times, access and checksums
+pub struct PropertiesDialog;
+
+impl PropertiesDialog {
+    pub fn present(parent: &impl IsA<gtk4::Widget>, paths: Vec<PathBuf>) {
+        let properties: Vec<FileProperties> = paths
+            .iter()
+            .filter_map(|path| match FileProperties::load(path) {
+                Ok(properties) => Some(properties),
+                Err(e) => {
+                    log::warn!("Failed to read properties of {:?}: {}", path, e);
+                    None
+                }
+            })
+            .collect();
+        let Some(first) = properties.first().cloned() else { return };
+
+        let title = if properties.len() == 1 {
+            first
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| first.path.to_string_lossy().to_string())
+        } else {
+            format!("{} items", properties.len())
+        };
+
+        let dialog = adw::PreferencesDialog::builder()
+            .title(&title)
+            .search_enabled(false)
+            .build();
+        let page = adw::PreferencesPage::new();
+        dialog.add(&page);
+
+        page.add(&Self::basic_group(&properties));
+        page.add(&Self::size_group(&dialog, &properties));
+        if properties.len() == 1 {
+            page.add(&Self::times_group(&first));
+        }
+        page.add(&Self::access_group(&dialog, &properties));
+        if properties.len() == 1 && !first.is_directory {
+            page.add(&Self::checksum_group(&first));
+        }
+
+        dialog.present(Some(parent));
+    }
+
+    fn info_row(title: &str, value: &str) -> adw::ActionRow {
+        adw::ActionRow::builder()
+            .title(title)
+            .subtitle(glib::markup_escape_text(value).as_str())
+            .subtitle_selectable(true)
+            .css_classes(["property"])
+            .build()
+    }
+
+    /// The value shared by all items, if they agree
+    fn common<T: PartialEq + Clone>(properties: &[FileProperties], value: impl Fn(&FileProperties) -> T) -> Option<T> {
+        let first = value(properties.first()?);
+        properties.iter().all(|p| value(p) == first).then_some(first)
+    }
+
+    fn basic_group(properties: &[FileProperties]) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder().title("Basic").build();
+
+        if let [single] = properties {
+            group.add(&Self::info_row("Path", &single.path.to_string_lossy()));
+            group.add(&Self::info_row(
+                "Type",
+                &format!("{} ({})", single.type_description, single.content_type),
+            ));
+            if let Some(target) = &single.symlink_target {
+                group.add(&Self::info_row("Link target", &target.to_string_lossy()));
+            }
+        } else {
+            let parent = Self::common(properties, |p| p.path.parent().map(|p| p.to_path_buf()));
+            if let Some(Some(parent)) = parent {
+                group.add(&Self::info_row("Location", &parent.to_string_lossy()));
+            }
+            let description = Self::common(properties, |p| p.type_description.clone())
+                .unwrap_or_else(|| String::from("Mixed"));
+            group.add(&Self::info_row("Type", &description));
+        }
+
+        group
+    }
+
+    fn size_group(dialog: &adw::PreferencesDialog, properties: &[FileProperties]) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder().title("Size").build();
+        let size_row = Self::info_row("Size", "Calculating…");
+        let contents_row = Self::info_row("Contents", "Calculating…");
+        group.add(&size_row);
+
+        let needs_scan = properties.len() > 1 || properties.iter().any(|p| p.is_directory);
+        if !needs_scan {
+            let size = properties[0].size;
+            size_row.set_subtitle(&format!("{} ({} bytes)", format_size(size), size));
+            return group;
+        }
+        group.add(&contents_row);
+
+        // Directory sizes are computed in the background and stream in while the dialog is open
+        let paths: Vec<PathBuf> = properties.iter().map(|p| p.path.clone()).collect();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = async_channel::unbounded::<(SizeTotals, bool)>();
+
+        let cancel_thread = cancel.clone();
+        thread::spawn(move || {
+            let totals = FileProperties::measure(&paths, &cancel_thread, |totals| {
+                let _ = tx.send_blocking((totals, false));
+            });
+            let _ = tx.send_blocking((totals, true));
+        });
+
+        dialog.connect_closed(move |_| {
+            cancel.store(true, Ordering::Relaxed);
+        });
+
+        glib::spawn_future_local(async move {
+            while let Ok((totals, finished)) = rx.recv().await {
+                let suffix = if finished { "" } else { "…" };
+                size_row.set_subtitle(&format!("{} ({} bytes){}", format_size(totals.size), totals.size, suffix));
+                contents_row.set_subtitle(&format!(
+                    "{} files, {} folders{}",
+                    totals.files, totals.directories, suffix
+                ));
+                if finished {
+                    break;
+                }
+            }
+        });
+
+        group
+    }
+
+    fn times_group(properties: &FileProperties) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder().title("Times").build();
+        let format_time = |time: Option<std::time::SystemTime>| {
+            time.map(|t| DateTime::<Local>::from(t).format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_else(|| String::from("Unknown"))
+        };
+
+        group.add(&Self::info_row("Created", &format_time(properties.created)));
+        group.add(&Self::info_row("Modified", &format_time(properties.modified)));
+        group.add(&Self::info_row("Accessed", &format_time(properties.accessed)));
+        group
+    }
+
+    fn access_group(dialog: &adw::PreferencesDialog, properties: &[FileProperties]) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder().title("Access").build();
+
+        let owner = Self::common(properties, |p| p.owner.clone()).unwrap_or_else(|| String::from("Mixed"));
+        let group_name = Self::common(properties, |p| p.group.clone()).unwrap_or_else(|| String::from("Mixed"));
+        group.add(&Self::info_row("Owner", &owner));
+        group.add(&Self::info_row("Group", &group_name));
+
+        // rwx checkboxes: rows are owner/group/others, columns read/write/execute
+        let grid = gtk4::Grid::builder()
+            .row_spacing(6)
+            .column_spacing(18)
+            .margin_top(12)
+            .margin_bottom(12)
+            .margin_start(12)
+            .margin_end(12)
+            .halign(gtk4::Align::Center)
+            .build();
+        for (column, label) in ["Read", "Write", "Execute"].iter().enumerate() {
+            grid.attach(&Label::builder().label(*label).css_classes(["dim-label"]).build(), column as i32 + 1, 0, 1, 1);
+        }
+
+        let mode = properties[0].mode;
+        let mut checks: Vec<(CheckButton, u32)> = Vec::new();
+        for (row, (label, shift)) in [("Owner", 6), ("Group", 3), ("Others", 0)].iter().enumerate() {
+            grid.attach(
+                &Label::builder().label(*label).halign(gtk4::Align::Start).build(),
+                0,
+                row as i32 + 1,
+                1,
+                1,
+            );
+            for (column, bit) in [0o4, 0o2, 0o1].iter().enumerate() {
+                let mask = bit << shift;
+                let check = CheckButton::builder()
+                    .active(mode & mask != 0)
+                    .halign(gtk4::Align::Center)
+                    .build();
+                grid.attach(&check, column as i32 + 1, row as i32 + 1, 1, 1);
+                checks.push((check, mask));
+            }
+        }
+        let grid_row = adw::PreferencesRow::builder().activatable(false).child(&grid).build();
+        group.add(&grid_row);
+
+        let octal_row = adw::EntryRow::builder()
+            .title("Octal")
+            .text(format!("{:o}", mode & 0o777))
+            .build();
+        group.add(&octal_row);
+
+        // Keep checkboxes and the octal field in sync without feedback loops
+        let syncing = Rc::new(RefCell::new(false));
+        let checks = Rc::new(checks);
+        for (check, _) in checks.iter() {
+            let checks = checks.clone();
+            let octal_row = octal_row.clone();
+            let syncing = syncing.clone();
+            check.connect_toggled(move |_| {
+                if *syncing.borrow() {
+                    return;
+                }
+                let mode = checks
+                    .iter()
+                    .filter(|(check, _)| check.is_active())
+                    .fold(0, |mode, (_, mask)| mode | mask);
+                syncing.replace(true);
+                octal_row.set_text(&format!("{:o}", mode));
+                syncing.replace(false);
+            });
+        }
+        {
+            let checks = checks.clone();
+            let syncing = syncing.clone();
+            octal_row.connect_changed(move |row| {
+                if *syncing.borrow() {
+                    return;
+                }
+                let Ok(mode) = u32::from_str_radix(row.text().trim(), 8) else { return };
+                if mode > 0o777 {
+                    return;
+                }
+                syncing.replace(true);
+                for (check, mask) in checks.iter() {
+                    check.set_active(mode & mask != 0);
+                }
+                syncing.replace(false);
+            });
+        }
+
+        let recursive_row = adw::SwitchRow::builder()
+            .title("Apply to enclosed items")
+            .subtitle("Files keep their own execute permission")
+            .build();
+        if properties.iter().any(|p| p.is_directory) {
+            group.add(&recursive_row);
+        }
+
+        let apply_button = gtk4::Button::builder()
+            .label("Apply")
+            .valign(gtk4::Align::Center)
+            .css_classes(["suggested-action"])
+            .build();
+        group.set_header_suffix(Some(&apply_button));
+
+        let paths: Vec<(PathBuf, u32)> = properties.iter().map(|p| (p.path.clone(), p.mode)).collect();
+        let dialog = dialog.clone();
+        apply_button.connect_clicked(move |button| {
+            let bits = checks
+                .iter()
+                .filter(|(check, _)| check.is_active())
+                .fold(0, |mode, (_, mask)| mode | mask);
+            let recursive = recursive_row.is_active();
+            let paths = paths.clone();
+
+            button.set_sensitive(false);
+            let (tx, rx) = async_channel::bounded::<Result<(), String>>(1);
+            thread::spawn(move || {
+                let result = paths.iter().try_for_each(|(path, old_mode)| {
+                    // setuid/setgid/sticky bits aren't shown, keep them as they were
+                    FileProperties::set_mode(path, (old_mode & 0o7000) | bits, recursive)
+                        .map_err(|e| format!("{}: {}", path.display(), e))
+                });
+                let _ = tx.send_blocking(result);
+            });
+
+            let button = button.clone();
+            let dialog = dialog.clone();
+            glib::spawn_future_local(async move {
+                if let Ok(result) = rx.recv().await {
+                    let message = match result {
+                        Ok(()) => String::from("Permissions changed"),
+                        Err(e) => format!("Could not change permissions: {}", e),
+                    };
+                    dialog.add_toast(adw::Toast::new(&message));
+                }
+                button.set_sensitive(true);
+            });
+        });
+
+        group
+    }
+
+    fn checksum_group(properties: &FileProperties) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder().title("Checksums").build();
+
+        for kind in [ChecksumKind::Md5, ChecksumKind::Sha256] {
+            let row = Self::info_row(kind.label(), "Not calculated");
+            let button = gtk4::Button::builder()
+                .label("Calculate")
+                .valign(gtk4::Align::Center)
+                .build();
+            row.add_suffix(&button);
+            group.add(&row);
+
+            let path = properties.path.clone();
+            button.connect_clicked(move |button| {
+                button.set_visible(false);
+                row.set_subtitle("Calculating…");
+
+                let path = path.clone();
+                let (tx, rx) = async_channel::bounded::<std::io::Result<String>>(1);
+                thread::spawn(move || {
+                    let _ = tx.send_blocking(FileProperties::checksum(&path, kind));
+                });
+
+                let row = row.clone();
+                glib::spawn_future_local(async move {
+                    match rx.recv().await {
+                        Ok(Ok(hash)) => row.set_subtitle(&hash),
+                        Ok(Err(e)) => row.set_subtitle(&glib::markup_escape_text(&format!("Failed: {}", e))),
+                        Err(_) => {}
+                    }
+                });
+            });
+        }
+
+        group
+    }
+}
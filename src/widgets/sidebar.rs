@@ -1,33 +1,37 @@
 use gtk4::glib::{self, clone};
 use gtk4::prelude::*;
 use gtk4::{
-    gio, Box as GtkBox, DropTarget, EventControllerKey, GestureClick, Image, Label, ListBox, ListBoxRow, 
-    Orientation, PopoverMenu, ScrolledWindow, SelectionMode, Separator,
+    gio, Box as GtkBox, DragSource, DropTarget, Entry, EventControllerKey, GestureClick, Image, Label, ListBox,
+    ListBoxRow, Orientation, PopoverMenu, ScrolledWindow, SelectionMode, Separator,
 };
 use std::cell::RefCell;
 use std::path::PathBuf;
 use std::rc::Rc;
-use std::fs::OpenOptions;
-use std::io::Write;
-
-use crate::core::{DriveScanner, PinnedFolderObject, PinnedFolderStore};
-
-// #region agent log
-fn debug_log(hypothesis_id: &str, location: &str, message: &str, data: serde_json::Value) {
-    let log_entry = serde_json::json!({
-        "sessionId": "debug-session",
-        "runId": "run1",
-        "hypothesisId": hypothesis_id,
-        "location": location,
-        "message": message,
-        "data": data,
-        "timestamp": std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis()
-    });
-    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open("/home/artwik/.config/alloy/.cursor/debug.log") {
-        let _ = writeln!(file, "{}", log_entry);
-    }
-}
-// #endregion
+use std::time::Duration;
+
+use crate::core::{DriveInfo, DriveScanner, PinColor, PinStyle, PinnedFolderObject, PinnedFolderStore, RecentFiles, Remote};
+
+/// Icons a pin can be given instead of the plain folder
+const PIN_ICONS: [(&str, &str); 10] = [
+    ("Default", ""),
+    ("Star", "starred-symbolic"),
+    ("Bookmark", "user-bookmarks-symbolic"),
+    ("Documents", "folder-documents-symbolic"),
+    ("Pictures", "folder-pictures-symbolic"),
+    ("Music", "folder-music-symbolic"),
+    ("Videos", "folder-videos-symbolic"),
+    ("Code", "text-x-script-symbolic"),
+    ("Games", "input-gaming-symbolic"),
+    ("Important", "emblem-important-symbolic"),
+];
+
+/// How often the drive capacity bars are brought up to date
+const DISK_USAGE_INTERVAL_SECS: u32 = 30;
+/// How long one drive may take to report its usage before it is skipped
+const DISK_USAGE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A handler set through one of the `connect_*` methods
+type Callback<T> = Rc<RefCell<Option<Box<dyn Fn(T)>>>>;
 
 // ============================================================================
 // Sidebar Item Types
@@ -40,6 +44,45 @@ pub enum SidebarItemType {
     Drive,
 }
 
+/// Reads drive usage on one thread, one round at a time
+#[derive(Clone)]
+struct DriveUsageWorker {
+    requests: async_channel::Sender<Vec<PathBuf>>,
+    /// Set while a round is being read
+    busy: Rc<std::cell::Cell<bool>>,
+    /// Another round was asked for meanwhile
+    rerun: Rc<std::cell::Cell<bool>>,
+}
+
+/// What a row of the pinned list shows
+#[derive(Clone, PartialEq)]
+enum PinRowKey {
+    Pin {
+        path: String,
+        name: String,
+        style: PinStyle,
+        missing: bool,
+        /// The group it is listed under, which differs from `style` for groups that are gone
+        section: Option<String>,
+    },
+    Group {
+        name: String,
+        collapsed: bool,
+    },
+}
+
+impl PinRowKey {
+    fn pin(pinned: &PinnedFolderObject, section: Option<String>) -> Self {
+        Self::Pin {
+            path: pinned.path_string(),
+            name: pinned.name(),
+            style: pinned.style(),
+            missing: pinned.is_missing(),
+            section,
+        }
+    }
+}
+
 // ============================================================================
 // NautilusSidebar - Main sidebar widget
 // ============================================================================
@@ -50,8 +93,11 @@ pub struct NautilusSidebar {
     pinned_list_box: ListBox,
     standard_list_box: ListBox,
     other_list_box: ListBox,
+    recent_label: Label,
+    recent_list_box: ListBox,
     pinned_store: PinnedFolderStore,
-    on_location_selected: Rc<RefCell<Option<Box<dyn Fn(PathBuf)>>>>,
+    on_location_selected: Callback<PathBuf>,
+    on_error: Callback<String>,
 }
 
 impl NautilusSidebar {
@@ -109,16 +155,63 @@ impl NautilusSidebar {
             .build();
         pinned_section.append(&pinned_label);
 
+        // Right-click the heading to choose where pins are kept
+        {
+            let action_group = gio::SimpleActionGroup::new();
+            let share_action = gio::SimpleAction::new_stateful(
+                "use-gtk-bookmarks",
+                None,
+                &pinned_store.uses_gtk_bookmarks().to_variant(),
+            );
+            let pinned_store_clone = pinned_store.clone();
+            share_action.connect_change_state(move |action, value| {
+                let Some(enabled) = value.and_then(|v| v.get::<bool>()) else { return };
+                match pinned_store_clone.set_use_gtk_bookmarks(enabled) {
+                    Ok(()) => action.set_state(&enabled.to_variant()),
+                    Err(e) => log::warn!("Failed to switch pin storage: {}", e),
+                }
+            });
+            action_group.add_action(&share_action);
+            pinned_section.insert_action_group("pins", Some(&action_group));
+
+            let new_group_action = gio::SimpleAction::new("new-group", None);
+            {
+                let pinned_store_clone = pinned_store.clone();
+                let pinned_label = pinned_label.clone();
+                new_group_action.connect_activate(move |_, _| {
+                    let pinned_store_clone = pinned_store_clone.clone();
+                    Self::prompt_name(&pinned_label, "New Group", "", move |name| {
+                        pinned_store_clone.add_group(&name);
+                    });
+                });
+            }
+            action_group.add_action(&new_group_action);
+
+            let menu = gio::Menu::new();
+            menu.append(Some("New Group…"), Some("pins.new-group"));
+            menu.append(Some("Share with GTK Bookmarks"), Some("pins.use-gtk-bookmarks"));
+            let popover = PopoverMenu::from_model(Some(&menu));
+            popover.set_parent(&pinned_label);
+            popover.set_has_arrow(false);
+
+            let gesture = GestureClick::builder().button(3).build();
+            gesture.connect_pressed(move |_, _, _, _| {
+                popover.popup();
+            });
+            pinned_label.add_controller(gesture);
+        }
+
         let pinned_list_box = ListBox::builder()
             .selection_mode(SelectionMode::Single)
             .css_classes(["navigation-sidebar"])
             .build();
 
         // Bind pinned store to list box using factory
-        let on_location_selected: Rc<RefCell<Option<Box<dyn Fn(PathBuf)>>>> = 
+        let on_location_selected: Callback<PathBuf> = 
             Rc::new(RefCell::new(None));
+        let on_error: Callback<String> = Rc::new(RefCell::new(None));
         
-        Self::bind_pinned_store(&pinned_list_box, &pinned_store, on_location_selected.clone());
+        Self::bind_pinned_store(&pinned_list_box, &pinned_store, on_location_selected.clone(), on_error.clone());
 
         // Keyboard shortcuts for Pinned List (F2 to rename)
         {
@@ -150,6 +243,7 @@ impl NautilusSidebar {
         {
             let pinned_store_clone = pinned_store.clone();
             let pinned_section_clone = pinned_section.clone();
+            let on_error = on_error.clone();
             
             let drop_target = DropTarget::new(
                 glib::Type::INVALID, // We will set multiple supported types
@@ -220,7 +314,7 @@ impl NautilusSidebar {
                         }
                         
                         if let Err(e) = pinned_store_clone.add(&path) {
-                            eprintln!("Failed to pin folder: {}", e);
+                            Self::report_to(&on_error, format!("Failed to pin folder: {}", e));
                         } else {
                             success = true;
                         }
@@ -249,6 +343,9 @@ impl NautilusSidebar {
         Self::add_standard_location(&standard_list_box, "Home", "user-home-symbolic", 
             dirs::home_dir().unwrap_or_else(|| PathBuf::from("/")), SidebarItemType::StandardFolder);
 
+        Self::add_standard_location(&standard_list_box, "Recent", "document-open-recent-symbolic",
+            RecentFiles::location(), SidebarItemType::SystemFolder);
+
         if let Some(documents) = dirs::document_dir() {
             Self::add_standard_location(&standard_list_box, "Documents", "folder-documents-symbolic", 
                 documents, SidebarItemType::StandardFolder);
@@ -281,6 +378,25 @@ impl NautilusSidebar {
 
         main_box.append(&standard_list_box);
 
+        // ===== Frequent Locations Section (filled by the window, hidden while empty) =====
+        let recent_label = Label::builder()
+            .label("Frequent")
+            .halign(gtk4::Align::Start)
+            .margin_start(12)
+            .margin_top(12)
+            .margin_bottom(6)
+            .css_classes(["dim-label", "caption"])
+            .visible(false)
+            .build();
+        main_box.append(&recent_label);
+
+        let recent_list_box = ListBox::builder()
+            .selection_mode(SelectionMode::None)
+            .css_classes(["navigation-sidebar"])
+            .visible(false)
+            .build();
+        main_box.append(&recent_list_box);
+
         // ===== Other Locations Section =====
         let other_label = Label::builder()
             .label("Other Locations")
@@ -297,23 +413,9 @@ impl NautilusSidebar {
             .css_classes(["navigation-sidebar"])
             .build();
 
-        Self::add_standard_location(&other_list_box, "Computer", "drive-harddisk-symbolic", 
-            PathBuf::from("/"), SidebarItemType::Drive);
-
-        // Add other drives
-        let drives = DriveScanner::scan();
-        for drive in &drives {
-            if drive.mount_point == PathBuf::from("/") {
-                continue;
-            }
-            if let Some(home) = dirs::home_dir() {
-                if drive.mount_point == home {
-                    continue;
-                }
-            }
-            Self::add_standard_location(&other_list_box, &drive.name, &drive.icon_name, 
-                drive.mount_point.clone(), SidebarItemType::Drive);
-        }
+        // Drives are rebuilt whenever media come and go
+        Self::fill_drives(&other_list_box);
+        Self::watch_drives(&other_list_box);
 
         main_box.append(&other_list_box);
         scrolled.set_child(Some(&main_box));
@@ -371,10 +473,10 @@ impl NautilusSidebar {
             });
         }
 
-        // Connect other list box row activation
+        // Connect recent list box row activation
         {
             let on_location_selected_clone = on_location_selected.clone();
-            other_list_box.connect_row_activated(move |_, row| {
+            recent_list_box.connect_row_activated(move |_, row| {
                 if let Some(path) = Self::get_row_path(row) {
                     if let Some(ref callback) = *on_location_selected_clone.borrow() {
                         callback(path);
@@ -383,108 +485,412 @@ impl NautilusSidebar {
             });
         }
 
+        // Connect other list box row activation
+        {
+            let on_location_selected_clone = on_location_selected.clone();
+            other_list_box.connect_row_activated(move |list_box, row| {
+                if let Some(volume) = unsafe { row.data::<gio::Volume>("volume").map(|v| v.as_ref().clone()) } {
+                    Self::mount_volume(list_box, &volume, on_location_selected_clone.clone());
+                } else if let Some(path) = Self::get_row_path(row) {
+                    if let Some(ref callback) = *on_location_selected_clone.borrow() {
+                        callback(path);
+                    }
+                }
+            });
+        }
+
         // Setup context menus for standard and other locations
         Self::setup_standard_context_menu(&standard_list_box, &pinned_store);
         Self::setup_standard_context_menu(&other_list_box, &pinned_store);
+        Self::setup_standard_context_menu(&recent_list_box, &pinned_store);
 
         Self {
             container,
             pinned_list_box,
             standard_list_box,
             other_list_box,
+            recent_label,
+            recent_list_box,
             pinned_store,
             on_location_selected,
+            on_error,
         }
     }
 
-    /// Bind the pinned store to a ListBox using a factory function
+    /// Show the pinned store in a ListBox: ungrouped pins first, then each group under
+    /// a collapsible header. Rows are kept and only moved or replaced when the store changes.
     fn bind_pinned_store(
         list_box: &ListBox, 
         store: &PinnedFolderStore,
-        on_location_selected: Rc<RefCell<Option<Box<dyn Fn(PathBuf)>>>>
+        on_location_selected: Callback<PathBuf>,
+        on_error: Callback<String>,
     ) {
-        let store_clone = store.clone();
-        let on_location_selected_clone = on_location_selected.clone();
-        
-        list_box.bind_model(
-            Some(store.store()),
-            move |obj| {
-                let pinned_obj = obj.clone().downcast::<PinnedFolderObject>()
-                    .expect("Expected PinnedFolderObject");
-                
-                // #region agent log
-                debug_log("B", "sidebar.rs:bind_pinned_store", "Factory called", serde_json::json!({
-                    "path": pinned_obj.path().to_string_lossy(),
-                    "name": pinned_obj.name(),
-                    "obj_ptr": format!("{:p}", &pinned_obj)
-                }));
-                // #endregion
-                
-                // Skip standard locations
-                if Self::is_standard_location(&pinned_obj.path()) {
-                    // Return an invisible row for standard locations
-                    let row = ListBoxRow::builder()
-                        .visible(false)
-                        .build();
-                    return row.upcast();
+        Self::fill_pinned(list_box, store, &on_error);
+        {
+            let list_box = list_box.downgrade();
+            let store_clone = store.clone();
+            let on_error = on_error.clone();
+            store.store().connect_items_changed(move |_, _, _, _| {
+                if let Some(list_box) = list_box.upgrade() {
+                    Self::fill_pinned(&list_box, &store_clone, &on_error);
                 }
-                
-                let row = Self::create_sidebar_row(
-                    &pinned_obj.name(), 
-                    "folder-symbolic", 
-                    &pinned_obj.path()
-                );
-                
-                // #region agent log
-                debug_log("B", "sidebar.rs:bind_pinned_store", "Before setup_pinned_row_context_menu", serde_json::json!({
-                    "row_ptr": format!("{:p}", &row),
-                    "path": pinned_obj.path().to_string_lossy()
-                }));
-                // #endregion
-                
-                // Setup context menu for pinned folder
-                Self::setup_pinned_row_context_menu(&row, &pinned_obj, &store_clone);
-                
-                // #region agent log
-                debug_log("B", "sidebar.rs:bind_pinned_store", "After setup_pinned_row_context_menu", serde_json::json!({
-                    "row_ptr": format!("{:p}", &row),
-                    "path": pinned_obj.path().to_string_lossy()
-                }));
-                // #endregion
-                
-                // Connect click for navigation
-                let path = pinned_obj.path();
-                let on_selected = on_location_selected_clone.clone();
-                row.connect_activate(move |_| {
-                    if let Some(ref callback) = *on_selected.borrow() {
-                        callback(path.clone());
+            });
+        }
+
+        // Pins on a drive come back when it is mounted
+        {
+            let monitor = gio::VolumeMonitor::get();
+            let handlers = vec![
+                monitor.connect_mount_added(clone!(#[strong] store, move |_, _| store.check_missing())),
+                monitor.connect_mount_removed(clone!(#[strong] store, move |_, _| store.check_missing())),
+            ];
+            let handlers = RefCell::new(handlers);
+            list_box.connect_destroy(move |_| {
+                for handler in handlers.borrow_mut().drain(..) {
+                    monitor.disconnect(handler);
+                }
+            });
+        }
+
+        // Drag pins to reorder them, between rows, onto group headers or below the last row
+        let drop_target = DropTarget::new(PinnedFolderObject::static_type(), gtk4::gdk::DragAction::MOVE);
+        {
+            let store = store.clone();
+            let on_error = on_error.clone();
+            drop_target.connect_drop(move |target, value, _, y| {
+                let Ok(dragged) = value.get::<PinnedFolderObject>() else { return false };
+                let Some(list_box) = target.widget().and_downcast::<ListBox>() else { return false };
+                let (before, group) = Self::pin_drop_position(&list_box, y);
+                // Move once the drop is done, as it reorders the rows
+                let store = store.clone();
+                let on_error = on_error.clone();
+                glib::idle_add_local_once(move || {
+                    if let Err(e) = store.move_pin(&dragged.path(), before.as_deref(), group) {
+                        Self::report_to(&on_error, format!("Failed to move pin: {}", e));
                     }
                 });
-                
-                row.upcast()
-            }
-        );
-        
+                true
+            });
+        }
+        list_box.add_controller(drop_target);
+
         // Connect row activation for pinned list
-        let on_location_selected_activation = on_location_selected.clone();
-        let store_for_activation = store.store().clone();
-        
+        let store_clone = store.clone();
         list_box.connect_row_activated(move |_, row| {
-            let index = row.index();
-            if index >= 0 {
-                if let Some(obj) = store_for_activation.item(index as u32) {
-                    if let Ok(pinned) = obj.downcast::<PinnedFolderObject>() {
-                        if let Some(ref callback) = *on_location_selected_activation.borrow() {
-                            callback(pinned.path());
-                        }
+            if let Some(group) = Self::get_row_group(row) {
+                let collapsed = store_clone.groups().iter().any(|g| g.name == group && g.collapsed);
+                store_clone.set_group_collapsed(&group, !collapsed);
+            } else if let Some(pinned) = unsafe { row.data::<PinnedFolderObject>("pin").map(|p| p.as_ref().clone()) } {
+                Self::open_pinned(&pinned, &on_location_selected, &on_error);
+            }
+        });
+    }
+
+    fn fill_pinned(list_box: &ListBox, store: &PinnedFolderStore, on_error: &Callback<String>) {
+        let pins: Vec<PinnedFolderObject> = (0..store.store().n_items())
+            .filter_map(|i| store.store().item(i).and_downcast::<PinnedFolderObject>())
+            // Standard locations are already listed below
+            .filter(|pinned| !Self::is_standard_location(&pinned.path()))
+            .collect();
+        let groups = store.groups();
+
+        // Pins naming a group that no longer exists are shown ungrouped
+        let is_ungrouped = |pinned: &PinnedFolderObject| {
+            pinned.style().group.is_none_or(|name| !groups.iter().any(|g| g.name == name))
+        };
+        let mut wanted: Vec<(PinRowKey, Option<&PinnedFolderObject>)> = pins
+            .iter()
+            .filter(|pinned| is_ungrouped(pinned))
+            .map(|pinned| (PinRowKey::pin(pinned, None), Some(pinned)))
+            .collect();
+        for group in &groups {
+            wanted.push((PinRowKey::Group { name: group.name.clone(), collapsed: group.collapsed }, None));
+            if group.collapsed {
+                continue;
+            }
+            for pinned in pins.iter().filter(|pinned| pinned.style().group.as_deref() == Some(group.name.as_str())) {
+                wanted.push((PinRowKey::pin(pinned, Some(group.name.clone())), Some(pinned)));
+            }
+        }
+
+        // Reuse the rows that still show the same thing and only move those out of place
+        let mut existing: Vec<(PinRowKey, ListBoxRow)> = std::iter::successors(list_box.row_at_index(0), |row| {
+            list_box.row_at_index(row.index() + 1)
+        })
+        .filter_map(|row| Self::get_row_key(&row).map(|key| (key, row)))
+        .collect();
+        for (index, (key, pinned)) in wanted.iter().enumerate() {
+            let row = match existing.iter().position(|(existing_key, _)| existing_key == key) {
+                Some(position) => existing.swap_remove(position).1,
+                None => {
+                    let row = match (key, pinned) {
+                        (PinRowKey::Group { name, collapsed }, _) => Self::create_group_row(name, *collapsed, store),
+                        (_, Some(pinned)) => Self::create_pin_row(pinned, store, on_error),
+                        (_, None) => continue,
+                    };
+                    unsafe {
+                        row.set_data("pin_key", key.clone());
                     }
+                    row
+                }
+            };
+            let index = index as i32;
+            if list_box.row_at_index(index).as_ref() != Some(&row) {
+                if row.parent().is_some() {
+                    list_box.remove(&row);
                 }
+                list_box.insert(&row, index);
+            }
+        }
+        for (_, row) in existing {
+            list_box.remove(&row);
+        }
+    }
+
+    /// Where a pin dropped at `y` goes: in front of another pin, or with no pin to go
+    /// in front of, at the end of a group (`None` being the ungrouped pins)
+    fn pin_drop_position(list_box: &ListBox, y: f64) -> (Option<PathBuf>, Option<String>) {
+        let end_of_section = |row: Option<ListBoxRow>| (None, row.and_then(|row| Self::get_row_section(&row)));
+        let Some(row) = list_box.row_at_y(y as i32) else {
+            return end_of_section(list_box.last_child().and_downcast::<ListBoxRow>());
+        };
+        let lower_half = row
+            .compute_bounds(list_box)
+            .is_some_and(|bounds| y > f64::from(bounds.y() + bounds.height() / 2.0));
+        let next = list_box.row_at_index(row.index() + 1);
+
+        match Self::get_row_key(&row) {
+            // Below a header is the start of its group, above it the end of the section before
+            Some(PinRowKey::Group { name, .. }) if lower_half => {
+                match next.as_ref().and_then(Self::get_row_key) {
+                    Some(PinRowKey::Pin { path, .. }) => (Some(PathBuf::from(path)), Some(name)),
+                    _ => (None, Some(name)),
+                }
+            }
+            Some(PinRowKey::Group { .. }) => end_of_section(list_box.row_at_index(row.index() - 1)),
+            Some(PinRowKey::Pin { path, section, .. }) if !lower_half => (Some(PathBuf::from(path)), section),
+            Some(PinRowKey::Pin { section, .. }) => match next.as_ref().and_then(Self::get_row_key) {
+                Some(PinRowKey::Pin { path, .. }) => (Some(PathBuf::from(path)), section),
+                _ => (None, section),
+            },
+            None => (None, None),
+        }
+    }
+
+    /// What a pinned row shows, to tell whether it can be kept
+    fn get_row_key(row: &ListBoxRow) -> Option<PinRowKey> {
+        unsafe { row.data::<PinRowKey>("pin_key").map(|key| key.as_ref().clone()) }
+    }
+
+    /// The group a pinned row is listed under, `None` for ungrouped pins
+    fn get_row_section(row: &ListBoxRow) -> Option<String> {
+        match Self::get_row_key(row)? {
+            PinRowKey::Pin { section, .. } => section,
+            PinRowKey::Group { name, .. } => Some(name),
+        }
+    }
+
+    fn create_pin_row(pinned: &PinnedFolderObject, store: &PinnedFolderStore, on_error: &Callback<String>) -> ListBoxRow {
+        let style = pinned.style();
+        let default_icon = if pinned.is_remote() { "folder-remote-symbolic" } else { "folder-symbolic" };
+        let row = Self::create_sidebar_row(
+            &pinned.name(),
+            style.icon_name.as_deref().unwrap_or(default_icon),
+            &pinned.path(),
+        );
+        if let Some(color) = style.color {
+            if let Some(icon) = row.child().and_then(|hbox| hbox.first_child()) {
+                icon.add_css_class(&color.css_class());
+            }
+        }
+
+        if pinned.is_missing() {
+            row.add_css_class("pin-missing");
+            row.set_tooltip_text(Some(&format!("Not available: {}", pinned.path_string())));
+        } else if pinned.is_remote() {
+            row.set_tooltip_text(Some(&pinned.path_string()));
+        }
+
+        unsafe {
+            row.set_data("pin", pinned.clone());
+        }
+
+        // Drag pins to reorder them; the list box takes the drop
+        let drag_source = DragSource::new();
+        drag_source.set_actions(gtk4::gdk::DragAction::MOVE);
+        drag_source.set_content(Some(&gtk4::gdk::ContentProvider::for_value(&pinned.to_value())));
+        row.add_controller(drag_source);
+
+
+        Self::setup_pinned_row_context_menu(&row, pinned, store, on_error);
+        row
+    }
+
+    /// Header of a pin group; activating it collapses or expands the group
+    fn create_group_row(name: &str, collapsed: bool, store: &PinnedFolderStore) -> ListBoxRow {
+        let row = ListBoxRow::builder()
+            .selectable(false)
+            .css_classes(["sidebar-row", "pin-group"])
+            .build();
+
+        let hbox = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .margin_start(8)
+            .margin_end(8)
+            .build();
+        let arrow = Image::builder()
+            .icon_name(if collapsed { "pan-end-symbolic" } else { "pan-down-symbolic" })
+            .pixel_size(12)
+            .css_classes(["dim-label"])
+            .build();
+        let label = Label::builder()
+            .label(name)
+            .halign(gtk4::Align::Start)
+            .hexpand(true)
+            .css_classes(["dim-label", "caption-heading"])
+            .build();
+        hbox.append(&arrow);
+        hbox.append(&label);
+        row.set_child(Some(&hbox));
+
+        unsafe {
+            row.set_data("pin_group", name.to_string());
+        }
+
+        // Rename or remove the group
+        let gesture = GestureClick::builder().button(3).build();
+        {
+            let store = store.clone();
+            let group = name.to_string();
+            gesture.connect_pressed(move |gesture, _, x, y| {
+                let Some(row) = gesture.widget() else { return };
+
+                let menu = gio::Menu::new();
+                menu.append(Some("Rename Group…"), Some("group.rename"));
+                menu.append(Some("Remove Group"), Some("group.remove"));
+
+                let action_group = gio::SimpleActionGroup::new();
+                let rename_action = gio::SimpleAction::new("rename", None);
+                {
+                    let store = store.clone();
+                    let group = group.clone();
+                    let row = row.clone();
+                    rename_action.connect_activate(move |_, _| {
+                        let store = store.clone();
+                        let old_name = group.clone();
+                        Self::prompt_name(&row, "Rename Group", &group, move |new_name| {
+                            store.rename_group(&old_name, &new_name);
+                        });
+                    });
+                }
+                action_group.add_action(&rename_action);
+
+                let remove_action = gio::SimpleAction::new("remove", None);
+                {
+                    let store = store.clone();
+                    let group = group.clone();
+                    remove_action.connect_activate(move |_, _| store.remove_group(&group));
+                }
+                action_group.add_action(&remove_action);
+
+                let popover = PopoverMenu::from_model(Some(&menu));
+                popover.set_parent(&row);
+                popover.set_pointing_to(Some(&gtk4::gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
+                popover.insert_action_group("group", Some(&action_group));
+                popover.connect_closed(|p| {
+                    // Unparent once the activated action has run
+                    let p = p.clone();
+                    glib::idle_add_local_once(move || p.unparent());
+                });
+                popover.popup();
+            });
+        }
+        row.add_controller(gesture);
+
+        row
+    }
+
+    /// Ask for a name, e.g. of a pin group
+    fn prompt_name<F: Fn(String) + 'static>(widget: &impl IsA<gtk4::Widget>, heading: &str, initial: &str, on_done: F) {
+        use libadwaita as adw;
+        use adw::prelude::*;
+
+        let dialog = adw::AlertDialog::builder()
+            .heading(heading)
+            .build();
+
+        let entry = Entry::builder()
+            .text(initial)
+            .activates_default(true)
+            .build();
+        dialog.set_extra_child(Some(&entry));
+
+        dialog.add_response("cancel", "Cancel");
+        dialog.add_response("ok", "OK");
+        dialog.set_response_appearance("ok", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("ok"));
+        dialog.set_close_response("cancel");
+
+        dialog.connect_response(None, move |_, response| {
+            let name = entry.text().trim().to_string();
+            if response == "ok" && !name.is_empty() {
+                on_done(name);
             }
         });
+
+        dialog.present(Some(widget));
+    }
+
+    /// Get the group a header row stands for
+    fn get_row_group(row: &ListBoxRow) -> Option<String> {
+        unsafe { row.data::<String>("pin_group").map(|g| g.as_ref().clone()) }
+    }
+
+    /// Tell the user about a failure, e.g. with a toast
+    fn report_to(on_error: &Callback<String>, message: String) {
+        log::warn!("{}", message);
+        if let Some(ref callback) = *on_error.borrow() {
+            callback(message);
+        }
+    }
+
+    /// Navigate to a pin. Network bookmarks are browsed in place, mounting them when opened.
+    fn open_pinned(pinned: &PinnedFolderObject, on_location_selected: &Callback<PathBuf>, on_error: &Callback<String>) {
+        if pinned.is_missing() {
+            Self::report_to(on_error, format!("Pinned folder is not available: {}", pinned.path_string()));
+            return;
+        }
+        let path = if pinned.is_remote() {
+            match Remote::parse_location(&pinned.path_string()) {
+                Some(path) => path,
+                None => {
+                    Self::report_to(on_error, format!("Pinned location is not supported: {}", pinned.path_string()));
+                    return;
+                }
+            }
+        } else {
+            pinned.path()
+        };
+        if let Some(ref callback) = *on_location_selected.borrow() {
+            callback(path);
+        }
     }
 
     /// Create a sidebar row widget
     fn create_sidebar_row(name: &str, icon_name: &str, path: &std::path::Path) -> ListBoxRow {
+        let row = Self::create_row_content(name, icon_name);
+        
+        // Store path in row data (unsafe but required for GTK data storage)
+        unsafe {
+            row.set_data("path", path.to_path_buf());
+        }
+        
+        row
+    }
+
+    /// Row with an icon and a label, the child being the GtkBox holding them
+    fn create_row_content(name: &str, icon_name: &str) -> ListBoxRow {
         let row = ListBoxRow::builder()
             .css_classes(["sidebar-row"])
             .build();
@@ -513,15 +919,340 @@ impl NautilusSidebar {
         hbox.append(&icon);
         hbox.append(&label);
         row.set_child(Some(&hbox));
-        
-        // Store path in row data (unsafe but required for GTK data storage)
+        row
+    }
+
+    /// Rebuild the drive list: the computer, mounted drives, then volumes that can be mounted
+    fn fill_drives(list_box: &ListBox) {
+        while let Some(row) = list_box.first_child() {
+            list_box.remove(&row);
+        }
+
+        let drives = DriveScanner::scan();
+        match drives.iter().find(|drive| drive.mount_point.as_deref() == Some(std::path::Path::new("/"))) {
+            Some(root) => {
+                let computer = DriveInfo {
+                    name: String::from("Computer"),
+                    icon_name: String::from("drive-harddisk-symbolic"),
+                    ..root.clone()
+                };
+                list_box.append(&Self::create_drive_row(list_box, &computer));
+            }
+            None => Self::add_standard_location(list_box, "Computer", "drive-harddisk-symbolic", 
+                PathBuf::from("/"), SidebarItemType::Drive),
+        }
+
+        let home = dirs::home_dir();
+        for drive in drives {
+            // The root and home directories are listed already
+            if drive.mount_point.as_deref() == Some(std::path::Path::new("/"))
+                || (drive.is_mounted() && drive.mount_point == home)
+            {
+                continue;
+            }
+            list_box.append(&Self::create_drive_row(list_box, &drive));
+        }
+
+        let connect_row = Self::create_row_content("Connect to Server…", "network-server-symbolic");
+        connect_row.set_action_name(Some("win.connect-to-server"));
+        list_box.append(&connect_row);
+
+        Self::refresh_drive_usage(list_box);
+    }
+
+    fn create_drive_row(list_box: &ListBox, drive: &DriveInfo) -> ListBoxRow {
+        let Some(mount_point) = &drive.mount_point else {
+            // Activating an unmounted volume mounts it
+            let row = Self::create_row_content(&drive.name, &drive.icon_name);
+            if !drive.device.is_empty() {
+                row.set_tooltip_text(Some(&format!("{} (not mounted)", drive.device)));
+            }
+            if let Some(volume) = &drive.volume {
+                unsafe {
+                    row.set_data("volume", volume.clone());
+                    row.set_data("item_type", SidebarItemType::Drive);
+                }
+            }
+            return row;
+        };
+
+        let row = Self::create_sidebar_row(&drive.name, &drive.icon_name, mount_point);
+
+        // Thin capacity bar under the name, shown once the usage is known
+        let usage_bar = gtk4::LevelBar::builder()
+            .min_value(0.0)
+            .max_value(1.0)
+            .visible(false)
+            .css_classes(["drive-usage"])
+            .build();
+        for offset in [gtk4::LEVEL_BAR_OFFSET_LOW, gtk4::LEVEL_BAR_OFFSET_HIGH, gtk4::LEVEL_BAR_OFFSET_FULL] {
+            usage_bar.remove_offset_value(Some(offset));
+        }
+        if let Some(hbox) = row.child().and_downcast::<GtkBox>() {
+            if let Some(label) = hbox.first_child().and_then(|icon| icon.next_sibling()) {
+                let name_box = GtkBox::builder()
+                    .orientation(Orientation::Vertical)
+                    .spacing(3)
+                    .valign(gtk4::Align::Center)
+                    .hexpand(true)
+                    .build();
+                hbox.remove(&label);
+                name_box.append(&label);
+                name_box.append(&usage_bar);
+                hbox.insert_child_after(&name_box, hbox.first_child().as_ref());
+            }
+        }
+
         unsafe {
-            row.set_data("path", path.to_path_buf());
+            row.set_data("item_type", SidebarItemType::Drive);
+            row.set_data("drive", drive.clone());
+            row.set_data("usage_bar", usage_bar);
         }
-        
+
+        if drive.can_eject() || drive.can_unmount() {
+            let tooltip = if drive.can_eject() { "Eject" } else { "Unmount" };
+            let button = gtk4::Button::builder()
+                .icon_name("media-eject-symbolic")
+                .tooltip_text(tooltip)
+                .valign(gtk4::Align::Center)
+                .css_classes(["flat", "circular", "sidebar-eject"])
+                .build();
+            let drive = drive.clone();
+            let list_box = list_box.downgrade();
+            button.connect_clicked(move |_| {
+                if let Some(list_box) = list_box.upgrade() {
+                    Self::eject_drive(&list_box, &drive);
+                }
+            });
+            if let Some(hbox) = row.child().and_downcast::<GtkBox>() {
+                hbox.append(&button);
+            }
+        }
+
         row
     }
 
+    /// Read the usage of every listed drive in the background and update the capacity bars
+    fn refresh_drive_usage(list_box: &ListBox) {
+        let worker = Self::drive_usage_worker(list_box);
+        // Requests made while a round is running fold into one more round after it
+        if worker.busy.get() {
+            worker.rerun.set(true);
+            return;
+        }
+        let mount_points: Vec<PathBuf> = (0..)
+            .map_while(|i| list_box.row_at_index(i))
+            .filter_map(|row| unsafe { row.data::<DriveInfo>("drive").and_then(|d| d.as_ref().mount_point.clone()) })
+            .collect();
+        if mount_points.is_empty() {
+            return;
+        }
+        if worker.requests.try_send(mount_points).is_ok() {
+            worker.busy.set(true);
+        }
+    }
+
+    /// The list's drive usage worker, started on first use
+    fn drive_usage_worker(list_box: &ListBox) -> DriveUsageWorker {
+        if let Some(worker) = unsafe { list_box.data::<DriveUsageWorker>("usage_worker").map(|w| w.as_ref().clone()) } {
+            return worker;
+        }
+
+        let (request_tx, request_rx) = async_channel::unbounded::<Vec<PathBuf>>();
+        let (result_tx, result_rx) = async_channel::unbounded::<Vec<(PathBuf, (u64, u64))>>();
+        // Ends once the list is gone and the request sender with it
+        std::thread::spawn(move || {
+            while let Ok(mount_points) = request_rx.recv_blocking() {
+                let usage = mount_points
+                    .into_iter()
+                    .filter_map(|mount_point| {
+                        let usage = DriveScanner::get_disk_usage_within(&mount_point, DISK_USAGE_TIMEOUT)?;
+                        Some((mount_point, usage))
+                    })
+                    .collect();
+                if result_tx.send_blocking(usage).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let worker = DriveUsageWorker {
+            requests: request_tx,
+            busy: Rc::new(std::cell::Cell::new(false)),
+            rerun: Rc::new(std::cell::Cell::new(false)),
+        };
+        {
+            // Not holding the request sender, so the thread can end with the list
+            let list_box = list_box.downgrade();
+            let busy = worker.busy.clone();
+            let rerun = worker.rerun.clone();
+            glib::spawn_future_local(async move {
+                while let Ok(usage) = result_rx.recv().await {
+                    busy.set(false);
+                    let Some(list_box) = list_box.upgrade() else { break };
+                    Self::apply_drive_usage(&list_box, &usage);
+                    if rerun.replace(false) {
+                        Self::refresh_drive_usage(&list_box);
+                    }
+                }
+            });
+        }
+        unsafe {
+            list_box.set_data("usage_worker", worker.clone());
+        }
+        worker
+    }
+
+    fn apply_drive_usage(list_box: &ListBox, usage: &[(PathBuf, (u64, u64))]) {
+        for row in (0..).map_while(|i| list_box.row_at_index(i)) {
+            let Some(mut drive) = (unsafe { row.data::<DriveInfo>("drive").map(|d| d.as_ref().clone()) }) else { continue };
+            let Some((_, (total, available))) = usage.iter().find(|(path, _)| drive.mount_point.as_ref() == Some(path)) else { continue };
+            drive.total_size = *total;
+            drive.available_size = *available;
+
+            if let Some(usage_bar) = unsafe { row.data::<gtk4::LevelBar>("usage_bar").map(|b| b.as_ref().clone()) } {
+                usage_bar.set_visible(drive.total_size > 0);
+                usage_bar.set_value(drive.used_fraction());
+                if drive.is_nearly_full() {
+                    usage_bar.add_css_class("warning");
+                } else {
+                    usage_bar.remove_css_class("warning");
+                }
+            }
+            let size_display = drive.size_display();
+            row.set_tooltip_text((!size_display.is_empty()).then_some(size_display.as_str()));
+            unsafe {
+                row.set_data("drive", drive);
+            }
+        }
+    }
+
+    /// Keep the drive list in step with the volume monitor
+    fn watch_drives(list_box: &ListBox) {
+        let monitor = gio::VolumeMonitor::get();
+        let pending = Rc::new(std::cell::Cell::new(false));
+
+        // Signals tend to arrive in bursts when a device is plugged in, so refresh once per burst
+        let refresh: Rc<dyn Fn()> = {
+            let list_box = list_box.downgrade();
+            Rc::new(move || {
+                if pending.replace(true) {
+                    return;
+                }
+                let list_box = list_box.clone();
+                let pending = pending.clone();
+                glib::idle_add_local_once(move || {
+                    pending.set(false);
+                    if let Some(list_box) = list_box.upgrade() {
+                        Self::fill_drives(&list_box);
+                    }
+                });
+            })
+        };
+
+        let handlers = vec![
+            monitor.connect_mount_added(clone!(#[strong] refresh, move |_, _| refresh())),
+            monitor.connect_mount_removed(clone!(#[strong] refresh, move |_, _| refresh())),
+            monitor.connect_mount_changed(clone!(#[strong] refresh, move |_, _| refresh())),
+            monitor.connect_volume_added(clone!(#[strong] refresh, move |_, _| refresh())),
+            monitor.connect_volume_removed(clone!(#[strong] refresh, move |_, _| refresh())),
+            monitor.connect_volume_changed(clone!(#[strong] refresh, move |_, _| refresh())),
+            monitor.connect_drive_connected(clone!(#[strong] refresh, move |_, _| refresh())),
+            monitor.connect_drive_disconnected(clone!(#[strong] refresh, move |_, _| refresh())),
+        ];
+
+        // Usage changes without any signal, so poll it
+        {
+            let list_box = list_box.downgrade();
+            glib::timeout_add_seconds_local(DISK_USAGE_INTERVAL_SECS, move || {
+                let Some(list_box) = list_box.upgrade() else {
+                    return glib::ControlFlow::Break;
+                };
+                Self::refresh_drive_usage(&list_box);
+                glib::ControlFlow::Continue
+            });
+        }
+
+        // The monitor is shared by every window, so drop our handlers with the sidebar
+        let handlers = RefCell::new(handlers);
+        list_box.connect_destroy(move |_| {
+            for handler in handlers.borrow_mut().drain(..) {
+                monitor.disconnect(handler);
+            }
+        });
+    }
+
+    /// Mount a volume and open it
+    fn mount_volume(
+        list_box: &ListBox,
+        volume: &gio::Volume,
+        on_location_selected: Callback<PathBuf>
+    ) {
+        let window = list_box.root().and_downcast::<gtk4::Window>();
+        let operation = gtk4::MountOperation::new(window.as_ref());
+        let list_box = list_box.downgrade();
+        let volume_clone = volume.clone();
+        volume.mount(
+            gio::MountMountFlags::NONE,
+            Some(&operation),
+            None::<&gio::Cancellable>,
+            move |result| match result {
+                Ok(()) => {
+                    let Some(path) = volume_clone.get_mount().and_then(|mount| mount.root().path()) else { return };
+                    if let Some(ref callback) = *on_location_selected.borrow() {
+                        callback(path);
+                    }
+                }
+                Err(e) => {
+                    log::warn!("Failed to mount {}: {}", volume_clone.name(), e);
+                    if let Some(list_box) = list_box.upgrade() {
+                        Self::show_drive_error(&list_box, &format!("Unable to mount {}", volume_clone.name()), &e);
+                    }
+                }
+            },
+        );
+    }
+
+    /// Eject removable media, or unmount it if it can't be ejected.
+    /// When applications still use the device, GTK lists them and offers to unmount anyway.
+    fn eject_drive(list_box: &ListBox, drive: &DriveInfo) {
+        let Some(mount) = &drive.mount else { return };
+        let window = list_box.root().and_downcast::<gtk4::Window>();
+        let operation = gtk4::MountOperation::new(window.as_ref());
+        let eject = drive.can_eject();
+        let name = drive.name.clone();
+        let list_box = list_box.downgrade();
+        let on_done = move |result: Result<(), glib::Error>| {
+            let Err(e) = result else { return };
+            log::warn!("Failed to {} {}: {}", if eject { "eject" } else { "unmount" }, name, e);
+            if let Some(list_box) = list_box.upgrade() {
+                let heading = format!("Unable to {} {}", if eject { "eject" } else { "unmount" }, name);
+                Self::show_drive_error(&list_box, &heading, &e);
+            }
+        };
+
+        if eject {
+            mount.eject_with_operation(gio::MountUnmountFlags::NONE, Some(&operation), None::<&gio::Cancellable>, on_done);
+        } else {
+            mount.unmount_with_operation(gio::MountUnmountFlags::NONE, Some(&operation), None::<&gio::Cancellable>, on_done);
+        }
+    }
+
+    fn show_drive_error(widget: &impl IsA<gtk4::Widget>, heading: &str, error: &glib::Error) {
+        use libadwaita as adw;
+        use adw::prelude::*;
+
+        // Errors the mount operation already showed, like the busy-device dialog, aren't repeated
+        let Some(body) = DriveScanner::error_message(error) else { return };
+        let dialog = adw::AlertDialog::builder()
+            .heading(heading)
+            .body(body)
+            .build();
+        dialog.add_response("close", "OK");
+        dialog.set_close_response("close");
+        dialog.present(Some(widget));
+    }
+
     /// Add a standard location to a ListBox
     fn add_standard_location(
         list_box: &ListBox, 
@@ -551,25 +1282,22 @@ impl NautilusSidebar {
     fn setup_pinned_row_context_menu(
         row: &ListBoxRow, 
         pinned_obj: &PinnedFolderObject,
-        store: &PinnedFolderStore
+        store: &PinnedFolderStore,
+        on_error: &Callback<String>,
     ) {
-        // #region agent log
-        debug_log("A", "sidebar.rs:setup_pinned_row_context_menu", "Function entry", serde_json::json!({
-            "row_ptr": format!("{:p}", row),
-            "path": pinned_obj.path().to_string_lossy()
-        }));
-        // #endregion
-        
         let gesture = GestureClick::builder().button(3).build();
         let current_popover: Rc<RefCell<Option<PopoverMenu>>> = Rc::new(RefCell::new(None));
         
         let path = pinned_obj.path();
         let store_clone = store.clone();
+        let pinned_obj = pinned_obj.clone();
+        let on_error = on_error.clone();
         
         gesture.connect_pressed(clone!(
             #[strong] current_popover,
             #[strong] path,
             #[strong] store_clone,
+            #[strong] pinned_obj,
             #[weak] row,
             move |_, _, x, y| {
                 // Close existing popover
@@ -590,13 +1318,49 @@ impl NautilusSidebar {
                 );
                 menu.append_item(&rename_item);
                 
+                // Appearance and grouping
+                let style_section = gio::Menu::new();
+                let icon_menu = gio::Menu::new();
+                for (label, icon_name) in PIN_ICONS {
+                    let item = gio::MenuItem::new(Some(label), None);
+                    item.set_action_and_target_value(Some("sidebar.pin-icon"), Some(&icon_name.to_variant()));
+                    icon_menu.append_item(&item);
+                }
+                style_section.append_submenu(Some("Icon"), &icon_menu);
+
+                let color_menu = gio::Menu::new();
+                let default_item = gio::MenuItem::new(Some("Default"), None);
+                default_item.set_action_and_target_value(Some("sidebar.pin-color"), Some(&"".to_variant()));
+                color_menu.append_item(&default_item);
+                for color in PinColor::ALL {
+                    let item = gio::MenuItem::new(Some(color.title()), None);
+                    item.set_action_and_target_value(Some("sidebar.pin-color"), Some(&color.id().to_variant()));
+                    color_menu.append_item(&item);
+                }
+                style_section.append_submenu(Some("Colour"), &color_menu);
+
+                let group_menu = gio::Menu::new();
+                let ungrouped_item = gio::MenuItem::new(Some("No Group"), None);
+                ungrouped_item.set_action_and_target_value(Some("sidebar.pin-group"), Some(&"".to_variant()));
+                group_menu.append_item(&ungrouped_item);
+                for group in store_clone.groups() {
+                    let item = gio::MenuItem::new(Some(&group.name), None);
+                    item.set_action_and_target_value(Some("sidebar.pin-group"), Some(&group.name.to_variant()));
+                    group_menu.append_item(&item);
+                }
+                group_menu.append(Some("New Group…"), Some("sidebar.pin-new-group"));
+                style_section.append_submenu(Some("Move to Group"), &group_menu);
+                menu.append_section(None, &style_section);
+
                 // Unpin action
+                let unpin_section = gio::Menu::new();
                 let unpin_item = gio::MenuItem::new(Some("Unpin from Sidebar"), None);
                 unpin_item.set_action_and_target_value(
                     Some("app.toggle-pin"),
                     Some(&path.to_string_lossy().to_string().to_variant())
                 );
-                menu.append_item(&unpin_item);
+                unpin_section.append_item(&unpin_item);
+                menu.append_section(None, &unpin_section);
 
                 let popover = PopoverMenu::from_model(Some(&menu));
                 popover.set_parent(&row);
@@ -620,6 +1384,65 @@ impl NautilusSidebar {
                     }
                 });
                 action_group.add_action(&rename_action);
+
+                let icon_action = gio::SimpleAction::new("pin-icon", Some(&String::static_variant_type()));
+                {
+                    let store = store_clone.clone();
+                    let pinned_obj = pinned_obj.clone();
+                    icon_action.connect_activate(move |_, param| {
+                        let Some(icon_name) = param.and_then(|p| p.get::<String>()) else { return };
+                        let icon_name = Some(icon_name).filter(|name| !name.is_empty());
+                        store.set_style(&pinned_obj.path(), PinStyle { icon_name, ..pinned_obj.style() });
+                    });
+                }
+                action_group.add_action(&icon_action);
+
+                let color_action = gio::SimpleAction::new("pin-color", Some(&String::static_variant_type()));
+                {
+                    let store = store_clone.clone();
+                    let pinned_obj = pinned_obj.clone();
+                    color_action.connect_activate(move |_, param| {
+                        let Some(id) = param.and_then(|p| p.get::<String>()) else { return };
+                        let color = PinColor::from_id(&id);
+                        store.set_style(&pinned_obj.path(), PinStyle { color, ..pinned_obj.style() });
+                    });
+                }
+                action_group.add_action(&color_action);
+
+                let group_action = gio::SimpleAction::new("pin-group", Some(&String::static_variant_type()));
+                {
+                    let store = store_clone.clone();
+                    let pinned_obj = pinned_obj.clone();
+                    let on_error = on_error.clone();
+                    group_action.connect_activate(move |_, param| {
+                        let Some(group) = param.and_then(|p| p.get::<String>()) else { return };
+                        let group = Some(group).filter(|name| !name.is_empty());
+                        if let Err(e) = store.move_pin(&pinned_obj.path(), None, group) {
+                            Self::report_to(&on_error, format!("Failed to move pin: {}", e));
+                        }
+                    });
+                }
+                action_group.add_action(&group_action);
+
+                let new_group_action = gio::SimpleAction::new("pin-new-group", None);
+                {
+                    let store = store_clone.clone();
+                    let pinned_obj = pinned_obj.clone();
+                    let row = row.clone();
+                    let on_error = on_error.clone();
+                    new_group_action.connect_activate(move |_, _| {
+                        let store = store.clone();
+                        let pinned_obj = pinned_obj.clone();
+                        let on_error = on_error.clone();
+                        Self::prompt_name(&row, "New Group", "", move |name| {
+                            store.add_group(&name);
+                            if let Err(e) = store.move_pin(&pinned_obj.path(), None, Some(name)) {
+                                Self::report_to(&on_error, format!("Failed to move pin: {}", e));
+                            }
+                        });
+                    });
+                }
+                action_group.add_action(&new_group_action);
                 
                 popover.insert_action_group("sidebar", Some(&action_group));
                 
@@ -635,53 +1458,15 @@ impl NautilusSidebar {
             }
         ));
         
-        // #region agent log
-        debug_log("A", "sidebar.rs:setup_pinned_row_context_menu", "Before add_controller", serde_json::json!({
-            "row_ptr": format!("{:p}", row),
-            "gesture_ptr": format!("{:p}", &gesture),
-            "has_widget": gesture.widget().is_some()
-        }));
-        // #endregion
-        
         row.add_controller(gesture);
-        
-        // #region agent log
-        debug_log("A", "sidebar.rs:setup_pinned_row_context_menu", "After add_controller", serde_json::json!({
-            "row_ptr": format!("{:p}", row)
-        }));
-        // #endregion
     }
 
     /// Setup context menu for standard/other locations
     fn setup_standard_context_menu(list_box: &ListBox, _store: &PinnedFolderStore) {
-        // #region agent log
-        debug_log("C", "sidebar.rs:setup_standard_context_menu", "Function entry", serde_json::json!({
-            "list_box_ptr": format!("{:p}", list_box)
-        }));
-        // #endregion
-        
         let gesture = GestureClick::builder().button(3).build();
         
-        // #region agent log
-        let widget_after_create = gesture.widget();
-        debug_log("D", "sidebar.rs:setup_standard_context_menu", "After creating gesture", serde_json::json!({
-            "list_box_ptr": format!("{:p}", list_box),
-            "gesture_ptr": format!("{:p}", &gesture),
-            "has_widget": widget_after_create.is_some(),
-            "widget_type": widget_after_create.as_ref().map(|w| format!("{:?}", w.type_()))
-        }));
-        // #endregion
-        
         let current_popover: Rc<RefCell<Option<PopoverMenu>>> = Rc::new(RefCell::new(None));
         
-        // #region agent log
-        let widget_before_connect = gesture.widget();
-        debug_log("F", "sidebar.rs:setup_standard_context_menu", "Before connect_pressed", serde_json::json!({
-            "gesture_ptr": format!("{:p}", &gesture),
-            "has_widget": widget_before_connect.is_some()
-        }));
-        // #endregion
-        
         gesture.connect_pressed(clone!(
             #[strong] current_popover,
             move |gesture, _, x, y| {
@@ -708,7 +1493,7 @@ impl NautilusSidebar {
                 }
                 
                 let Some(row) = found_row else { return };
-                let Some(_path) = Self::get_row_path(&row) else { return };
+                let Some(path) = Self::get_row_path(&row) else { return };
                 let item_type = Self::get_row_item_type(&row);
                 
                 // Don't show menu for system folders (like Trash)
@@ -716,78 +1501,35 @@ impl NautilusSidebar {
                     return;
                 }
 
-                // No context menu items available
-                return;
+                let menu = gio::Menu::new();
+                let analyze_item = gio::MenuItem::new(Some("Analyze Disk Usage"), None);
+                analyze_item.set_action_and_target_value(
+                    Some("win.analyze-disk-usage"),
+                    Some(&path.to_string_lossy().to_string().to_variant())
+                );
+                menu.append_item(&analyze_item);
+
+                // The click was on the list; point at the same spot in the row
+                let point = widget
+                    .compute_point(&row, &gtk4::graphene::Point::new(x as f32, y as f32))
+                    .unwrap_or_else(|| gtk4::graphene::Point::new(x as f32, y as f32));
+                let popover = PopoverMenu::from_model(Some(&menu));
+                popover.set_parent(&row);
+                popover.set_position(gtk4::PositionType::Bottom);
+                popover.set_pointing_to(Some(&gtk4::gdk::Rectangle::new(point.x() as i32, point.y() as i32, 1, 1)));
+
+                let current_popover_clone = current_popover.clone();
+                popover.connect_closed(move |p| {
+                    p.unparent();
+                    current_popover_clone.borrow_mut().take();
+                });
+
+                *current_popover.borrow_mut() = Some(popover.clone());
+                popover.popup();
             }
         ));
         
-        // #region agent log
-        let widget_after_connect = gesture.widget();
-        debug_log("F", "sidebar.rs:setup_standard_context_menu", "After connect_pressed", serde_json::json!({
-            "gesture_ptr": format!("{:p}", &gesture),
-            "has_widget": widget_after_connect.is_some(),
-            "widget_type": widget_after_connect.as_ref().map(|w| format!("{:?}", w.type_()))
-        }));
-        // #endregion
-        
-        // #region agent log
-        let widget_before = gesture.widget();
-        let controllers_count = list_box.observe_controllers().n_items();
-        
-        // Check if this gesture is already in the list_box controllers
-        let (gesture_already_in_list, existing_gesture_click_count, existing_gesture_has_widget) = {
-            let mut found = false;
-            let mut gesture_click_count = 0;
-            let mut has_widget_count = 0;
-            for i in 0..controllers_count {
-                if let Some(controller) = list_box.observe_controllers().item(i) {
-                    if let Ok(existing_gesture) = controller.downcast::<GestureClick>() {
-                        gesture_click_count += 1;
-                        if existing_gesture.widget().is_some() {
-                            has_widget_count += 1;
-                        }
-                        if existing_gesture.as_ref() as *const _ == &gesture as *const _ {
-                            found = true;
-                            break;
-                        }
-                    }
-                }
-            }
-            (found, gesture_click_count, has_widget_count)
-        };
-        
-        debug_log("C", "sidebar.rs:setup_standard_context_menu", "Before add_controller", serde_json::json!({
-            "list_box_ptr": format!("{:p}", list_box),
-            "gesture_ptr": format!("{:p}", &gesture),
-            "has_widget": widget_before.is_some(),
-            "widget_type": widget_before.as_ref().map(|w| format!("{:?}", w.type_())),
-            "list_box_controllers_count": controllers_count,
-            "gesture_already_in_list": gesture_already_in_list,
-            "existing_gesture_click_count": existing_gesture_click_count,
-            "existing_gesture_has_widget": existing_gesture_has_widget
-        }));
-        // #endregion
-        
-        // Check if gesture already has a widget - this would cause the GTK assertion error
-        if widget_before.is_some() {
-            eprintln!("ERROR: Gesture already has widget before add_controller! widget={:?}", widget_before);
-            return; // Don't add controller if it already has a widget
-        }
-        
-        // Check if gesture is already in the list_box - this would also cause the GTK assertion error
-        if gesture_already_in_list {
-            eprintln!("ERROR: Gesture is already in list_box controllers! Not adding again.");
-            return; // Don't add controller if it's already in the list
-        }
-        
         list_box.add_controller(gesture);
-        
-        // #region agent log
-        debug_log("C", "sidebar.rs:setup_standard_context_menu", "After add_controller", serde_json::json!({
-            "list_box_ptr": format!("{:p}", list_box),
-            "controllers_count": list_box.observe_controllers().n_items()
-        }));
-        // #endregion
     }
 
     /// Show rename dialog for pinned folder
@@ -852,7 +1594,7 @@ impl NautilusSidebar {
                     let new_name = entry.text().to_string().trim().to_string();
                     if !new_name.is_empty() && new_name != current_name_clone {
                         if let Err(e) = store_clone.rename(&path_clone, &new_name) {
-                            eprintln!("Failed to rename: {}", e);
+                            log::warn!("Failed to rename: {}", e);
                         }
                     }
                 }
@@ -881,10 +1623,39 @@ impl NautilusSidebar {
         }
     }
 
+    /// Show the most frecent locations, best first
+    pub fn set_recent_locations(&self, paths: &[PathBuf]) {
+        while let Some(row) = self.recent_list_box.first_child() {
+            self.recent_list_box.remove(&row);
+        }
+
+        for path in paths {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string_lossy().to_string());
+            let row = Self::create_sidebar_row(&name, "document-open-recent-symbolic", path);
+            row.set_tooltip_text(Some(&path.to_string_lossy()));
+            unsafe {
+                row.set_data("item_type", SidebarItemType::StandardFolder);
+            }
+            self.recent_list_box.append(&row);
+        }
+
+        let visible = !paths.is_empty();
+        self.recent_label.set_visible(visible);
+        self.recent_list_box.set_visible(visible);
+    }
+
     pub fn connect_location_selected<F: Fn(PathBuf) + 'static>(&self, callback: F) {
         *self.on_location_selected.borrow_mut() = Some(Box::new(callback));
     }
 
+    /// Called with a message when something the user asked for fails
+    pub fn connect_error<F: Fn(String) + 'static>(&self, callback: F) {
+        *self.on_error.borrow_mut() = Some(Box::new(callback));
+    }
+
     pub fn refresh(&self) {
         // The ListStore binding automatically updates the UI when the store changes
         // This method is kept for API compatibility but may not need to do anything
@@ -894,13 +1665,18 @@ impl NautilusSidebar {
         self.pinned_list_box.invalidate_filter();
     }
 
+    /// Update the drive capacity bars, e.g. after files were copied or deleted
+    pub fn refresh_disk_usage(&self) {
+        Self::refresh_drive_usage(&self.other_list_box);
+    }
+
     pub fn unpin_selected(&self) {
         if let Some(row) = self.pinned_list_box.selected_row() {
             if let Some(path) = Self::get_row_path(&row) {
                 if let Err(e) = self.pinned_store.remove(&path) {
-                    eprintln!("Failed to unpin: {}", e);
+                    log::warn!("Failed to unpin: {}", e);
                 } else {
-                    println!("[DEBUG] Unpinned folder via method: {:?}", path);
+                    log::debug!("Unpinned folder via method: {:?}", path);
                 }
             }
         }
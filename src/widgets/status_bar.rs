@@ -0,0 +1,283 @@
+use gtk4::prelude::*;
+use gtk4::{glib, Box as GtkBox, Label, Orientation};
+use libadwaita as adw;
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::core::{format_size, DiskUsageScanner, DriveInfo, DriveScanner};
+
+/// Wait before sizing a selection, so dragging out a rubber band doesn't start a scan per item
+const SELECTION_SIZE_DELAY_MS: u64 = 250;
+/// How often a running selection size is shown
+const SELECTION_SIZE_REFRESH_MS: u64 = 200;
+
+/// Strip along the bottom of the file view with details about the current folder
+#[derive(Clone)]
+pub struct StatusBar {
+    container: GtkBox,
+    items_label: Label,
+    selection_label: Label,
+    spinner: adw::Spinner,
+    activity_label: Label,
+    free_space_label: Label,
+    free_space_id: Rc<RefCell<u64>>,
+    /// Bumped on every selection change; a running size calculation stops when it no longer matches
+    selection_id: Rc<RefCell<u64>>,
+    selection_cancel: Rc<RefCell<Arc<AtomicBool>>>,
+    /// Background work in progress, newest last
+    activities: Rc<RefCell<Vec<(u64, String)>>>,
+    next_activity_id: Rc<RefCell<u64>>,
+    loading_activity: Rc<RefCell<Option<u64>>>,
+}
+
+impl StatusBar {
+    pub fn new() -> Self {
+        let container = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(12)
+            .margin_start(12)
+            .margin_end(12)
+            .margin_top(4)
+            .margin_bottom(4)
+            .css_classes(["status-bar"])
+            .build();
+
+        let items_label = Label::builder()
+            .css_classes(["dim-label", "caption"])
+            .build();
+        container.append(&items_label);
+
+        let selection_label = Label::builder()
+            .visible(false)
+            .css_classes(["caption"])
+            .build();
+        container.append(&selection_label);
+
+        // Pushes the activity and free space to the far end
+        let spacer = GtkBox::builder().hexpand(true).build();
+        container.append(&spacer);
+
+        let spinner = adw::Spinner::builder()
+            .visible(false)
+            .build();
+        container.append(&spinner);
+
+        let activity_label = Label::builder()
+            .visible(false)
+            .ellipsize(gtk4::pango::EllipsizeMode::End)
+            .css_classes(["dim-label", "caption"])
+            .build();
+        container.append(&activity_label);
+
+        let free_space_label = Label::builder()
+            .visible(false)
+            .css_classes(["dim-label", "caption"])
+            .build();
+        container.append(&free_space_label);
+
+        Self {
+            container,
+            items_label,
+            selection_label,
+            spinner,
+            activity_label,
+            free_space_label,
+            free_space_id: Rc::new(RefCell::new(0)),
+            selection_id: Rc::new(RefCell::new(0)),
+            selection_cancel: Rc::new(RefCell::new(Arc::new(AtomicBool::new(false)))),
+            activities: Rc::new(RefCell::new(Vec::new())),
+            next_activity_id: Rc::new(RefCell::new(0)),
+            loading_activity: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    pub fn container(&self) -> &GtkBox {
+        &self.container
+    }
+
+    /// Show how many items the folder has, and how many of them are hidden
+    pub fn show_items(&self, count: u32, hidden: usize) {
+        let items = if count == 1 { "1 item".to_string() } else { format!("{} items", count) };
+        if hidden > 0 {
+            self.items_label.set_text(&format!("{} ({} hidden)", items, hidden));
+        } else {
+            self.items_label.set_text(&items);
+        }
+    }
+
+    /// Show the number and total size of the selected items. Folders are measured
+    /// recursively in the background; a new selection cancels the previous measurement.
+    pub fn show_selection(&self, paths: Vec<PathBuf>) {
+        let mut id_guard = self.selection_id.borrow_mut();
+        *id_guard += 1;
+        let id = *id_guard;
+        drop(id_guard);
+        self.selection_cancel.borrow().store(true, Ordering::Relaxed);
+
+        if paths.is_empty() {
+            self.selection_label.set_visible(false);
+            return;
+        }
+        let count = format!("{} selected", paths.len());
+        self.selection_label.set_text(&count);
+        self.selection_label.set_visible(true);
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.selection_cancel.replace(cancel.clone());
+
+        let status_bar = self.clone();
+        glib::timeout_add_local_once(Duration::from_millis(SELECTION_SIZE_DELAY_MS), move || {
+            if *status_bar.selection_id.borrow() != id {
+                return;
+            }
+
+            let total = Arc::new(AtomicU64::new(0));
+            let done = Arc::new(AtomicBool::new(false));
+            {
+                let total = total.clone();
+                let done = done.clone();
+                let cancel = cancel.clone();
+                thread::spawn(move || {
+                    for path in &paths {
+                        let Ok(metadata) = fs::symlink_metadata(path) else { continue };
+                        if metadata.is_dir() {
+                            DiskUsageScanner::walk(path, false, &cancel, |listing| {
+                                let size: u64 = listing.entries.iter().filter(|e| !e.is_dir).map(|e| e.size).sum();
+                                total.fetch_add(size, Ordering::Relaxed);
+                            });
+                        } else {
+                            total.fetch_add(metadata.len(), Ordering::Relaxed);
+                        }
+                        if cancel.load(Ordering::Relaxed) {
+                            break;
+                        }
+                    }
+                    done.store(true, Ordering::Relaxed);
+                });
+            }
+
+            // Most selections are sized within a tick; only long ones get the spinner
+            let activity: Rc<RefCell<Option<u64>>> = Rc::new(RefCell::new(None));
+            let label = status_bar.selection_label.downgrade();
+            let status_bar = status_bar.clone();
+            glib::timeout_add_local(Duration::from_millis(SELECTION_SIZE_REFRESH_MS), move || {
+                let current = *status_bar.selection_id.borrow() == id;
+                let finished = done.load(Ordering::Relaxed);
+                if let Some(label) = label.upgrade().filter(|_| current) {
+                    let size = format_size(total.load(Ordering::Relaxed));
+                    if finished {
+                        label.set_text(&format!("{}, {}", count, size));
+                    } else {
+                        label.set_text(&format!("{}, {}…", count, size));
+                    }
+                }
+
+                if !current || finished {
+                    if let Some(activity_id) = activity.borrow_mut().take() {
+                        status_bar.end_activity(activity_id);
+                    }
+                    return glib::ControlFlow::Break;
+                }
+                if activity.borrow().is_none() {
+                    activity.replace(Some(status_bar.begin_activity("Calculating size…")));
+                }
+                glib::ControlFlow::Continue
+            });
+        });
+    }
+
+    /// Show the spinner with `message` until `end_activity` is called with the returned id.
+    /// When several activities overlap, the newest message is shown.
+    pub fn begin_activity(&self, message: &str) -> u64 {
+        let mut id_guard = self.next_activity_id.borrow_mut();
+        *id_guard += 1;
+        let id = *id_guard;
+        drop(id_guard);
+
+        self.activities.borrow_mut().push((id, message.to_string()));
+        self.update_activity();
+        id
+    }
+
+    pub fn end_activity(&self, id: u64) {
+        self.activities.borrow_mut().retain(|(activity_id, _)| *activity_id != id);
+        self.update_activity();
+    }
+
+    /// Show or hide the spinner for a directory being read
+    pub fn set_loading(&self, loading: bool) {
+        let previous = self.loading_activity.borrow_mut().take();
+        if let Some(id) = previous {
+            self.end_activity(id);
+        }
+        if loading {
+            let id = self.begin_activity("Loading…");
+            self.loading_activity.replace(Some(id));
+        }
+    }
+
+    fn update_activity(&self) {
+        let activities = self.activities.borrow();
+        let message = activities.last().map(|(_, message)| message.as_str());
+        self.spinner.set_visible(message.is_some());
+        self.activity_label.set_visible(message.is_some());
+        self.activity_label.set_text(message.unwrap_or_default());
+    }
+
+    /// Show the free space on the filesystem holding `path`, read in the background
+    pub fn show_free_space(&self, path: &Path) {
+        let mut id_guard = self.free_space_id.borrow_mut();
+        *id_guard += 1;
+        let id = *id_guard;
+        drop(id_guard);
+
+        // Virtual locations like the Recent list have no filesystem
+        if !path.is_absolute() {
+            self.free_space_label.set_visible(false);
+            return;
+        }
+
+        // Archive members live below the archive, so measure the nearest real directory
+        let path: PathBuf = path.to_path_buf();
+        let (tx, rx) = async_channel::bounded::<(u64, u64)>(1);
+        thread::spawn(move || {
+            let usage = path
+                .ancestors()
+                .find(|ancestor| ancestor.is_dir())
+                .map(DriveScanner::get_disk_usage)
+                .unwrap_or((0, 0));
+            let _ = tx.send_blocking(usage);
+        });
+
+        let label = self.free_space_label.downgrade();
+        let free_space_id = self.free_space_id.clone();
+        glib::spawn_future_local(async move {
+            let Ok((total, available)) = rx.recv().await else { return };
+            let Some(label) = label.upgrade() else { return };
+            if *free_space_id.borrow() != id {
+                return;
+            }
+
+            label.set_visible(total > 0);
+            label.set_text(&format!("{} free of {}", format_size(available), format_size(total)));
+            let used = total.saturating_sub(available) as f64 / total.max(1) as f64;
+            if used >= DriveInfo::NEARLY_FULL {
+                label.add_css_class("warning");
+            } else {
+                label.remove_css_class("warning");
+            }
+        });
+    }
+}
+
+impl Default for StatusBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
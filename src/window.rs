@@ -7,52 +7,61 @@ use std::cell::RefCell;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
-use std::fs::OpenOptions;
-use std::io::Write;
 use std::thread;
 use std::time::Duration;
 use async_channel;
 
-use crate::core::{Clipboard, ClipboardMode, FileOperations, SidebarPrefs, ProgressInfo};
-use crate::widgets::{FileGridView, NautilusHeaderBar, NautilusSidebar};
-
-// #region agent log
-fn debug_log(hypothesis_id: &str, location: &str, message: &str, data: serde_json::Value) {
-    let log_entry = serde_json::json!({
-        "sessionId": "debug-session",
-        "runId": "run1",
-        "hypothesisId": hypothesis_id,
-        "location": location,
-        "message": message,
-        "data": data,
-        "timestamp": std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis()
-    });
-    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open("/home/artwik/.config/alloy/.cursor/debug.log") {
-        let _ = writeln!(file, "{}", log_entry);
-    }
+use crate::core::{Archive, ArchiveFormat, Clipboard, ClipboardMode, ConflictResolution, DriveScanner, FileOperations, Logger, NavigationHistory, RecentLocations, Remote, SidebarPrefs, Vfs, ProgressInfo, SortKey, ViewMode, WindowState};
+use crate::widgets::{ConnectServerDialog, DiskUsageWindow, ErrorReport, FileGridView, NautilusHeaderBar, NautilusSidebar, PreviewPane, PropertiesDialog, StatusBar};
+
+/// Recent locations offered by the path entry
+const RECENT_LOCATIONS_IN_ENTRY: usize = 20;
+/// Recent locations listed in the sidebar
+const RECENT_LOCATIONS_IN_SIDEBAR: usize = 5;
+
+/// What a paste copies or moves, and where to
+struct PasteJob {
+    paths: Vec<PathBuf>,
+    dest: PathBuf,
+    mode: ClipboardMode,
+}
+
+thread_local! {
+    /// Recent locations shared by every window; read from disk once per run
+    static RECENT_LOCATIONS: Rc<RefCell<RecentLocations>> = Rc::new(RefCell::new(RecentLocations::load()));
 }
-// #endregion
 
+#[derive(Clone)]
 pub struct BlinkWindow {
     pub window: adw::ApplicationWindow,
+    file_view: FileGridView,
+    current_path: Rc<RefCell<PathBuf>>,
+    history: Rc<RefCell<NavigationHistory>>,
 }
 
 impl BlinkWindow {
-    pub fn new(app: &adw::Application) -> Self {
+    /// Open a window, optionally restoring one from the previous session
+    pub fn new(app: &adw::Application, restore: Option<WindowState>) -> Self {
         // Create main window - Nautilus style
         let window = adw::ApplicationWindow::builder()
             .application(app)
             .title("Files")
-            .default_width(1100)
-            .default_height(700)
+            .default_width(restore.as_ref().map(|state| state.width).unwrap_or(1100))
+            .default_height(restore.as_ref().map(|state| state.height).unwrap_or(700))
             .build();
+        if restore.as_ref().is_some_and(|state| state.maximized) {
+            window.maximize();
+        }
 
         // Shared state
         let current_path: Rc<RefCell<PathBuf>> = Rc::new(RefCell::new(
-            dirs::home_dir().unwrap_or_else(|| PathBuf::from("/")),
+            restore
+                .as_ref()
+                .map(|state| state.location.clone())
+                .or_else(dirs::home_dir)
+                .unwrap_or_else(|| PathBuf::from("/")),
         ));
-        let history: Rc<RefCell<Vec<PathBuf>>> = Rc::new(RefCell::new(Vec::new()));
-        let history_index: Rc<RefCell<i32>> = Rc::new(RefCell::new(-1));
+        let history: Rc<RefCell<NavigationHistory>> = Rc::new(RefCell::new(NavigationHistory::default()));
         let clipboard: Rc<RefCell<Clipboard>> = Rc::new(RefCell::new(Clipboard::new()));
 
         // Create header bar (Nautilus style)
@@ -81,18 +90,47 @@ impl BlinkWindow {
         content_box.append(header_bar.container());
 
         // File grid view with scroll
-        let file_view = FileGridView::new();
+        let vfs = Vfs::default();
+        let file_view = FileGridView::new(vfs.clone());
         let scrolled = ScrolledWindow::builder()
             .vexpand(true)
             .hexpand(true)
             .child(file_view.container())
             .build();
         scrolled.add_css_class("nautilus-scrolled");
-        content_box.append(&scrolled);
+
+        // Preview pane on the right, toggled with F3
+        let preview_pane = PreviewPane::new(vfs.clone());
+        preview_pane.set_visible(false);
+        let content_paned = gtk4::Paned::builder()
+            .orientation(Orientation::Horizontal)
+            .start_child(&scrolled)
+            .end_child(preview_pane.container())
+            .resize_start_child(true)
+            .resize_end_child(false)
+            .shrink_end_child(false)
+            .vexpand(true)
+            .build();
+        content_box.append(&content_paned);
+
+        let status_bar = StatusBar::new();
+        content_box.append(status_bar.container());
+
+        // Recoverable errors show up as toasts over the content
+        let toast_overlay = adw::ToastOverlay::new();
+        toast_overlay.set_child(Some(&content_box));
+        {
+            let toast_overlay = toast_overlay.clone();
+            file_view.connect_error(move |message| Self::show_error_toast(&toast_overlay, &message));
+        }
+        {
+            let toast_overlay = toast_overlay.clone();
+            sidebar.connect_error(move |message| Self::show_error_toast(&toast_overlay, &message));
+        }
 
         let content_page = adw::NavigationPage::builder()
             .title("Files")
-            .child(&content_box)
+            .child(&toast_overlay)
             .build();
         split_view.set_content(Some(&content_page));
 
@@ -104,22 +142,48 @@ impl BlinkWindow {
         // Keyboard shortcuts
         {
             let file_view_clone = file_view.clone();
+            let preview_pane_clone = preview_pane.clone();
             let sidebar_clone = sidebar.clone();
-            let last_show_hidden_clone = last_show_hidden.clone();
             let key_controller = gtk4::EventControllerKey::new();
-            key_controller.connect_key_pressed(move |_, keyval, modifiers, _| {
-                // Ctrl+H to toggle hidden files
+            key_controller.connect_key_pressed(move |controller, keyval, modifiers, _| {
+                // Ctrl+H to toggle hidden files (remembered for this folder)
                 if keyval == gtk4::gdk::Key::h && modifiers & gtk4::gdk::ModifierType::CONTROL_MASK.bits() != 0 {
-                    let mut current_hidden = last_show_hidden_clone.borrow_mut();
-                    let new_hidden = !*current_hidden;
-                    *current_hidden = new_hidden;
-                    
-                    file_view_clone.set_show_hidden(new_hidden);
-                    file_view_clone.refresh();
-                    println!("Toggled hidden files (session): {}", new_hidden);
+                    file_view_clone.toggle_show_hidden();
                     return gtk4::glib::Propagation::Stop;
                 }
                 
+                // Alt+Left / Alt+Right to go back/forward
+                if modifiers & gtk4::gdk::ModifierType::ALT_MASK.bits() != 0 {
+                    let action_name = match keyval {
+                        gtk4::gdk::Key::Left => Some("win.go-back"),
+                        gtk4::gdk::Key::Right => Some("win.go-forward"),
+                        _ => None,
+                    };
+                    if let (Some(action_name), Some(widget)) = (action_name, controller.widget()) {
+                        let _ = widget.activate_action(action_name, None);
+                        return gtk4::glib::Propagation::Stop;
+                    }
+                }
+
+                // Ctrl+= / Ctrl+- / Ctrl+0 to zoom
+                if modifiers & gtk4::gdk::ModifierType::CONTROL_MASK.bits() != 0 {
+                    match keyval {
+                        gtk4::gdk::Key::equal | gtk4::gdk::Key::plus | gtk4::gdk::Key::KP_Add => {
+                            file_view_clone.zoom_in();
+                            return gtk4::glib::Propagation::Stop;
+                        }
+                        gtk4::gdk::Key::minus | gtk4::gdk::Key::KP_Subtract => {
+                            file_view_clone.zoom_out();
+                            return gtk4::glib::Propagation::Stop;
+                        }
+                        gtk4::gdk::Key::_0 | gtk4::gdk::Key::KP_0 => {
+                            file_view_clone.reset_zoom();
+                            return gtk4::glib::Propagation::Stop;
+                        }
+                        _ => {}
+                    }
+                }
+
                 // Key 'o' to unpin selected item in sidebar
                 if keyval == gtk4::gdk::Key::o {
                     sidebar_clone.unpin_selected();
@@ -131,6 +195,16 @@ impl BlinkWindow {
                     file_view_clone.rename_selected();
                     return gtk4::glib::Propagation::Stop;
                 }
+
+                // F3 to toggle the preview pane
+                if keyval == gtk4::gdk::Key::F3 {
+                    let visible = !preview_pane_clone.is_visible();
+                    preview_pane_clone.set_visible(visible);
+                    if visible {
+                        preview_pane_clone.show_path(file_view_clone.selected_paths().into_iter().next());
+                    }
+                    return gtk4::glib::Propagation::Stop;
+                }
                 
                 gtk4::glib::Propagation::Proceed
             });
@@ -139,38 +213,24 @@ impl BlinkWindow {
 
         // Initialize
         let initial_path = current_path.borrow().clone();
-        
-        // #region agent log
-        debug_log("E", "window.rs:new", "Initial path", serde_json::json!({
-            "path": initial_path.to_string_lossy(),
-            "exists": initial_path.exists(),
-            "is_dir": initial_path.is_dir()
-        }));
-        // #endregion
-        
-        // Load with hidden files setting from config
-        let show_hidden = SidebarPrefs::show_hidden_files();
-        file_view.set_show_hidden(show_hidden);
-        
-        // #region agent log
-        debug_log("E", "window.rs:new", "Before load_directory", serde_json::json!({
-            "path": initial_path.to_string_lossy()
-        }));
-        // #endregion
-        
+        log::debug!("Opening window at {}", initial_path.display());
         file_view.load_directory(&initial_path);
-        
-        // #region agent log
-        debug_log("E", "window.rs:new", "After load_directory", serde_json::json!({
-            "path": initial_path.to_string_lossy()
-        }));
-        // #endregion
         header_bar.set_path(&initial_path);
-        history.borrow_mut().push(initial_path.clone());
-        *history_index.borrow_mut() = 0;
 
-        // Select Home in sidebar initially
-        sidebar.select_location(0);
+        match restore {
+            Some(state) => {
+                history.replace(NavigationHistory::from_parts(state.history, state.history_index));
+                file_view.set_view_mode(state.view_mode);
+                file_view.select_paths_when_loaded(state.selection);
+                file_view.scroll_when_loaded(state.scroll);
+            }
+            None => {
+                history.borrow_mut().push(initial_path.clone());
+
+                // Select Home in sidebar initially
+                sidebar.select_location(0);
+            }
+        }
 
         // =========================================================================
         // Register app.toggle-pin action with path parameter
@@ -178,6 +238,7 @@ impl BlinkWindow {
         {
             let pinned_store = sidebar.pinned_store().clone();
             let sidebar_clone = sidebar.clone();
+            let file_view_clone = file_view.clone();
             
             let toggle_pin_action = gio::SimpleAction::new(
                 "toggle-pin",
@@ -187,6 +248,7 @@ impl BlinkWindow {
             toggle_pin_action.connect_activate(clone!(
                 #[strong] pinned_store,
                 #[strong] sidebar_clone,
+                #[strong] file_view_clone,
                 move |_, param| {
                     if let Some(path_str) = param.and_then(|p| p.get::<String>()) {
                         let path = PathBuf::from(&path_str);
@@ -194,15 +256,15 @@ impl BlinkWindow {
                         match pinned_store.toggle_pin(&path) {
                             Ok(is_now_pinned) => {
                                 if is_now_pinned {
-                                    println!("[DEBUG] Pinned folder: {:?}", path);
+                                    log::debug!("Pinned folder: {:?}", path);
                                 } else {
-                                    println!("[DEBUG] Unpinned folder: {:?}", path);
+                                    log::debug!("Unpinned folder: {:?}", path);
                                 }
                                 // Refresh sidebar to update UI
                                 sidebar_clone.refresh();
                             }
                             Err(e) => {
-                                eprintln!("Failed to toggle pin: {}", e);
+                                file_view_clone.report_error(format!("Failed to toggle pin: {}", e));
                             }
                         }
                     }
@@ -236,9 +298,8 @@ impl BlinkWindow {
                 // Only update if the value actually changed
                 if show_hidden != *last_value {
                     *last_value = show_hidden;
-                    file_view_monitor.set_show_hidden(show_hidden);
-                    file_view_monitor.refresh();
-                    println!("Hidden files visibility changed to: {}", show_hidden);
+                    file_view_monitor.set_default_show_hidden(show_hidden);
+                    log::debug!("Hidden files visibility changed to: {}", show_hidden);
                 }
                 
                 glib::ControlFlow::Continue
@@ -246,60 +307,49 @@ impl BlinkWindow {
         }
 
 
-        // Add mouse button navigation (back/forward buttons)
-        {
-            // Create gesture for button 8 (back)
-            let file_view_back = file_view.clone();
-            let header_bar_back = header_bar.clone();
-            let current_path_back = current_path.clone();
-            let history_back = history.clone();
-            let history_index_back = history_index.clone();
-
-            let gesture_back = gtk4::GestureClick::builder().button(8).build();
-            gesture_back.connect_pressed(move |_, _, _, _| {
-                let mut idx = history_index_back.borrow_mut();
-                if *idx > 0 {
-                    *idx -= 1;
-                    let hist = history_back.borrow();
-                    if let Some(path) = hist.get(*idx as usize) {
-                        current_path_back.replace(path.clone());
-                        file_view_back.load_directory(path);
-                        header_bar_back.set_path(path);
-                    }
+        // Mouse buttons 8/9 go back/forward
+        for (button, action_name) in [(8, "win.go-back"), (9, "win.go-forward")] {
+            let gesture = gtk4::GestureClick::builder().button(button).build();
+            gesture.connect_pressed(move |gesture, _, _, _| {
+                if let Some(widget) = gesture.widget() {
+                    let _ = widget.activate_action(action_name, None);
                 }
             });
-            window.add_controller(gesture_back);
-
-            // Create gesture for button 9 (forward)
-            let file_view_forward = file_view.clone();
-            let header_bar_forward = header_bar.clone();
-            let current_path_forward = current_path.clone();
-            let history_forward = history.clone();
-            let history_index_forward = history_index.clone();
-
-            let gesture_forward = gtk4::GestureClick::builder().button(9).build();
-            gesture_forward.connect_pressed(move |_, _, _, _| {
-                let hist = history_forward.borrow();
-                let mut idx = history_index_forward.borrow_mut();
-                if (*idx as usize) < hist.len() - 1 {
-                    *idx += 1;
-                    if let Some(path) = hist.get(*idx as usize) {
-                        current_path_forward.replace(path.clone());
-                        file_view_forward.load_directory(path);
-                        header_bar_forward.set_path(path);
-                    }
-                }
-            });
-            window.add_controller(gesture_forward);
+            window.add_controller(gesture);
         }
 
+        let go_back_action = gio::SimpleAction::new("go-back", None);
+        let go_forward_action = gio::SimpleAction::new("go-forward", None);
+        let go_to_history_action = gio::SimpleAction::new("go-to-history", Some(glib::VariantTy::UINT64));
+
+        // Refresh everything derived from the history and the recent locations
+        let sync_navigation = {
+            let header_bar = header_bar.clone();
+            let sidebar = sidebar.clone();
+            let history = history.clone();
+            let go_back_action = go_back_action.clone();
+            let go_forward_action = go_forward_action.clone();
+
+            move |recent: &RecentLocations| {
+                let history = history.borrow();
+                header_bar.set_history(&history.back_entries(), &history.forward_entries());
+                go_back_action.set_enabled(history.can_go_back());
+                go_forward_action.set_enabled(history.can_go_forward());
+
+                let ranked = recent.ranked(RECENT_LOCATIONS_IN_ENTRY);
+                sidebar.set_recent_locations(&ranked[..ranked.len().min(RECENT_LOCATIONS_IN_SIDEBAR)]);
+                header_bar.set_recent_locations(ranked);
+            }
+        };
+
         // Helper function to navigate to a path
         let navigate_to = {
             let file_view = file_view.clone();
             let header_bar = header_bar.clone();
             let current_path = current_path.clone();
             let history = history.clone();
-            let history_index = history_index.clone();
+            let sync_navigation = sync_navigation.clone();
+            let recent = RECENT_LOCATIONS.with(Rc::clone);
 
             move |path: PathBuf, add_to_history: bool| {
                 current_path.replace(path.clone());
@@ -307,15 +357,77 @@ impl BlinkWindow {
                 header_bar.set_path(&path);
 
                 if add_to_history {
-                    let mut hist = history.borrow_mut();
-                    let mut idx = history_index.borrow_mut();
-                    *idx += 1;
-                    hist.truncate(*idx as usize);
-                    hist.push(path);
+                    history.borrow_mut().push(path.clone());
+                }
+
+                // Archives, the Recent list and remote shares aren't folders worth offering again
+                let mut recent = recent.borrow_mut();
+                if !file_view.is_read_only() && !Remote::is_remote(&path) {
+                    recent.record(&path);
+                    recent.save_in_background();
                 }
+                sync_navigation(&recent);
             }
         };
 
+        {
+            let history = history.clone();
+            let navigate_to = navigate_to.clone();
+            go_back_action.connect_activate(move |_, _| {
+                let path = history.borrow_mut().back();
+                if let Some(path) = path {
+                    navigate_to(path, false);
+                }
+            });
+        }
+        {
+            let history = history.clone();
+            let navigate_to = navigate_to.clone();
+            go_forward_action.connect_activate(move |_, _| {
+                let path = history.borrow_mut().forward();
+                if let Some(path) = path {
+                    navigate_to(path, false);
+                }
+            });
+        }
+        {
+            let history = history.clone();
+            let navigate_to = navigate_to.clone();
+            go_to_history_action.connect_activate(move |_, param| {
+                let Some(index) = param.and_then(|p| p.get::<u64>()) else {
+                    return;
+                };
+                let path = history.borrow_mut().go_to(index as usize);
+                if let Some(path) = path {
+                    navigate_to(path, false);
+                }
+            });
+        }
+        window.add_action(&go_back_action);
+        window.add_action(&go_forward_action);
+        window.add_action(&go_to_history_action);
+        // Drop deleted folders from the recent locations without stalling on slow mounts
+        {
+            let recent = RECENT_LOCATIONS.with(Rc::clone);
+            sync_navigation(&recent.borrow());
+
+            let snapshot = recent.borrow().clone();
+            let (tx, rx) = async_channel::bounded(1);
+            thread::spawn(move || {
+                let _ = tx.send_blocking(snapshot.missing());
+            });
+            let sync_navigation = sync_navigation.clone();
+            glib::spawn_future_local(async move {
+                let Ok(missing) = rx.recv().await else { return };
+                if missing.is_empty() {
+                    return;
+                }
+                recent.borrow_mut().forget(&missing);
+                recent.borrow().save_in_background();
+                sync_navigation(&recent.borrow());
+            });
+        }
+
         // Connect sidebar navigation
         {
             let navigate_to = navigate_to.clone();
@@ -348,22 +460,137 @@ impl BlinkWindow {
             });
         }
 
+        // Keep the Recent view current, whoever changes the list
+        {
+            let manager = gtk4::RecentManager::default();
+            let file_view = file_view.clone();
+            let handler = manager.connect_changed(move |_| {
+                if file_view.is_showing_recent() {
+                    file_view.refresh();
+                }
+            });
+            let handler = RefCell::new(Some(handler));
+            window.connect_destroy(move |_| {
+                if let Some(handler) = handler.take() {
+                    manager.disconnect(handler);
+                }
+            });
+        }
+
         // Connect search
         {
             let file_view = file_view.clone();
+            let status_bar = status_bar.clone();
             header_bar.connect_search(move |query| {
                 file_view.filter(&query);
+                status_bar.show_items(file_view.item_count(), file_view.hidden_count());
             });
         }
 
         // Connect view toggle (grid/list)
         {
             let file_view = file_view.clone();
-            let header_bar_clone = header_bar.clone();
             header_bar.connect_view_toggle(move || {
                 file_view.toggle_view_mode();
-                let is_grid = file_view.is_grid_mode();
-                header_bar_clone.set_view_icon(is_grid);
+            });
+        }
+
+        // View options menu (per-folder settings)
+        {
+            let settings = file_view.view_settings();
+
+            let sort_by = gio::SimpleAction::new_stateful(
+                "sort-by",
+                Some(&String::static_variant_type()),
+                &settings.sort_key.id().to_variant(),
+            );
+            {
+                let file_view = file_view.clone();
+                sort_by.connect_activate(move |_, param| {
+                    let Some(key) = param.and_then(|p| p.get::<String>()).and_then(|id| SortKey::from_id(&id)) else {
+                        return;
+                    };
+                    file_view.set_sort(key, file_view.view_settings().sort_descending);
+                });
+            }
+
+            let sort_descending = gio::SimpleAction::new_stateful("sort-descending", None, &settings.sort_descending.to_variant());
+            {
+                let file_view = file_view.clone();
+                sort_descending.connect_activate(move |_, _| {
+                    let settings = file_view.view_settings();
+                    file_view.set_sort(settings.sort_key, !settings.sort_descending);
+                });
+            }
+
+            let show_hidden = gio::SimpleAction::new_stateful("show-hidden", None, &settings.show_hidden.to_variant());
+            {
+                let file_view = file_view.clone();
+                show_hidden.connect_activate(move |_, _| {
+                    file_view.toggle_show_hidden();
+                });
+            }
+
+            let inherit = gio::SimpleAction::new_stateful("view-inherit", None, &file_view.inherits_settings().to_variant());
+            {
+                let file_view = file_view.clone();
+                inherit.connect_activate(move |action, _| {
+                    let inherit = !file_view.inherits_settings();
+                    file_view.set_inherit_settings(inherit);
+                    action.set_state(&inherit.to_variant());
+                });
+            }
+
+            let make_default = gio::SimpleAction::new("view-make-default", None);
+            {
+                let file_view = file_view.clone();
+                make_default.connect_activate(move |_, _| {
+                    file_view.make_settings_default();
+                });
+            }
+
+            let reset = gio::SimpleAction::new("view-reset", None);
+            {
+                let file_view = file_view.clone();
+                reset.connect_activate(move |_, _| {
+                    file_view.reset_settings();
+                });
+            }
+
+            let zoom_in = gio::SimpleAction::new("zoom-in", None);
+            let zoom_out = gio::SimpleAction::new("zoom-out", None);
+            let zoom_reset = gio::SimpleAction::new("zoom-reset", None);
+            {
+                let file_view = file_view.clone();
+                zoom_in.connect_activate(move |_, _| file_view.zoom_in());
+            }
+            {
+                let file_view = file_view.clone();
+                zoom_out.connect_activate(move |_, _| file_view.zoom_out());
+            }
+            {
+                let file_view = file_view.clone();
+                zoom_reset.connect_activate(move |_, _| file_view.reset_zoom());
+            }
+            zoom_in.set_enabled(settings.can_zoom_in());
+            zoom_out.set_enabled(settings.can_zoom_out());
+
+            for action in [
+                &sort_by, &sort_descending, &show_hidden, &inherit, &make_default, &reset, &zoom_in, &zoom_out, &zoom_reset,
+            ] {
+                window.add_action(action);
+            }
+
+            // Keep the header bar and menu in sync with whichever folder is shown
+            header_bar.set_view_icon(settings.view_mode == ViewMode::Grid);
+            let header_bar_clone = header_bar.clone();
+            file_view.connect_settings_changed(move |settings| {
+                header_bar_clone.set_view_icon(settings.view_mode == ViewMode::Grid);
+                sort_by.set_state(&settings.sort_key.id().to_variant());
+                sort_descending.set_state(&settings.sort_descending.to_variant());
+                show_hidden.set_state(&settings.show_hidden.to_variant());
+                zoom_in.set_enabled(settings.can_zoom_in());
+                zoom_out.set_enabled(settings.can_zoom_out());
             });
         }
 
@@ -374,6 +601,10 @@ impl BlinkWindow {
             let window_weak = window.downgrade();
 
             header_bar.connect_new_folder(move || {
+                // Archives and the Recent list can't hold new folders
+                if file_view.is_read_only() {
+                    return;
+                }
                 let current = current_path.borrow().clone();
                 let file_view = file_view.clone();
                 
@@ -410,12 +641,11 @@ impl BlinkWindow {
                             if let Some(entry) = dialog.extra_child().and_downcast::<gtk4::Entry>() {
                                 let name = entry.text();
                                 if !name.is_empty() {
-                                    let new_path = current_clone.join(name.as_str());
-                                    if let Err(e) = FileOperations::create_directory(&new_path) {
-                                        eprintln!("Failed to create folder: {}", e);
-                                    } else {
-                                        file_view.refresh();
-                                    }
+                                    let new_path = Vfs::child(&current_clone, &name);
+                                    let backend = file_view.backend_for(&current_clone);
+                                    Self::run_in_background(&file_view, "Failed to create folder", move || {
+                                        backend.mkdir(&new_path)
+                                    });
                                 }
                             }
                         }
@@ -445,6 +675,7 @@ impl BlinkWindow {
             let clipboard_clone = clipboard.clone();
             let current_path_clone = current_path.clone();
             let file_view_clone = file_view.clone();
+            let status_bar = status_bar.clone();
             let window_clone = window.clone();
             file_view.connect_paste(move || {
                 let dest = current_path_clone.borrow().clone();
@@ -456,271 +687,15 @@ impl BlinkWindow {
                 if mode == ClipboardMode::None || paths.is_empty() {
                     return;
                 }
-                
-                // Calculate total size for progress
-                let (total_size, total_files) = FileOperations::calculate_total_size(&paths);
-                
-                // Create progress dialog
-                let progress_info = Arc::new(Mutex::new(ProgressInfo {
-                    current_file: String::new(),
-                    bytes_copied: 0,
-                    total_bytes: total_size,
-                    files_copied: 0,
-                    total_files,
-                }));
-                
-                let (progress_bar, status_label, file_label) = Self::create_progress_dialog(&window_clone, mode);
-                let progress_info_clone = progress_info.clone();
-                
-                // Update UI periodically
-                let progress_bar_weak = progress_bar.downgrade();
-                let status_label_weak = status_label.downgrade();
-                let file_label_weak = file_label.downgrade();
-                
-                glib::timeout_add_local(Duration::from_millis(100), move || {
-                    let progress_info = progress_info_clone.lock().unwrap();
-                    
-                    if let Some(progress_bar) = progress_bar_weak.upgrade() {
-                        if progress_info.total_bytes > 0 {
-                            let fraction = progress_info.bytes_copied as f64 / progress_info.total_bytes as f64;
-                            progress_bar.set_fraction(fraction);
-                        } else if progress_info.total_files > 0 {
-                            let fraction = progress_info.files_copied as f64 / progress_info.total_files as f64;
-                            progress_bar.set_fraction(fraction);
-                        }
-                    }
-                    
-                    if let Some(status_label) = status_label_weak.upgrade() {
-                        if progress_info.total_bytes > 0 {
-                            let mb_copied = progress_info.bytes_copied as f64 / (1024.0 * 1024.0);
-                            let mb_total = progress_info.total_bytes as f64 / (1024.0 * 1024.0);
-                            status_label.set_text(&format!("{:.1} MB / {:.1} MB", mb_copied, mb_total));
-                        } else {
-                            status_label.set_text(&format!("{} / {} files", progress_info.files_copied, progress_info.total_files));
-                        }
-                    }
-                    
-                    if let Some(file_label) = file_label_weak.upgrade() {
-                        if !progress_info.current_file.is_empty() {
-                            file_label.set_text(&progress_info.current_file);
-                        }
-                    }
-                    
-                    // Continue updating if not complete
-                    if progress_info.files_copied < progress_info.total_files {
-                        glib::ControlFlow::Continue
-                    } else {
-                        glib::ControlFlow::Break
-                    }
-                });
-                
-                // Perform operation in background thread
-                let dest_clone = dest.clone();
-                let paths_clone = paths.clone();
-                let mode_clone = mode;
-                let progress_info_thread = progress_info.clone();
-                let progress_bar_weak_final = progress_bar.downgrade();
-                let file_view_clone_final = file_view_clone.clone();
-                let clipboard_clone_final = clipboard_clone.clone();
-                
-                // Communication definitions
-                #[derive(Debug, Clone)]
-                enum ConflictResolution {
-                    Replace,
-                    Rename(String),
-                    Cancel,
-                }
 
-                #[derive(Debug, Clone)]
-                enum PasteEvent {
-                    Conflict(PathBuf),
-                    Finished(ClipboardMode),
+                let is_remote = Remote::is_remote(&dest) || paths.iter().any(|path| Remote::is_remote(path));
+                let job = PasteJob { paths, dest, mode };
+                if is_remote {
+                    Self::paste_remote(&window_clone, &file_view_clone, &status_bar, job, clipboard_clone.clone());
+                    return;
                 }
                 
-                // Worker -> UI (Events)
-                let (event_tx, event_rx) = async_channel::unbounded::<PasteEvent>();
-                // UI -> Worker (Resolution)
-                let (resolve_tx, resolve_rx) = async_channel::unbounded::<ConflictResolution>();
-                
-                // Listen for events on UI thread
-                let window_weak = window_clone.downgrade();
-                
-                glib::spawn_future_local(async move {
-                    while let Ok(event) = event_rx.recv().await {
-                        match event {
-                            PasteEvent::Finished(mode) => {
-                                // Completed
-                                if mode == ClipboardMode::Cut {
-                                    clipboard_clone_final.borrow_mut().clear();
-                                }
-                                
-                                if let Some(progress_bar) = progress_bar_weak_final.upgrade() {
-                                    if let Some(dialog) = progress_bar.parent().and_then(|p| p.parent()) {
-                                        if let Some(dialog) = dialog.downcast_ref::<adw::Window>() {
-                                            dialog.close();
-                                        }
-                                    }
-                                }
-                                
-                                glib::timeout_add_local(Duration::from_millis(500), move || {
-                                    file_view_clone_final.refresh();
-                                    glib::ControlFlow::Break
-                                });
-                                break;
-                            }
-                            PasteEvent::Conflict(conflicting_path) => {
-                                // Handle conflict
-                                if let Some(window) = window_weak.upgrade() {
-                                    let file_name = conflicting_path.file_name()
-                                        .map(|n| n.to_string_lossy().to_string())
-                                        .unwrap_or_else(|| "file".to_string());
-                                    
-                                    let (response_tx, response_rx) = async_channel::bounded(1);
-                                    
-                                    let dialog = adw::AlertDialog::builder()
-                                        .heading("File Conflict")
-                                        .body(&format!("\"{}\" already exists. What do you want to do?", file_name))
-                                        .build();
-                                    
-                                    dialog.add_response("cancel", "Cancel");
-                                    dialog.add_response("rename", "Rename");
-                                    dialog.add_response("replace", "Replace");
-                                    
-                                    dialog.set_response_appearance("replace", adw::ResponseAppearance::Destructive);
-                                    dialog.set_default_response(Some("rename"));
-                                    dialog.set_close_response("cancel");
-                                    
-                                    let window_for_dialog = window.clone();
-                                    let response_tx_clone = response_tx.clone();
-                                    let file_name_clone = file_name.clone();
-                                    
-                                    dialog.connect_response(None, move |_dialog, response| {
-                                        if response == "replace" {
-                                            let _ = response_tx_clone.send_blocking(ConflictResolution::Replace);
-                                        } else if response == "rename" {
-                                            // Show rename input dialog
-                                            let rename_dialog = adw::AlertDialog::builder()
-                                                .heading("Rename")
-                                                .body("Enter new name for the destination file")
-                                                .build();
-                                            
-                                            let entry = gtk4::Entry::builder()
-                                                .text(&file_name_clone)
-                                                .activates_default(true)
-                                                .build();
-                                            entry.add_css_class("nautilus-entry");
-                                            
-                                            rename_dialog.set_extra_child(Some(&entry));
-                                            rename_dialog.add_response("cancel", "Cancel");
-                                            rename_dialog.add_response("apply", "Rename");
-                                            rename_dialog.set_response_appearance("apply", adw::ResponseAppearance::Suggested);
-                                            rename_dialog.set_default_response(Some("apply"));
-                                            rename_dialog.set_close_response("cancel");
-                                            
-                                            let response_tx_inner = response_tx_clone.clone();
-                                            
-                                            rename_dialog.connect_response(None, move |d, res| {
-                                                if res == "apply" {
-                                                    if let Some(entry) = d.extra_child().and_downcast::<gtk4::Entry>() {
-                                                        let new_name = entry.text().to_string();
-                                                        if !new_name.is_empty() {
-                                                            let _ = response_tx_inner.send_blocking(ConflictResolution::Rename(new_name));
-                                                            return;
-                                                        }
-                                                    }
-                                                }
-                                                let _ = response_tx_inner.send_blocking(ConflictResolution::Cancel);
-                                            });
-                                            
-                                            rename_dialog.present(Some(&window_for_dialog));
-                                            
-                                        } else {
-                                            let _ = response_tx_clone.send_blocking(ConflictResolution::Cancel);
-                                        }
-                                    });
-                                    
-                                    dialog.present(Some(&window));
-                                    
-                                    // Wait for user response
-                                    if let Ok(res) = response_rx.recv().await {
-                                        let _ = resolve_tx.send(res).await;
-                                    } else {
-                                        let _ = resolve_tx.send(ConflictResolution::Cancel).await;
-                                    }
-                                } else {
-                                    let _ = resolve_tx.send(ConflictResolution::Cancel).await;
-                                }
-                            }
-                        }
-                    }
-                });
-                
-                // Spawn worker thread
-                thread::spawn(move || {
-                    for source in &paths_clone {
-                        let file_name: String = match source.file_name() {
-                            Some(name) => name.to_string_lossy().to_string(),
-                            None => continue,
-                        };
-                        
-                        let mut dest_path = dest_clone.join(&file_name);
-                        let mut skip_file = false;
-
-                        // Resolve conflicts
-                        loop {
-                            if !dest_path.exists() {
-                                break;
-                            }
-                            
-                            // Ask UI for help
-                            if event_tx.send_blocking(PasteEvent::Conflict(dest_path.clone())).is_err() {
-                                return;
-                            }
-                            
-                            match resolve_rx.recv_blocking() {
-                                Ok(ConflictResolution::Replace) => break,
-                                Ok(ConflictResolution::Rename(new_name)) => {
-                                    dest_path = dest_clone.join(new_name);
-                                },
-                                Ok(ConflictResolution::Cancel) | Err(_) => {
-                                    skip_file = true;
-                                    break; 
-                                }
-                            }
-                        }
-                        
-                        if skip_file {
-                            // Cancel operation (or just this file? User intent usually implies Abort)
-                            let _ = event_tx.send_blocking(PasteEvent::Finished( mode_clone));
-                            return; 
-                        }
-                        
-                        match mode_clone {
-                            ClipboardMode::Copy => {
-                                if let Err(e) = FileOperations::copy_file_with_progress(
-                                    source,
-                                    &dest_path,
-                                    Some(progress_info_thread.clone()),
-                                ) {
-                                    eprintln!("Copy error: {}", e);
-                                }
-                            }
-                            ClipboardMode::Cut => {
-                                if let Err(e) = FileOperations::move_file_with_progress(
-                                    source,
-                                    &dest_path,
-                                    Some(progress_info_thread.clone()),
-                                ) {
-                                    eprintln!("Move error: {}", e);
-                                }
-                            }
-                            ClipboardMode::None => {}
-                        }
-                    }
-                    
-                    // Signal completion
-                    let _ = event_tx.send_blocking(PasteEvent::Finished(mode_clone));
-                });
+                Self::paste_local(&window_clone, &file_view_clone, &status_bar, job, clipboard_clone.clone());
             });
         }
 
@@ -733,6 +708,10 @@ impl BlinkWindow {
                     let paths_clone = paths.clone();
                     
                     let count = paths.len();
+                    if paths.iter().any(|path| Remote::is_remote(path)) {
+                        Self::delete_remote(&window, &file_view, paths);
+                        return;
+                    }
                     let message = if count == 1 {
                         format!("Move \"{}\" to trash?", paths[0].file_name().unwrap_or_default().to_string_lossy())
                     } else {
@@ -748,21 +727,16 @@ impl BlinkWindow {
                     dialog.add_response("trash", "Move to Trash");
                     dialog.set_response_appearance("trash", adw::ResponseAppearance::Destructive);
 
+                    let window_clone = window.clone();
                     dialog.connect_response(None, move |_, response| {
                         if response == "trash" {
-                            println!("[DEBUG] Deleting {} items", paths_clone.len());
-                            for path in &paths_clone {
-                                println!("[DEBUG] Moving to trash: {:?}", path);
-                                if let Err(e) = FileOperations::delete(path) {
-                                    eprintln!("Delete error: {}", e);
-                                }
-                            }
-                            
-                            // Refresh with a small delay to allow the filesystem to update
-                            let file_view_delayed = file_view.clone();
-                            glib::timeout_add_local_once(std::time::Duration::from_millis(150), move || {
-                                file_view_delayed.refresh();
-                            });
+                            log::debug!("Deleting {} items", paths_clone.len());
+                            Self::delete_in_background(
+                                &window_clone,
+                                &file_view,
+                                "Some Items Could Not Be Moved to the Trash",
+                                paths_clone.clone(),
+                            );
                         }
                     });
 
@@ -776,18 +750,19 @@ impl BlinkWindow {
         {
             let pinned_store = sidebar.pinned_store().clone();
             let sidebar_clone = sidebar.clone();
+            let file_view_clone = file_view.clone();
             file_view.connect_pin(move |path| {
                 match pinned_store.toggle_pin(&path) {
                     Ok(is_now_pinned) => {
                         if is_now_pinned {
-                            println!("[DEBUG] Pinned folder: {:?}", path);
+                            log::debug!("Pinned folder: {:?}", path);
                         } else {
-                            println!("[DEBUG] Unpinned folder: {:?}", path);
+                            log::debug!("Unpinned folder: {:?}", path);
                         }
                         sidebar_clone.refresh();
                     }
                     Err(e) => {
-                        eprintln!("Failed to toggle pin: {}", e);
+                        file_view_clone.report_error(format!("Failed to toggle pin: {}", e));
                     }
                 }
             });
@@ -801,9 +776,13 @@ impl BlinkWindow {
                     let file_view = file_view_clone.clone();
                     let path_clone = path.clone();
                     
-                    let current_name = path.file_name()
-                        .map(|n| n.to_string_lossy().to_string())
-                        .unwrap_or_default();
+                    let current_name = if Remote::is_remote(&path) {
+                        Remote::display_name(&path)
+                    } else {
+                        path.file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_default()
+                    };
 
                     let dialog = adw::AlertDialog::builder()
                         .heading("Rename")
@@ -836,11 +815,12 @@ impl BlinkWindow {
                             if let Some(entry) = dialog.extra_child().and_downcast::<gtk4::Entry>() {
                                 let new_name = entry.text();
                                 if !new_name.is_empty() && new_name.as_str() != current_name_clone {
-                                    if let Err(e) = FileOperations::rename(&path_clone, &new_name) {
-                                        eprintln!("Rename error: {}", e);
-                                    } else {
-                                        file_view.refresh();
-                                    }
+                                    let path = path_clone.clone();
+                                    let new_name = new_name.to_string();
+                                    let backend = file_view.backend_for(&path);
+                                    Self::run_in_background(&file_view, "Rename error", move || {
+                                        backend.rename(&path, &new_name).map(|_| ())
+                                    });
                                 }
                             }
                         }
@@ -851,8 +831,320 @@ impl BlinkWindow {
             });
         }
 
+        // Preview pane (while shown) and status bar follow the selection
+        {
+            let preview_pane_clone = preview_pane.clone();
+            let status_bar = status_bar.clone();
+            file_view.connect_selection_changed(move |paths| {
+                if preview_pane_clone.is_visible() {
+                    preview_pane_clone.show_path(paths.first().cloned());
+                }
+                status_bar.show_selection(paths);
+            });
+        }
+
+        // Item counts follow the items as they change
+        {
+            let status_bar = status_bar.clone();
+            let file_view_clone = file_view.clone();
+            file_view.connect_items_changed(move || {
+                status_bar.show_items(file_view_clone.item_count(), file_view_clone.hidden_count());
+            });
+        }
+
+        // Free space follows the folder; (re)loads also follow file operations, so drives are updated too
+        {
+            let status_bar = status_bar.clone();
+            let sidebar_clone = sidebar.clone();
+            file_view.connect_directory_loaded(move |path| {
+                status_bar.show_free_space(&path);
+                sidebar_clone.refresh_disk_usage();
+            });
+        }
+
+        {
+            let status_bar = status_bar.clone();
+            file_view.connect_loading_changed(move |loading| {
+                status_bar.set_loading(loading);
+            });
+        }
+
+        // Quick look (Space): preview the selected item, arrow keys move through items
+        {
+            let file_view_clone = file_view.clone();
+            let vfs = vfs.clone();
+            let window_weak = window.downgrade();
+            file_view.connect_quick_look(move |path| {
+                let Some(window) = window_weak.upgrade() else { return };
+
+                let pane = PreviewPane::new(vfs.clone());
+                pane.show_path(Some(path));
+
+                let toolbar_view = adw::ToolbarView::new();
+                toolbar_view.add_top_bar(&adw::HeaderBar::new());
+                toolbar_view.set_content(Some(pane.container()));
+
+                let dialog = adw::Dialog::builder()
+                    .title("Quick Look")
+                    .content_width(720)
+                    .content_height(560)
+                    .child(&toolbar_view)
+                    .build();
+
+                let key_controller = gtk4::EventControllerKey::new();
+                key_controller.set_propagation_phase(gtk4::PropagationPhase::Capture);
+                let file_view = file_view_clone.clone();
+                let dialog_weak = dialog.downgrade();
+                key_controller.connect_key_pressed(move |_, keyval, _, _| {
+                    let offset = match keyval {
+                        gtk4::gdk::Key::Left | gtk4::gdk::Key::Up => -1,
+                        gtk4::gdk::Key::Right | gtk4::gdk::Key::Down => 1,
+                        gtk4::gdk::Key::space => {
+                            if let Some(dialog) = dialog_weak.upgrade() {
+                                dialog.close();
+                            }
+                            return gtk4::glib::Propagation::Stop;
+                        }
+                        _ => return gtk4::glib::Propagation::Proceed,
+                    };
+
+                    if let Some(path) = file_view.select_relative(offset) {
+                        pane.show_path(Some(path));
+                    }
+                    gtk4::glib::Propagation::Stop
+                });
+                dialog.add_controller(key_controller);
+
+                dialog.present(Some(&window));
+            });
+        }
+
+        {
+            let window_weak = window.downgrade();
+            file_view.connect_properties(move |paths| {
+                if let Some(window) = window_weak.upgrade() {
+                    PropertiesDialog::present(&window, paths);
+                }
+            });
+        }
+
+        // Disk usage analyzer for a folder or drive, offered by the file view and sidebar menus
+        {
+            let analyze_action = gio::SimpleAction::new("analyze-disk-usage", Some(&String::static_variant_type()));
+            let window_weak = window.downgrade();
+            let file_view = file_view.clone();
+            analyze_action.connect_activate(move |_, param| {
+                let Some(path) = param.and_then(|p| p.get::<String>()) else { return };
+                let Some(window) = window_weak.upgrade() else { return };
+                let file_view = file_view.clone();
+                DiskUsageWindow::present(&window, PathBuf::from(path), move || file_view.refresh());
+            });
+            window.add_action(&analyze_action);
+        }
+
+        // Connect to Server: mount the share, pin it to the sidebar and open it
+        {
+            let connect_action = gio::SimpleAction::new("connect-to-server", None);
+            let window_weak = window.downgrade();
+            let sidebar_clone = sidebar.clone();
+            let navigate_to = navigate_to.clone();
+            let toast_overlay = toast_overlay.clone();
+            connect_action.connect_activate(move |_, _| {
+                let Some(window) = window_weak.upgrade() else { return };
+                let window_weak = window.downgrade();
+                let sidebar = sidebar_clone.clone();
+                let navigate_to = navigate_to.clone();
+                let toast_overlay = toast_overlay.clone();
+                let on_error = move |message: String| Self::show_error_toast(&toast_overlay, &message);
+                ConnectServerDialog::present(&window, move |path| {
+                    let Some(window) = window_weak.upgrade() else { return };
+                    let sidebar = sidebar.clone();
+                    let navigate_to = navigate_to.clone();
+                    glib::spawn_future_local(async move {
+                        let operation = gtk4::MountOperation::new(Some(&window));
+                        if let Err(e) = Remote::mount(&Remote::file(&path), Some(operation.upcast_ref())).await {
+                            log::warn!("Failed to connect to {}: {}", path.display(), e);
+                            if let Some(message) = DriveScanner::error_message(&e) {
+                                let dialog = adw::AlertDialog::builder()
+                                    .heading("Unable to Connect")
+                                    .body(&message)
+                                    .build();
+                                dialog.add_response("ok", "OK");
+                                dialog.present(Some(&window));
+                            }
+                            return;
+                        }
+
+                        let pinned_store = sidebar.pinned_store();
+                        if !pinned_store.is_pinned(&path) {
+                            if let Err(e) = pinned_store.add(&path) {
+                                log::warn!("Failed to save connection: {}", e);
+                            }
+                        }
+                        navigate_to(path, true);
+                    });
+                }, on_error);
+            });
+            window.add_action(&connect_action);
+        }
+
+        // Copy Debug Info: versions, session details and recent log lines for bug reports
+        {
+            let copy_debug_info = gio::SimpleAction::new("copy-debug-info", None);
+            let window_weak = window.downgrade();
+            let toast_overlay = toast_overlay.clone();
+            copy_debug_info.connect_activate(move |_, _| {
+                let Some(window) = window_weak.upgrade() else { return };
+                window.clipboard().set_text(&Logger::debug_info());
+                toast_overlay.add_toast(adw::Toast::new("Debug info copied"));
+            });
+            window.add_action(&copy_debug_info);
+        }
+
+        // Archive extraction and compression
+        {
+            let file_view_clone = file_view.clone();
+            let status_bar = status_bar.clone();
+            let window_weak = window.downgrade();
+            file_view.connect_extract_here(move |paths| {
+                let Some(window) = window_weak.upgrade() else { return };
+                let archives: Vec<PathBuf> = paths.into_iter().filter(|p| Archive::is_archive(p)).collect();
+                let measured = archives.clone();
+                let measure = move || Self::archives_size(&measured);
+
+                Self::run_archive_job(&window, &file_view_clone, &status_bar, "Extracting files...", measure, move |progress| {
+                    for archive in &archives {
+                        let destination = Archive::extract_here_destination(archive)?;
+                        Archive::extract(archive, &destination, Some(progress.clone()))?;
+                    }
+                    Ok(())
+                });
+            });
+        }
+
+        {
+            let file_view_clone = file_view.clone();
+            let status_bar = status_bar.clone();
+            let window_weak = window.downgrade();
+            file_view.connect_extract_to(move |paths| {
+                let Some(window) = window_weak.upgrade() else { return };
+                let archives: Vec<PathBuf> = paths.into_iter().filter(|p| Archive::is_archive(p)).collect();
+                if archives.is_empty() {
+                    return;
+                }
+
+                let folder_dialog = gtk4::FileDialog::builder()
+                    .title("Extract to…")
+                    .modal(true)
+                    .build();
+                if let Some(parent) = archives[0].parent() {
+                    folder_dialog.set_initial_folder(Some(&gio::File::for_path(parent)));
+                }
+
+                let file_view = file_view_clone.clone();
+                let status_bar = status_bar.clone();
+                let window_clone = window.clone();
+                folder_dialog.select_folder(Some(&window), gio::Cancellable::NONE, move |result| {
+                    let Some(folder) = result.ok().and_then(|f| f.path()) else { return };
+                    let measured = archives.clone();
+                    let measure = move || Self::archives_size(&measured);
+
+                    Self::run_archive_job(&window_clone, &file_view, &status_bar, "Extracting files...", measure, move |progress| {
+                        for archive in &archives {
+                            // Each archive gets its own folder inside the chosen destination
+                            let destination = FileOperations::unique_path(&folder, &Archive::archive_stem(archive), "");
+                            Archive::extract(archive, &destination, Some(progress.clone()))?;
+                        }
+                        Ok(())
+                    });
+                });
+            });
+        }
+
+        {
+            let file_view_clone = file_view.clone();
+            let status_bar = status_bar.clone();
+            let current_path_clone = current_path.clone();
+            let window_weak = window.downgrade();
+            file_view.connect_compress(move |paths| {
+                let Some(window) = window_weak.upgrade() else { return };
+                if paths.is_empty() {
+                    return;
+                }
+
+                // Default name: the single item's name, or the current folder's name
+                let default_name = if paths.len() == 1 {
+                    paths[0].file_stem().map(|n| n.to_string_lossy().to_string())
+                } else {
+                    current_path_clone.borrow().file_name().map(|n| n.to_string_lossy().to_string())
+                }
+                .unwrap_or_else(|| "Archive".to_string());
+
+                let dialog = adw::AlertDialog::builder()
+                    .heading("Compress")
+                    .body(format!("Create an archive from {} item(s)", paths.len()))
+                    .build();
+
+                let content = GtkBox::new(Orientation::Vertical, 12);
+                let entry = gtk4::Entry::builder()
+                    .text(&default_name)
+                    .activates_default(true)
+                    .build();
+                entry.add_css_class("nautilus-entry");
+                content.append(&entry);
+
+                let format_names: Vec<&str> = ArchiveFormat::ALL.iter().map(|f| f.display_name()).collect();
+                let format_dropdown = gtk4::DropDown::from_strings(&format_names);
+                content.append(&format_dropdown);
+
+                dialog.set_extra_child(Some(&content));
+                dialog.add_response("cancel", "Cancel");
+                dialog.add_response("compress", "Compress");
+                dialog.set_response_appearance("compress", adw::ResponseAppearance::Suggested);
+                dialog.set_default_response(Some("compress"));
+                dialog.set_close_response("cancel");
+
+                let entry_clone = entry.clone();
+                glib::idle_add_local_once(move || {
+                    entry_clone.grab_focus();
+                    entry_clone.select_region(0, -1);
+                });
+
+                let file_view = file_view_clone.clone();
+                let status_bar = status_bar.clone();
+                let current_path = current_path_clone.clone();
+                let window_clone = window.clone();
+                dialog.connect_response(None, move |_, response| {
+                    if response != "compress" {
+                        return;
+                    }
+
+                    let name = entry.text().trim().to_string();
+                    if name.is_empty() {
+                        return;
+                    }
+
+                    let format = ArchiveFormat::ALL
+                        .get(format_dropdown.selected() as usize)
+                        .copied()
+                        .unwrap_or(ArchiveFormat::Zip);
+                    let destination = FileOperations::unique_path(&current_path.borrow(), &name, format.extension());
+                    let sources = paths.clone();
+                    let measured = paths.clone();
+                    let measure = move || FileOperations::calculate_total_size(&measured);
+
+                    Self::run_archive_job(&window_clone, &file_view, &status_bar, "Compressing files...", measure, move |progress| {
+                        Archive::compress(&sources, &destination, format, Some(progress))
+                    });
+                });
+
+                dialog.present(Some(&window));
+            });
+        }
+
         // Connect open terminal (key 'f')
         {
+            let file_view_clone = file_view.clone();
             file_view.connect_open_terminal(move |path| {
                 let dir = if path.is_dir() {
                     path
@@ -906,13 +1198,14 @@ impl BlinkWindow {
                 }
                 
                 if !opened {
-                    eprintln!("Failed to open terminal - no terminal emulator found");
+                    file_view_clone.report_error(String::from("Failed to open a terminal: no terminal emulator found"));
                 }
             });
         }
 
         // Connect open micro (key 'm')
         {
+            let file_view_clone = file_view.clone();
             file_view.connect_open_micro(move |path| {
                 if !path.is_dir() {
                     let path_str = path.to_string_lossy().to_string();
@@ -965,28 +1258,49 @@ impl BlinkWindow {
                             .arg(&path_str)
                             .spawn()
                         {
-                            eprintln!("Failed to open micro: {}", e);
+                            file_view_clone.report_error(format!("Failed to open micro: {}", e));
                         }
                     }
                 }
             });
         }
 
-        Self { window }
+        Self {
+            window,
+            file_view,
+            current_path,
+            history,
+        }
     }
 
     pub fn present(&self) {
         self.window.present();
     }
+
+    /// What to save so this window can be reopened next time
+    pub fn state(&self) -> WindowState {
+        let (width, height) = self.window.default_size();
+        WindowState {
+            width,
+            height,
+            maximized: self.window.is_maximized(),
+            location: self.current_path.borrow().clone(),
+            history: self.history.borrow().entries().to_vec(),
+            history_index: self.history.borrow().index(),
+            view_mode: self.file_view.view_settings().view_mode,
+            selection: self.file_view.selected_paths(),
+            scroll: self.file_view.scroll_position(),
+        }
+    }
     
     fn create_progress_dialog(
         window: &adw::ApplicationWindow,
-        mode: ClipboardMode,
+        title: &str,
     ) -> (ProgressBar, Label, Label) {
         let dialog = adw::Window::builder()
             .transient_for(window)
             .modal(true)
-            .title(if mode == ClipboardMode::Copy { "Copying files..." } else { "Moving files..." })
+            .title(title)
             .default_width(400)
             .resizable(false)
             .build();
@@ -1020,4 +1334,530 @@ impl BlinkWindow {
         
         (progress_bar, status_label, file_label)
     }
+
+    /// Mirror a shared ProgressInfo into the progress dialog widgets until the dialog closes
+    fn watch_progress(
+        progress_info: Arc<Mutex<ProgressInfo>>,
+        progress_bar: &ProgressBar,
+        status_label: &Label,
+        file_label: &Label,
+    ) {
+        let progress_bar_weak = progress_bar.downgrade();
+        let status_label_weak = status_label.downgrade();
+        let file_label_weak = file_label.downgrade();
+
+        glib::timeout_add_local(Duration::from_millis(100), move || {
+            let progress_info = progress_info.lock().unwrap();
+
+            // The job closes the dialog once it's done, however long counting the items takes
+            let Some(progress_bar) = progress_bar_weak.upgrade().filter(|bar| bar.root().is_some()) else {
+                return glib::ControlFlow::Break;
+            };
+
+            if progress_info.total_bytes == 0 && progress_info.total_files == 0 {
+                // Still counting what there is to do
+                progress_bar.pulse();
+                if let Some(status_label) = status_label_weak.upgrade() {
+                    status_label.set_text("Counting files…");
+                }
+                return glib::ControlFlow::Continue;
+            }
+
+            if progress_info.total_bytes > 0 {
+                let fraction = progress_info.bytes_copied as f64 / progress_info.total_bytes as f64;
+                progress_bar.set_fraction(fraction.min(1.0));
+            } else if progress_info.total_files > 0 {
+                let fraction = progress_info.files_copied as f64 / progress_info.total_files as f64;
+                progress_bar.set_fraction(fraction.min(1.0));
+            }
+
+            if let Some(status_label) = status_label_weak.upgrade() {
+                if progress_info.total_bytes > 0 {
+                    let mb_copied = progress_info.bytes_copied as f64 / (1024.0 * 1024.0);
+                    let mb_total = progress_info.total_bytes as f64 / (1024.0 * 1024.0);
+                    status_label.set_text(&format!("{:.1} MB / {:.1} MB", mb_copied, mb_total));
+                } else {
+                    status_label.set_text(&format!("{} / {} files", progress_info.files_copied, progress_info.total_files));
+                }
+            }
+
+            if let Some(file_label) = file_label_weak.upgrade() {
+                if !progress_info.current_file.is_empty() {
+                    file_label.set_text(&progress_info.current_file);
+                }
+            }
+
+            glib::ControlFlow::Continue
+        });
+    }
+
+    /// Copy or move local items into the job's `dest` with a progress dialog, asking what to do about
+    /// names that are taken. Items that fail are listed at the end, with the option to retry them.
+    fn paste_local(
+        window: &adw::ApplicationWindow,
+        file_view: &FileGridView,
+        status_bar: &StatusBar,
+        job: PasteJob,
+        clipboard: Rc<RefCell<Clipboard>>,
+    ) {
+        let PasteJob { paths, dest, mode } = job;
+        // The worker fills in the totals, walking folders and archives can take a while
+        let progress_info = Arc::new(Mutex::new(ProgressInfo {
+            current_file: String::new(),
+            bytes_copied: 0,
+            total_bytes: 0,
+            files_copied: 0,
+            total_files: 0,
+        }));
+        
+        let title = if mode == ClipboardMode::Copy { "Copying files..." } else { "Moving files..." };
+        let (progress_bar, status_label, file_label) = Self::create_progress_dialog(window, title);
+        Self::watch_progress(progress_info.clone(), &progress_bar, &status_label, &file_label);
+        let activity = status_bar.begin_activity(title.trim_end_matches('.'));
+        let status_bar_final = status_bar.clone();
+        
+        // Perform operation in background thread
+        let dest_clone = dest.clone();
+        let dest_retry = dest;
+        let paths_clone = paths.clone();
+        let mode_clone = mode;
+        let progress_info_thread = progress_info.clone();
+        let progress_bar_weak_final = progress_bar.downgrade();
+        let file_view_final = file_view.clone();
+        let clipboard_final = clipboard.clone();
+        
+        // Communication definitions
+        #[derive(Debug, Clone)]
+        enum PasteEvent {
+            Conflict(PathBuf),
+            /// Carries the items that failed, with why
+            Finished(ClipboardMode, Vec<(PathBuf, String)>),
+        }
+        
+        // Worker -> UI (Events)
+        let (event_tx, event_rx) = async_channel::unbounded::<PasteEvent>();
+        // UI -> Worker (Resolution)
+        let (resolve_tx, resolve_rx) = async_channel::unbounded::<ConflictResolution>();
+        
+        // Listen for events on UI thread
+        let window_weak = window.downgrade();
+        
+        glib::spawn_future_local(async move {
+            while let Ok(event) = event_rx.recv().await {
+                match event {
+                    PasteEvent::Finished(mode, failures) => {
+                        // Completed
+                        status_bar_final.end_activity(activity);
+                        if mode == ClipboardMode::Cut {
+                            clipboard_final.borrow_mut().clear();
+                        }
+                        
+                        if let Some(progress_bar) = progress_bar_weak_final.upgrade() {
+                            Self::close_progress_dialog(&progress_bar);
+                        }
+                        
+                        let file_view_refresh = file_view_final.clone();
+                        glib::timeout_add_local(Duration::from_millis(500), move || {
+                            file_view_refresh.refresh();
+                            glib::ControlFlow::Break
+                        });
+
+                        if let (false, Some(window)) = (failures.is_empty(), window_weak.upgrade()) {
+                            let heading = if mode == ClipboardMode::Cut {
+                                "Some Items Could Not Be Moved"
+                            } else {
+                                "Some Items Could Not Be Copied"
+                            };
+                            let parent = window.clone();
+                            ErrorReport::present(&parent, heading, failures, move |failed| {
+                                let job = PasteJob { paths: failed, dest: dest_retry.clone(), mode };
+                                Self::paste_local(&window, &file_view_final, &status_bar_final, job, clipboard_final.clone());
+                            });
+                        }
+                        break;
+                    }
+                    PasteEvent::Conflict(conflicting_path) => {
+                        let file_name = conflicting_path.file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_else(|| "file".to_string());
+                        let resolution = match window_weak.upgrade() {
+                            Some(window) => Self::ask_conflict(&window, file_name).await,
+                            None => ConflictResolution::Cancel,
+                        };
+                        let _ = resolve_tx.send(resolution).await;
+                    }
+                }
+            }
+        });
+        
+        // Spawn worker thread
+        thread::spawn(move || {
+            let (total_bytes, total_files) = FileOperations::calculate_total_size(&paths_clone);
+            {
+                let mut progress = progress_info_thread.lock().unwrap();
+                progress.total_bytes = total_bytes;
+                progress.total_files = total_files;
+            }
+
+            let mut failures = Vec::new();
+            for source in &paths_clone {
+                let file_name: String = match source.file_name() {
+                    Some(name) => name.to_string_lossy().to_string(),
+                    None => continue,
+                };
+                
+                let mut dest_path = dest_clone.join(&file_name);
+                let mut skip_file = false;
+
+                // Resolve conflicts
+                loop {
+                    if !dest_path.exists() {
+                        break;
+                    }
+                    
+                    // Ask UI for help
+                    if event_tx.send_blocking(PasteEvent::Conflict(dest_path.clone())).is_err() {
+                        return;
+                    }
+                    
+                    match resolve_rx.recv_blocking() {
+                        Ok(ConflictResolution::Replace) => break,
+                        Ok(ConflictResolution::Rename(new_name)) => {
+                            dest_path = dest_clone.join(new_name);
+                        },
+                        Ok(ConflictResolution::Cancel) | Err(_) => {
+                            skip_file = true;
+                            break; 
+                        }
+                    }
+                }
+                
+                if skip_file {
+                    // Cancel operation (or just this file? User intent usually implies Abort)
+                    let _ = event_tx.send_blocking(PasteEvent::Finished(mode_clone, failures));
+                    return; 
+                }
+                
+                match mode_clone {
+                    ClipboardMode::Copy => {
+                        if let Err(e) = FileOperations::copy_file_with_progress(
+                            source,
+                            &dest_path,
+                            Some(progress_info_thread.clone()),
+                        ) {
+                            log::warn!("Copy error: {}", e);
+                            failures.push((source.clone(), e.to_string()));
+                        }
+                    }
+                    ClipboardMode::Cut => {
+                        if let Err(e) = FileOperations::move_file_with_progress(
+                            source,
+                            &dest_path,
+                            Some(progress_info_thread.clone()),
+                        ) {
+                            log::warn!("Move error: {}", e);
+                            failures.push((source.clone(), e.to_string()));
+                        }
+                    }
+                    ClipboardMode::None => {}
+                }
+            }
+            
+            // Signal completion
+            let _ = event_tx.send_blocking(PasteEvent::Finished(mode_clone, failures));
+        });
+    }
+
+    /// Tell the user about a recoverable error without interrupting them
+    fn show_error_toast(toast_overlay: &adw::ToastOverlay, message: &str) {
+        let toast = adw::Toast::builder()
+            .title(message)
+            .use_markup(false)
+            .timeout(5)
+            .build();
+        toast_overlay.add_toast(toast);
+    }
+
+    /// Ask whether to replace an item whose name is taken, paste it under another name or stop
+    async fn ask_conflict(window: &adw::ApplicationWindow, file_name: String) -> ConflictResolution {
+        let (response_tx, response_rx) = async_channel::bounded(1);
+        
+        let dialog = adw::AlertDialog::builder()
+            .heading("File Conflict")
+            .body(format!("\"{}\" already exists. What do you want to do?", file_name))
+            .build();
+        
+        dialog.add_response("cancel", "Cancel");
+        dialog.add_response("rename", "Rename");
+        dialog.add_response("replace", "Replace");
+        
+        dialog.set_response_appearance("replace", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("rename"));
+        dialog.set_close_response("cancel");
+        
+        let window_for_dialog = window.clone();
+        
+        dialog.connect_response(None, move |_dialog, response| {
+            if response == "replace" {
+                let _ = response_tx.send_blocking(ConflictResolution::Replace);
+            } else if response == "rename" {
+                // Show rename input dialog
+                let rename_dialog = adw::AlertDialog::builder()
+                    .heading("Rename")
+                    .body("Enter new name for the destination file")
+                    .build();
+                
+                let entry = gtk4::Entry::builder()
+                    .text(&file_name)
+                    .activates_default(true)
+                    .build();
+                entry.add_css_class("nautilus-entry");
+                
+                rename_dialog.set_extra_child(Some(&entry));
+                rename_dialog.add_response("cancel", "Cancel");
+                rename_dialog.add_response("apply", "Rename");
+                rename_dialog.set_response_appearance("apply", adw::ResponseAppearance::Suggested);
+                rename_dialog.set_default_response(Some("apply"));
+                rename_dialog.set_close_response("cancel");
+                
+                let response_tx_inner = response_tx.clone();
+                
+                rename_dialog.connect_response(None, move |d, res| {
+                    if res == "apply" {
+                        if let Some(entry) = d.extra_child().and_downcast::<gtk4::Entry>() {
+                            let new_name = entry.text().to_string();
+                            if !new_name.is_empty() {
+                                let _ = response_tx_inner.send_blocking(ConflictResolution::Rename(new_name));
+                                return;
+                            }
+                        }
+                    }
+                    let _ = response_tx_inner.send_blocking(ConflictResolution::Cancel);
+                });
+                
+                rename_dialog.present(Some(&window_for_dialog));
+                
+            } else {
+                let _ = response_tx.send_blocking(ConflictResolution::Cancel);
+            }
+        });
+        
+        dialog.present(Some(window));
+        
+        // Wait for user response
+        response_rx.recv().await.unwrap_or(ConflictResolution::Cancel)
+    }
+
+    /// Copy or move to or from a remote location through GIO, asking what to do about names that are taken like a local paste does.
+    fn paste_remote(
+        window: &adw::ApplicationWindow,
+        file_view: &FileGridView,
+        status_bar: &StatusBar,
+        job: PasteJob,
+        clipboard: Rc<RefCell<Clipboard>>,
+    ) {
+        let PasteJob { paths, dest, mode } = job;
+        let progress_info = Arc::new(Mutex::new(ProgressInfo {
+            current_file: String::new(),
+            bytes_copied: 0,
+            total_bytes: 0,
+            files_copied: 0,
+            total_files: 0,
+        }));
+
+        let title = if mode == ClipboardMode::Copy { "Copying files..." } else { "Moving files..." };
+        let (progress_bar, status_label, file_label) = Self::create_progress_dialog(window, title);
+        Self::watch_progress(progress_info.clone(), &progress_bar, &status_label, &file_label);
+        let activity = status_bar.begin_activity(title.trim_end_matches('.'));
+
+        let window = window.clone();
+        let file_view = file_view.clone();
+        let status_bar = status_bar.clone();
+        glib::spawn_future_local(async move {
+            let (total_bytes, total_files) = Remote::measure(&paths).await;
+            {
+                let mut progress = progress_info.lock().unwrap();
+                progress.total_bytes = total_bytes;
+                progress.total_files = total_files;
+            }
+
+            let is_move = mode == ClipboardMode::Cut;
+            let failures = Remote::transfer(&paths, &dest, is_move, progress_info, |file_name| {
+                let window = window.clone();
+                async move { Self::ask_conflict(&window, file_name).await }
+            })
+            .await;
+            if is_move {
+                clipboard.borrow_mut().clear();
+            }
+            status_bar.end_activity(activity);
+            Self::close_progress_dialog(&progress_bar);
+            file_view.refresh();
+
+            if failures.is_empty() {
+                return;
+            }
+            let failures: Vec<(PathBuf, String)> = failures
+                .into_iter()
+                .map(|(path, e)| {
+                    log::warn!("{} failed: {}: {}", title.trim_end_matches('.'), path.display(), e);
+                    (path, e.message().to_string())
+                })
+                .collect();
+            let heading = if is_move { "Some Items Could Not Be Moved" } else { "Some Items Could Not Be Copied" };
+            let parent = window.clone();
+            ErrorReport::present(&parent, heading, failures, move |failed| {
+                let job = PasteJob { paths: failed, dest: dest.clone(), mode };
+                Self::paste_remote(&window, &file_view, &status_bar, job, clipboard.clone());
+            });
+        });
+    }
+
+    /// Servers rarely have a trash, so the user is warned that remote items are likely gone for good
+    fn delete_remote(window: &adw::ApplicationWindow, file_view: &FileGridView, paths: Vec<PathBuf>) {
+        let items = if paths.len() == 1 {
+            format!("\"{}\"", Remote::display_name(&paths[0]))
+        } else {
+            format!("{} items", paths.len())
+        };
+        let message = format!("{} will be deleted from the server. Most servers have no trash, so this can't be undone.", items);
+
+        let dialog = adw::AlertDialog::builder()
+            .heading("Delete from Server?")
+            .body(&message)
+            .build();
+        dialog.add_response("cancel", "Cancel");
+        dialog.add_response("delete", "Delete");
+        dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+
+        let window_clone = window.clone();
+        let file_view = file_view.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "delete" {
+                Self::delete_in_background(&window_clone, &file_view, "Some Items Could Not Be Deleted", paths.clone());
+            }
+        });
+
+        dialog.present(Some(window));
+    }
+
+    /// Trash or delete items off the main thread, then list the ones that failed with the option to retry them
+    fn delete_in_background(window: &adw::ApplicationWindow, file_view: &FileGridView, heading: &'static str, paths: Vec<PathBuf>) {
+        let (tx, rx) = async_channel::bounded::<Vec<(PathBuf, String)>>(1);
+        let backends: Vec<_> = paths.iter().map(|path| file_view.backend_for(path)).collect();
+        thread::spawn(move || {
+            let failures = paths
+                .into_iter()
+                .zip(backends)
+                .filter_map(|(path, backend)| {
+                    let result = backend.delete(&path);
+                    result.err().map(|e| (path, e.to_string()))
+                })
+                .collect();
+            let _ = tx.send_blocking(failures);
+        });
+
+        let window = window.clone();
+        let file_view = file_view.clone();
+        glib::spawn_future_local(async move {
+            let Ok(failures) = rx.recv().await else { return };
+            file_view.refresh();
+            if failures.is_empty() {
+                return;
+            }
+            for (path, e) in &failures {
+                log::warn!("Delete error: {}: {}", path.display(), e);
+            }
+            let parent = window.clone();
+            ErrorReport::present(&parent, heading, failures, move |failed| {
+                Self::delete_in_background(&window, &file_view, heading, failed);
+            });
+        });
+    }
+
+    /// Run a file operation off the main thread, then reload the view
+    fn run_in_background<F>(file_view: &FileGridView, failure: &'static str, job: F)
+    where
+        F: FnOnce() -> std::io::Result<()> + Send + 'static,
+    {
+        let (tx, rx) = async_channel::bounded::<std::io::Result<()>>(1);
+        thread::spawn(move || {
+            let _ = tx.send_blocking(job());
+        });
+
+        let file_view = file_view.clone();
+        glib::spawn_future_local(async move {
+            if let Ok(Err(e)) = rx.recv().await {
+                file_view.report_error(format!("{}: {}", failure, e));
+            }
+            file_view.refresh();
+        });
+    }
+
+    fn close_progress_dialog(progress_bar: &ProgressBar) {
+        if let Some(dialog) = progress_bar.parent().and_then(|p| p.parent()) {
+            if let Some(dialog) = dialog.downcast_ref::<adw::Window>() {
+                dialog.close();
+            }
+        }
+    }
+
+    /// Total unpacked size and file count of whole archives
+    fn archives_size(archives: &[PathBuf]) -> (u64, usize) {
+        archives.iter().fold((0, 0), |(bytes, files), archive| {
+            let (b, f) = Archive::member_size(archive, std::path::Path::new(""));
+            (bytes + b, files + f)
+        })
+    }
+
+    /// Run an archive extraction or compression in the background with a progress dialog,
+    /// refreshing the file view once it finishes. `measure` works out the totals on the same
+    /// thread before the job starts.
+    fn run_archive_job<M, F>(
+        window: &adw::ApplicationWindow,
+        file_view: &FileGridView,
+        status_bar: &StatusBar,
+        title: &str,
+        measure: M,
+        job: F,
+    ) where
+        M: FnOnce() -> (u64, usize) + Send + 'static,
+        F: FnOnce(Arc<Mutex<ProgressInfo>>) -> std::io::Result<()> + Send + 'static,
+    {
+        let progress_info = Arc::new(Mutex::new(ProgressInfo {
+            current_file: String::new(),
+            bytes_copied: 0,
+            total_bytes: 0,
+            files_copied: 0,
+            total_files: 0,
+        }));
+
+        let (progress_bar, status_label, file_label) = Self::create_progress_dialog(window, title);
+        Self::watch_progress(progress_info.clone(), &progress_bar, &status_label, &file_label);
+        let activity = status_bar.begin_activity(title.trim_end_matches('.'));
+
+        let (tx, rx) = async_channel::bounded::<std::io::Result<()>>(1);
+        let progress_info_thread = progress_info.clone();
+        thread::spawn(move || {
+            let (total_bytes, total_files) = measure();
+            {
+                let mut progress = progress_info_thread.lock().unwrap();
+                progress.total_bytes = total_bytes;
+                progress.total_files = total_files;
+            }
+            let _ = tx.send_blocking(job(progress_info_thread));
+        });
+
+        let file_view = file_view.clone();
+        let status_bar = status_bar.clone();
+        let title = title.to_string();
+        glib::spawn_future_local(async move {
+            if let Ok(Err(e)) = rx.recv().await {
+                file_view.report_error(format!("{} failed: {}", title.trim_end_matches('.'), e));
+            }
+            status_bar.end_activity(activity);
+            Self::close_progress_dialog(&progress_bar);
+            file_view.refresh();
+        });
+    }
 }